@@ -0,0 +1,132 @@
+use mmd::pmx::reader::*;
+use mmd::{DefaultConfig, Error};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+const VERTICES: i32 = 500_000;
+const TRIANGLES: i32 = 900_000;
+const MATERIALS: i32 = 200;
+const BONES: i32 = 300;
+
+fn text(out: &mut Vec<u8>, s: &str) {
+  let bytes: Vec<u8> = s.encode_utf16().flat_map(u16::to_le_bytes).collect();
+  out.extend_from_slice(&(bytes.len() as i32).to_le_bytes());
+  out.extend_from_slice(&bytes);
+}
+
+fn floats(out: &mut Vec<u8>, count: usize) {
+  for i in 0..count {
+    out.extend_from_slice(&(i as f32).to_le_bytes());
+  }
+}
+
+fn generate(path: &Path) -> std::io::Result<()> {
+  let mut out = Vec::new();
+  out.extend_from_slice(b"PMX ");
+  out.extend_from_slice(&2.0f32.to_le_bytes());
+  out.extend_from_slice(&[8, 0, 0, 4, 1, 1, 2, 2, 1]);
+  for s in &["ベンチ", "bench", "", ""] {
+    text(&mut out, s);
+  }
+
+  out.extend_from_slice(&VERTICES.to_le_bytes());
+  for i in 0..VERTICES {
+    floats(&mut out, 8);
+    out.push(1);
+    out.extend_from_slice(&((i % BONES) as i16).to_le_bytes());
+    out.extend_from_slice(&(((i + 1) % BONES) as i16).to_le_bytes());
+    floats(&mut out, 2);
+  }
+
+  out.extend_from_slice(&(TRIANGLES * 3).to_le_bytes());
+  for i in 0..TRIANGLES * 3 {
+    out.extend_from_slice(&(i % VERTICES).to_le_bytes());
+  }
+
+  out.extend_from_slice(&1i32.to_le_bytes());
+  text(&mut out, "texture.png");
+
+  out.extend_from_slice(&MATERIALS.to_le_bytes());
+  for i in 0..MATERIALS {
+    text(&mut out, &format!("材質{}", i));
+    text(&mut out, &format!("material {}", i));
+    floats(&mut out, 11);
+    out.push(0b0001_0000);
+    floats(&mut out, 5);
+    out.extend_from_slice(&[0, 0xff, 0, 1, 2]);
+    text(&mut out, "");
+    out.extend_from_slice(&(TRIANGLES * 3 / MATERIALS).to_le_bytes());
+  }
+
+  out.extend_from_slice(&BONES.to_le_bytes());
+  for i in 0..BONES {
+    text(&mut out, &format!("ボーン{}", i));
+    text(&mut out, &format!("bone {}", i));
+    floats(&mut out, 3);
+    out.extend_from_slice(&((i - 1) as i16).to_le_bytes());
+    out.extend_from_slice(&0i32.to_le_bytes());
+    out.extend_from_slice(&0b0000_0000_0001_1111u16.to_le_bytes());
+    out.extend_from_slice(&(((i + 1) % BONES) as i16).to_le_bytes());
+  }
+
+  let mut file = BufWriter::new(File::create(path)?);
+  file.write_all(&out)?;
+  file.flush()
+}
+
+fn bones(path: &Path, skip: bool) -> Result<usize, Error> {
+  let header = HeaderReader::new(BufReader::new(File::open(path)?))?;
+  let mut vertices = VertexReader::new(header)?;
+  if skip {
+    vertices.skip()?;
+  }
+  let mut surfaces = SurfaceReader::new(vertices)?;
+  if skip {
+    surfaces.skip()?;
+  }
+  let mut textures = TextureReader::new(surfaces)?;
+  if skip {
+    textures.skip()?;
+  }
+  let mut materials = MaterialReader::new(textures)?;
+  if skip {
+    materials.skip()?;
+  }
+  let mut bones = BoneReader::new(materials)?;
+  let names = bones
+    .iter::<DefaultConfig>()
+    .map(|b| b.map(|b| b.universal_name))
+    .collect::<Result<Vec<_>, _>>()?;
+  Ok(names.len())
+}
+
+fn measure(path: &Path, skip: bool) -> Result<Duration, Error> {
+  const RUNS: u32 = 5;
+  let mut best = Duration::from_secs(u64::MAX);
+  for _ in 0..RUNS {
+    let start = Instant::now();
+    assert_eq!(bones(path, skip)?, BONES as usize);
+    best = best.min(start.elapsed());
+  }
+  Ok(best)
+}
+
+fn main() -> Result<(), Error> {
+  let path = std::env::temp_dir().join("mmd-skip-bench.pmx");
+  generate(&path)?;
+
+  let decode = measure(&path, false)?;
+  let skip = measure(&path, true)?;
+  println!(
+    "bones of a {} vertex model: decode {:?}, skip {:?} ({:.1}x)",
+    VERTICES,
+    decode,
+    skip,
+    decode.as_secs_f64() / skip.as_secs_f64()
+  );
+
+  std::fs::remove_file(&path)?;
+  Ok(())
+}
@@ -43,5 +43,29 @@ fn main() -> Result<(), Error> {
     println!("\n{}) {}", i, b?);
   }
 
+  let mut display_frames = DisplayFrameReader::<_>::new(morphs)?;
+  println!("\n\nDisplay frames:");
+  for (i, d) in display_frames.iter::<DefaultConfig>().enumerate() {
+    println!("\n{}) {}", i, d?);
+  }
+
+  let mut rigid_bodies = RigidBodyReader::<_>::new(display_frames)?;
+  println!("\n\nRigid bodies:");
+  for (i, r) in rigid_bodies.iter::<DefaultConfig>().enumerate() {
+    println!("\n{}) {}", i, r?);
+  }
+
+  let mut joints = JointReader::<_>::new(rigid_bodies)?;
+  println!("\n\nJoints:");
+  for (i, j) in joints.iter::<DefaultConfig>().enumerate() {
+    println!("\n{}) {}", i, j?);
+  }
+
+  let mut soft_bodies = SoftBodyReader::<_>::new(joints)?;
+  println!("\n\nSoft bodies:");
+  for (i, s) in soft_bodies.iter::<DefaultConfig>().enumerate() {
+    println!("\n{}) {}", i, s?);
+  }
+
   Ok(())
 }
@@ -0,0 +1,21 @@
+use mmd::{DefaultConfig, Error, Model, TextEncoding};
+use std::env;
+
+fn main() -> Result<(), Error> {
+  let args = env::args().collect::<Vec<_>>();
+  if args.len() < 3 {
+    eprintln!("Usage: optimize <input.pmx> <output.pmx> [utf8|utf16]");
+    return Ok(());
+  }
+
+  let mut model = Model::<DefaultConfig>::open(&args[1])?;
+  println!("Before: {}", model.settings);
+  model.optimize();
+  match args.get(3).map(String::as_str) {
+    Some("utf8") => model.reencode(TextEncoding::UTF8),
+    Some("utf16") => model.reencode(TextEncoding::UTF16LE),
+    _ => {}
+  }
+  println!("After: {}", model.settings);
+  model.save(&args[2])
+}
@@ -0,0 +1,22 @@
+use mmd::pmx::text;
+use mmd::Error;
+use std::env;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+
+fn main() -> Result<(), Error> {
+  let args = env::args().collect::<Vec<_>>();
+  if args.len() < 3 {
+    eprintln!("Usage: text <input.pmx|input.json> <output.json|output.pmx>");
+    return Ok(());
+  }
+
+  let read = BufReader::new(File::open(&args[1])?);
+  let write = BufWriter::new(File::create(&args[2])?);
+  if args[1].ends_with(".json") {
+    text::text_to_pmx(read, write)?;
+  } else {
+    text::pmx_to_text(read, write)?;
+  }
+  Ok(())
+}
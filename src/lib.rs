@@ -1,17 +1,38 @@
 #![deny(warnings)]
 
+pub mod pmd;
 pub mod pmx;
+pub mod vmd;
+pub mod vpd;
 
+#[cfg(feature = "async")]
+pub use self::pmx::async_reader::{
+  self, AsyncBoneReader, AsyncDisplayFrameReader, AsyncHeaderReader, AsyncJointReader,
+  AsyncMaterialReader, AsyncMorphReader, AsyncReader, AsyncRigidBodyReader, AsyncSoftBodyReader,
+  AsyncSurfaceReader, AsyncTextureReader, AsyncVertexReader, StreamBuffer,
+};
 pub use self::pmx::bone::Bone;
-pub use self::pmx::error::{Error, Result};
+pub use self::pmx::display_frame::DisplayFrame;
+pub use self::pmx::error::{Error, Location, Result, Section};
+pub use self::pmx::index::PmxIndex;
+pub use self::pmx::joint::Joint;
+pub use self::pmx::limits::ParseLimits;
 pub use self::pmx::material::Material;
+pub use self::pmx::model::Model;
 pub use self::pmx::reader::{
-  self, BoneReader, HeaderReader, MaterialReader, MorphReader, SurfaceReader, TextureReader,
-  VertexReader,
+  self, BoneReader, DisplayFrameReader, HeaderReader, JointReader, MaterialReader, MorphReader,
+  RigidBodyReader, SoftBodyReader, SurfaceReader, TextureReader, VertexReader,
 };
+pub use self::pmx::rigid_body::RigidBody;
 pub use self::pmx::settings::Settings;
+pub use self::pmx::soft_body::SoftBody;
 pub use self::pmx::types::*;
 pub use self::pmx::vertex::Vertex;
+pub use self::pmx::view::{self, ModelView, Text};
 pub use self::pmx::weight_deform::WeightDeform;
+pub use self::pmx::writer::{
+  self, BoneWriter, DisplayFrameWriter, HeaderWriter, JointWriter, MaterialWriter, MorphWriter,
+  RigidBodyWriter, SoftBodyWriter, SurfaceWriter, TextureWriter, VertexWriter,
+};
 
 mod display;
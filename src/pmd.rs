@@ -0,0 +1,11 @@
+pub mod convert;
+pub mod model;
+pub mod reader;
+pub mod types;
+
+pub use self::model::Model;
+pub use self::reader::{
+  BoneReader, DisplayReader, EnglishReader, HeaderReader, IkReader, JointReader, MaterialReader,
+  MorphReader, RigidBodyReader, SurfaceReader, ToonReader, VertexReader,
+};
+pub use self::types::*;
@@ -0,0 +1,449 @@
+use crate::{
+  pmd::{self, BoneKind},
+  pmx::bone::*,
+  pmx::display_frame::{DisplayFrame, FrameElement},
+  pmx::joint::JointType,
+  pmx::material::{DrawingFlags, EnvironmentBlendMode, Toon},
+  pmx::morph::*,
+  pmx::weight_deform::*,
+  Config, Error, IndexSize, Joint, Material, Model, Result, RigidBody, Section, Settings,
+  TextEncoding, Vertex,
+};
+use enumflags2::BitFlags;
+use std::convert::{TryFrom, TryInto};
+
+impl<C: Config> pmd::Model<C> {
+  // Follows PmxEditor's PMD import: the base morph is folded into vertex morphs, bone types
+  // become flags, sphere maps and toon textures move into the texture list.
+  pub fn to_pmx(&self) -> Result<Model<C>> {
+    let mut textures = Vec::new();
+    let materials = self.materials(&mut textures)?;
+    let mut model = Model {
+      version: 2.0,
+      // replaced by the optimal settings once everything is converted
+      settings: Settings {
+        text_encoding: TextEncoding::UTF16LE,
+        additional_vec4_count: 0,
+        vertex_index_size: IndexSize::I32,
+        texture_index_size: IndexSize::I32,
+        material_index_size: IndexSize::I32,
+        bone_index_size: IndexSize::I32,
+        morph_index_size: IndexSize::I32,
+        rigidbody_index_size: IndexSize::I32,
+      },
+      extra_globals: Vec::new(),
+      model_local_name: self.local_name.clone(),
+      model_universal_name: self.universal_name.clone(),
+      local_comments: self.local_comment.clone(),
+      universal_comments: self.universal_comment.clone(),
+      vertices: self.vertices.iter().map(vertex).collect(),
+      surfaces: self.surfaces.clone(),
+      textures,
+      materials,
+      bones: self.bones(),
+      morphs: self.morphs()?,
+      display_frames: self.display_frames()?,
+      rigid_bodies: self.rigid_bodies(),
+      joints: self.joints.iter().map(joint).collect(),
+      soft_bodies: Vec::new(),
+    };
+    model.optimize();
+    Ok(model)
+  }
+
+  fn materials(&self, textures: &mut Vec<String>) -> Result<Vec<Material<C>>> {
+    let mut texture = |path: &str| -> Result<C::TextureIndex> {
+      let i = match textures.iter().position(|t| t == path) {
+        Some(i) => i,
+        None => {
+          textures.push(path.to_string());
+          textures.len() - 1
+        }
+      };
+      index(i)
+    };
+
+    let mut materials = Vec::with_capacity(self.materials.len());
+    for (i, m) in self.materials.iter().enumerate() {
+      let mut texture_index = index(-1)?;
+      let mut environment_index = index(-1)?;
+      let mut environment_blend_mode = EnvironmentBlendMode::Disabled;
+      for path in m.texture.split('*').filter(|p| !p.is_empty()) {
+        let extension = path.rsplit('.').next().unwrap_or("").to_ascii_lowercase();
+        match extension.as_str() {
+          "sph" => {
+            environment_index = texture(path)?;
+            environment_blend_mode = EnvironmentBlendMode::Multiply;
+          }
+          "spa" => {
+            environment_index = texture(path)?;
+            environment_blend_mode = EnvironmentBlendMode::Additive;
+          }
+          _ => texture_index = texture(path)?,
+        }
+      }
+
+      // the default toon01.bmp to toon10.bmp are the shared toons of PMX
+      let toon = match m.toon {
+        None => Toon::Texture(index(-1)?),
+        Some(toon) => {
+          let path = match self.toon_textures.get(toon as usize) {
+            Some(path) => path.clone(),
+            None => format!("toon{:02}.bmp", toon as u32 + 1),
+          };
+          if path == format!("toon{:02}.bmp", toon as u32 + 1) && toon < 10 {
+            Toon::Internal(toon)
+          } else {
+            Toon::Texture(texture(&path)?)
+          }
+        }
+      };
+
+      let alpha = m.diffuse_color.as_ref()[3];
+      let mut draw_flags = BitFlags::from(DrawingFlags::GroundShadow);
+      if alpha < 1.0 {
+        draw_flags |= DrawingFlags::NoCull;
+      }
+      // MMD turns off self shadowing for this exact alpha
+      if (alpha - 0.98).abs() > f32::EPSILON {
+        draw_flags |= DrawingFlags::DrawShadow | DrawingFlags::ReceiveShadow;
+      }
+      if m.edge {
+        draw_flags |= DrawingFlags::HasEdge;
+      }
+
+      materials.push(Material {
+        local_name: format!("材質{}", i + 1),
+        universal_name: format!("Material{}", i + 1),
+        diffuse_color: m.diffuse_color.clone(),
+        specular_color: m.specular_color.clone(),
+        specular_strength: m.specular_strength,
+        ambient_color: m.ambient_color.clone(),
+        draw_flags,
+        edge_color: [0.0, 0.0, 0.0, 1.0].into(),
+        edge_scale: 1.0,
+        texture_index,
+        environment_index,
+        environment_blend_mode,
+        toon,
+        metadata: String::new(),
+        surface_count: m.surface_count,
+      });
+    }
+    Ok(materials)
+  }
+
+  fn bones(&self) -> Vec<Bone<C>> {
+    let mut bones = Vec::with_capacity(self.bones.len());
+    for b in &self.bones {
+      let mut bone_flags = BitFlags::from(BoneFlags::Rotatable);
+      match b.kind {
+        BoneKind::Hidden | BoneKind::IkTarget | BoneKind::Invisible => {}
+        _ => bone_flags |= BoneFlags::Display | BoneFlags::CanOperate,
+      }
+      if let BoneKind::RotateMove | BoneKind::Ik = b.kind {
+        bone_flags |= BoneFlags::Movable;
+      }
+
+      let tail = position(&b.tail).filter(|&t| t > 0 && t < self.bones.len());
+      let connection = match (b.kind, tail) {
+        (BoneKind::RotationFollow, _) | (_, None) => Connection::Position([0.0; 3].into()),
+        (_, Some(_)) => {
+          bone_flags |= BoneFlags::Connection;
+          Connection::Index(b.tail.clone())
+        }
+      };
+
+      let additional = match b.kind {
+        BoneKind::RotationInfluenced => Some(1.0),
+        BoneKind::RotationFollow => b
+          .tail
+          .clone()
+          .try_into()
+          .ok()
+          .map(|t: i32| t as f32 / 100.0),
+        _ => None,
+      }
+      .map(|rate| {
+        bone_flags |= BoneFlags::AddRotation;
+        Additional {
+          parent: b.ik_parent.clone(),
+          rate,
+        }
+      });
+
+      let fixed_axis = match (b.kind, tail) {
+        (BoneKind::Twist, Some(t)) => {
+          let axis = sub(&self.bones[t].position, &b.position);
+          let length = axis.iter().map(|v| v * v).sum::<f32>().sqrt();
+          (length > 0.0).then(|| {
+            bone_flags |= BoneFlags::FixedAxis;
+            axis.map(|v| v / length).into()
+          })
+        }
+        _ => None,
+      };
+
+      bones.push(Bone {
+        local_name: b.local_name.clone(),
+        universal_name: b.universal_name.clone(),
+        position: b.position.clone(),
+        parent: b.parent.clone(),
+        transform_level: 0,
+        bone_flags,
+        unknown_flags: 0,
+        connection,
+        additional,
+        fixed_axis,
+        local_axis: None,
+        external_parent_transform: None,
+        inverse_kinematics: None,
+      });
+    }
+
+    for ik in &self.iks {
+      let bone = match position(&ik.bone).and_then(|i| bones.get_mut(i)) {
+        Some(bone) => bone,
+        None => continue,
+      };
+      // PmxEditor limits knees to bending backwards only
+      let links = ik
+        .chain
+        .iter()
+        .map(|link| {
+          let knee = position(link)
+            .and_then(|i| self.bones.get(i))
+            .is_some_and(|b| b.local_name.contains("ひざ"));
+          IKLink {
+            ik_bone: link.clone(),
+            limits: knee.then(|| {
+              (
+                [-std::f32::consts::PI, 0.0, 0.0].into(),
+                [(-0.5f32).to_radians(), 0.0, 0.0].into(),
+              )
+            }),
+            raw_limits: knee as u8,
+          }
+        })
+        .collect();
+      bone.bone_flags |= BoneFlags::InverseKinematics;
+      bone.inverse_kinematics = Some(InverseKinematics {
+        ik_bone: ik.target.clone(),
+        iterations: ik.iterations.into(),
+        limit_angle: ik.control_weight * 4.0,
+        links,
+      });
+    }
+    bones
+  }
+
+  fn base(&self) -> Option<usize> {
+    self.morphs.iter().position(|m| m.panel == Panel::Hidden)
+  }
+
+  fn morphs(&self) -> Result<Vec<Morph<C>>> {
+    let base = self.base().map(|b| &self.morphs[b].offsets);
+    let mut morphs = Vec::with_capacity(self.morphs.len());
+    for morph in &self.morphs {
+      if morph.panel == Panel::Hidden {
+        continue;
+      }
+      let offsets = morph
+        .offsets
+        .iter()
+        .map(|o| {
+          let vertex = match base {
+            Some(base) => {
+              let i = position(&o.vertex).filter(|&i| i < base.len());
+              let i = i.ok_or_else(|| out_of_range(Section::Morphs, &o.vertex, base.len()))?;
+              base[i].vertex.clone()
+            }
+            None => o.vertex.clone(),
+          };
+          Ok(VertexOffset {
+            vertex,
+            offset: o.offset.clone(),
+          })
+        })
+        .collect::<Result<_>>()?;
+      morphs.push(Morph {
+        local_name: morph.local_name.clone(),
+        universal_name: morph.universal_name.clone(),
+        panel: morph.panel,
+        offsets: Offsets::Vertex(offsets),
+      });
+    }
+    Ok(morphs)
+  }
+
+  // PMX morph indices, shifted down past the dropped base morph
+  fn morph_index(&self, morph: &C::MorphIndex) -> Result<C::MorphIndex> {
+    let i = position(morph)
+      .filter(|&i| i < self.morphs.len() && Some(i) != self.base())
+      .ok_or_else(|| out_of_range(Section::DisplayLists, morph, self.morphs.len()))?;
+    index(i - self.base().map_or(0, |b| (b < i) as usize))
+  }
+
+  fn display_frames(&self) -> Result<Vec<DisplayFrame<C>>> {
+    let mut frames = vec![
+      DisplayFrame {
+        local_name: "Root".to_string(),
+        universal_name: "Root".to_string(),
+        special: true,
+        raw_special: 1,
+        elements: if self.bones.is_empty() {
+          Vec::new()
+        } else {
+          vec![FrameElement::Bone(index(0)?)]
+        },
+      },
+      DisplayFrame {
+        local_name: "表情".to_string(),
+        universal_name: "Exp".to_string(),
+        special: true,
+        raw_special: 1,
+        elements: self
+          .morph_display
+          .iter()
+          .map(|m| self.morph_index(m).map(FrameElement::Morph))
+          .collect::<Result<_>>()?,
+      },
+    ];
+    for frame in &self.bone_frames {
+      frames.push(DisplayFrame {
+        local_name: frame.local_name.trim_end().to_string(),
+        universal_name: frame.universal_name.trim_end().to_string(),
+        special: false,
+        raw_special: 0,
+        elements: Vec::new(),
+      });
+    }
+    for display in &self.bone_display {
+      match frames.get_mut(display.frame as usize + 1) {
+        Some(frame) if display.frame > 0 => frame
+          .elements
+          .push(FrameElement::Bone(display.bone.clone())),
+        _ => {
+          return Err(Error::ElementOutOfRange {
+            section: Section::DisplayLists,
+            index: display.frame as usize,
+            count: self.bone_frames.len() as i32,
+          })
+        }
+      }
+    }
+    Ok(frames)
+  }
+
+  fn rigid_bodies(&self) -> Vec<RigidBody<C>> {
+    self
+      .rigid_bodies
+      .iter()
+      .map(|r| {
+        // positions are relative to the bone, or to the first bone when there is none
+        let bone = position(&r.bone_index)
+          .or(Some(0))
+          .and_then(|i| self.bones.get(i));
+        let shape_position = match bone {
+          Some(bone) => add(&r.shape_position, &bone.position).into(),
+          None => r.shape_position.clone(),
+        };
+        RigidBody {
+          local_name: r.local_name.clone(),
+          universal_name: String::new(),
+          bone_index: r.bone_index.clone(),
+          group: r.group,
+          non_collision_mask: r.non_collision_mask,
+          shape: r.shape,
+          shape_size: r.shape_size.clone(),
+          shape_position,
+          shape_rotation: r.shape_rotation.clone(),
+          mass: r.mass,
+          move_attenuation: r.move_attenuation,
+          rotation_damping: r.rotation_damping,
+          repulsion: r.repulsion,
+          friction: r.friction,
+          physics_mode: r.physics_mode,
+        }
+      })
+      .collect()
+  }
+}
+
+fn vertex<C: Config>(v: &pmd::Vertex<C>) -> Vertex<C> {
+  let [bone_1, bone_2] = &v.bones;
+  let weight_deform = if bone_1 == bone_2 || v.weight >= 100 {
+    WeightDeform::Bdef1(Bdef1 {
+      bone_index: bone_1.clone(),
+    })
+  } else if v.weight == 0 {
+    WeightDeform::Bdef1(Bdef1 {
+      bone_index: bone_2.clone(),
+    })
+  } else {
+    WeightDeform::Bdef2(Bdef2 {
+      bone_1_index: bone_1.clone(),
+      bone_2_index: bone_2.clone(),
+      bone_1_weight: v.weight as f32 / 100.0,
+    })
+  };
+  Vertex {
+    position: v.position.clone(),
+    normal: v.normal.clone(),
+    uv: v.uv.clone(),
+    additional: std::iter::empty().collect(),
+    weight_deform,
+    edge_scale: if v.edge { 1.0 } else { 0.0 },
+  }
+}
+
+fn joint<C: Config>(j: &pmd::Joint<C>) -> Joint<C> {
+  Joint {
+    local_name: j.local_name.clone(),
+    universal_name: String::new(),
+    joint_type: JointType::Spring6Dof,
+    rigid_body_a: j.rigid_body_a.clone(),
+    rigid_body_b: j.rigid_body_b.clone(),
+    position: j.position.clone(),
+    rotation: j.rotation.clone(),
+    position_min: j.position_min.clone(),
+    position_max: j.position_max.clone(),
+    rotation_min: j.rotation_min.clone(),
+    rotation_max: j.rotation_max.clone(),
+    position_spring: j.position_spring.clone(),
+    rotation_spring: j.rotation_spring.clone(),
+  }
+}
+
+fn position<I: TryInto<i32> + Clone>(index: &I) -> Option<usize> {
+  index
+    .clone()
+    .try_into()
+    .ok()
+    .and_then(|i: i32| usize::try_from(i).ok())
+}
+
+fn index<I: TryFrom<i32>, N: TryInto<i32>>(i: N) -> Result<I> {
+  i.try_into()
+    .ok()
+    .and_then(|i| I::try_from(i).ok())
+    .ok_or(Error::IndexOverflow(i64::MAX))
+}
+
+fn out_of_range<I: TryInto<i32> + Clone>(section: Section, i: &I, count: usize) -> Error {
+  Error::ElementOutOfRange {
+    section,
+    index: position(i).unwrap_or(usize::MAX),
+    count: count as i32,
+  }
+}
+
+fn add<V: AsRef<[f32]>>(a: &V, b: &V) -> [f32; 3] {
+  let (a, b) = (a.as_ref(), b.as_ref());
+  [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn sub<V: AsRef<[f32]>>(a: &V, b: &V) -> [f32; 3] {
+  let (a, b) = (a.as_ref(), b.as_ref());
+  [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
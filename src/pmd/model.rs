@@ -0,0 +1,126 @@
+use crate::{
+  pmd::{reader::*, types::*},
+  Config, ParseLimits, Result,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(bound(
+    serialize = "C::VertexIndex: Serialize, C::BoneIndex: Serialize, C::MorphIndex: Serialize, C::RigidbodyIndex: Serialize, C::Vec2: Serialize, C::Vec3: Serialize, C::Vec4: Serialize",
+    deserialize = "C::VertexIndex: Deserialize<'de>, C::BoneIndex: Deserialize<'de>, C::MorphIndex: Deserialize<'de>, C::RigidbodyIndex: Deserialize<'de>, C::Vec2: Deserialize<'de>, C::Vec3: Deserialize<'de>, C::Vec4: Deserialize<'de>"
+  ))
+)]
+pub struct Model<C: Config> {
+  pub version: f32,
+  pub local_name: String,
+  pub universal_name: String,
+  pub local_comment: String,
+  pub universal_comment: String,
+  pub vertices: Vec<Vertex<C>>,
+  pub surfaces: Vec<[C::VertexIndex; 3]>,
+  pub materials: Vec<Material<C>>,
+  pub bones: Vec<Bone<C>>,
+  pub iks: Vec<Ik<C>>,
+  pub morphs: Vec<Morph<C>>,
+  pub morph_display: Vec<C::MorphIndex>,
+  pub bone_frames: Vec<BoneFrame>,
+  pub bone_display: Vec<BoneDisplay<C>>,
+  pub toon_textures: Vec<String>,
+  pub rigid_bodies: Vec<RigidBody<C>>,
+  pub joints: Vec<Joint<C>>,
+}
+
+impl<C: Config> Model<C> {
+  pub fn open<P: AsRef<Path>>(path: P) -> Result<Model<C>> {
+    Self::read(BufReader::new(File::open(path)?))
+  }
+
+  pub fn read<R: Read>(read: R) -> Result<Model<C>> {
+    Self::read_with_limits(read, ParseLimits::default())
+  }
+
+  pub fn read_with_limits<R: Read>(read: R, limits: ParseLimits) -> Result<Model<C>> {
+    let header = HeaderReader::with_limits(read, limits)?;
+    let version = header.version;
+    let local_name = header.model_name.clone();
+    let local_comment = header.comment.clone();
+
+    let mut vertices = VertexReader::new(header)?;
+    let vertex_list = vertices.iter::<C>().collect::<Result<_>>()?;
+
+    let mut surfaces = SurfaceReader::new(vertices)?;
+    let surface_list = surfaces.iter::<C>().collect::<Result<_>>()?;
+
+    let mut materials = MaterialReader::new(surfaces)?;
+    let material_list = materials.iter::<C>().collect::<Result<_>>()?;
+
+    let mut bones = BoneReader::new(materials)?;
+    let mut bone_list: Vec<_> = bones.iter::<C>().collect::<Result<_>>()?;
+
+    let mut iks = IkReader::new(bones)?;
+    let ik_list = iks.iter::<C>().collect::<Result<_>>()?;
+
+    let mut morphs = MorphReader::new(iks)?;
+    let mut morph_list: Vec<_> = morphs.iter::<C>().collect::<Result<_>>()?;
+
+    let mut display = DisplayReader::new(morphs)?;
+    let mut display_lists = display.next::<C>()?.unwrap_or_else(|| DisplayLists {
+      morphs: Vec::new(),
+      bone_frames: Vec::new(),
+      bones: Vec::new(),
+    });
+
+    let mut english = EnglishReader::new(display)?;
+    let english_names = english.next()?.unwrap_or_default();
+
+    let mut toons = ToonReader::new(english)?;
+    let toon_textures = toons.next()?.unwrap_or_default();
+
+    let mut rigid_bodies = RigidBodyReader::new(toons)?;
+    let rigid_body_list = rigid_bodies.iter::<C>().collect::<Result<_>>()?;
+
+    let mut joints = JointReader::new(rigid_bodies)?;
+    let joint_list = joints.iter::<C>().collect::<Result<_>>()?;
+
+    for (bone, name) in bone_list.iter_mut().zip(english_names.bones) {
+      bone.universal_name = name;
+    }
+    for (morph, name) in morph_list.iter_mut().skip(1).zip(english_names.morphs) {
+      morph.universal_name = name;
+    }
+    for (frame, name) in display_lists
+      .bone_frames
+      .iter_mut()
+      .zip(english_names.bone_frames)
+    {
+      frame.universal_name = name;
+    }
+
+    Ok(Model {
+      version,
+      local_name,
+      universal_name: english_names.model_name,
+      local_comment,
+      universal_comment: english_names.comment,
+      vertices: vertex_list,
+      surfaces: surface_list,
+      materials: material_list,
+      bones: bone_list,
+      iks: ik_list,
+      morphs: morph_list,
+      morph_display: display_lists.morphs,
+      bone_frames: display_lists.bone_frames,
+      bone_display: display_lists.bones,
+      toon_textures,
+      rigid_bodies: rigid_body_list,
+      joints: joint_list,
+    })
+  }
+}
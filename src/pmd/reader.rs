@@ -0,0 +1,198 @@
+pub mod bone;
+pub mod display;
+pub mod extension;
+pub mod header;
+pub mod ik;
+pub mod joint;
+pub mod material;
+pub mod morph;
+pub mod rigid_body;
+pub mod surface;
+pub mod vertex;
+
+pub use bone::BoneReader;
+pub use display::DisplayReader;
+pub use extension::{EnglishReader, ToonReader};
+pub use header::HeaderReader;
+pub use ik::IkReader;
+pub use joint::JointReader;
+pub use material::MaterialReader;
+pub use morph::MorphReader;
+pub use rigid_body::RigidBodyReader;
+pub use surface::SurfaceReader;
+pub use vertex::VertexReader;
+
+use crate::{
+  reader::{helpers::ReadHelpers, limited::LimitedRead},
+  Error, Index, Result,
+};
+use byteorder::{ReadBytesExt, LE};
+use encoding::all::WINDOWS_31J;
+use encoding::{DecoderTrap, Encoding};
+use std::convert::TryFrom;
+use std::io::Read;
+
+// Element counts needed by the english name block near the end of the file
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Counts {
+  pub bones: usize,
+  pub morphs: usize,
+  pub bone_frames: usize,
+}
+
+pub(crate) trait PmdReadHelpers: ReadHelpers {
+  // Names are fixed size Shift-JIS fields, anything after the first NUL is padding. Editors and
+  // MMD cut names to the field size without regard for characters, so a dangling lead byte at
+  // the end is dropped rather than rejected.
+  fn read_name(&mut self, size: usize) -> Result<String> {
+    let mut buf = vec![0u8; size];
+    self.read_exact(&mut buf)?;
+    let end = buf.iter().position(|&b| b == 0).unwrap_or(size);
+    WINDOWS_31J
+      .decode(&buf[..end], DecoderTrap::Strict)
+      .or_else(|e| match end {
+        0 => Err(e),
+        _ => WINDOWS_31J
+          .decode(&buf[..end - 1], DecoderTrap::Strict)
+          .map_err(|_| e),
+      })
+      .map_err(Error::DecodeText)
+  }
+
+  // 0xFFFF stands for no element
+  fn read_u16_index<I: Index>(&mut self) -> Result<I> {
+    match self.read_u16::<LE>()? {
+      0xFFFF => I::try_from(-1i16).map_err(|_| Error::IndexOverflow(-1)),
+      v => I::try_from(v as i32).map_err(|_| Error::IndexOverflow(v.into())),
+    }
+  }
+
+  fn read_u32_index<I: TryFrom<i32>>(&mut self) -> Result<I> {
+    let v = self.read_u32::<LE>()?;
+    i32::try_from(v)
+      .ok()
+      .and_then(|v| I::try_from(v).ok())
+      .ok_or(Error::IndexOverflow(v.into()))
+  }
+
+  // Sections past the morph display lists were added later and may be missing entirely
+  fn at_end(&mut self, buf: &mut [u8]) -> Result<bool> {
+    let read = self.read(buf)?;
+    if read == 0 {
+      return Ok(true);
+    }
+    self.read_exact(&mut buf[read..])?;
+    Ok(false)
+  }
+
+  fn read_optional_count(&mut self) -> Result<i32> {
+    let mut buf = [0u8; 4];
+    if self.at_end(&mut buf)? {
+      return Ok(0);
+    }
+    let count = i32::from_le_bytes(buf);
+    if count < 0 {
+      return Err(Error::NegativeCount(count));
+    }
+    Ok(count)
+  }
+}
+
+impl<R: Read> PmdReadHelpers for LimitedRead<R> {}
+
+// next and iter are the same for every element reader, only next_impl differs
+macro_rules! element_reader {
+  ($reader:ident, $iterator:ident, $section:expr, $item:ty) => {
+    impl<R: std::io::Read> $reader<R> {
+      #[allow(clippy::should_implement_trait)]
+      pub fn next<C: crate::Config>(&mut self) -> crate::Result<Option<$item>> {
+        if self.poison {
+          return Err(crate::Error::Poisoned);
+        }
+        let element = (self.count - self.remaining) as usize;
+        let offset = self.read.position;
+        let result = self.next_impl::<C>();
+        if result.is_err() {
+          self.poison = true;
+        }
+        result.map_err(|e| e.at($section, Some(element), offset))
+      }
+
+      pub fn iter<C: crate::Config>(&mut self) -> $iterator<'_, R, C> {
+        $iterator {
+          reader: self,
+          phantom: std::marker::PhantomData,
+        }
+      }
+    }
+
+    pub struct $iterator<'a, R, C = crate::DefaultConfig> {
+      reader: &'a mut $reader<R>,
+      phantom: std::marker::PhantomData<C>,
+    }
+
+    impl<R: std::io::Read, C: crate::Config> Iterator for $iterator<'_, R, C> {
+      type Item = crate::Result<$item>;
+
+      fn next(&mut self) -> Option<Self::Item> {
+        self
+          .reader
+          .next::<C>()
+          .map_or_else(|e| Some(Err(e)), |v| v.map(Ok))
+      }
+
+      fn size_hint(&self) -> (usize, Option<usize>) {
+        (
+          self.reader.remaining as usize,
+          Some(self.reader.remaining as usize),
+        )
+      }
+    }
+  };
+  // for elements without any index or vector in them
+  ($reader:ident, $iterator:ident, $section:expr, $item:ty, plain) => {
+    impl<R: std::io::Read> $reader<R> {
+      #[allow(clippy::should_implement_trait)]
+      pub fn next(&mut self) -> crate::Result<Option<$item>> {
+        if self.poison {
+          return Err(crate::Error::Poisoned);
+        }
+        let element = (self.count - self.remaining) as usize;
+        let offset = self.read.position;
+        let result = self.next_impl();
+        if result.is_err() {
+          self.poison = true;
+        }
+        result.map_err(|e| e.at($section, Some(element), offset))
+      }
+
+      pub fn iter(&mut self) -> $iterator<'_, R> {
+        $iterator { reader: self }
+      }
+    }
+
+    pub struct $iterator<'a, R> {
+      reader: &'a mut $reader<R>,
+    }
+
+    impl<R: std::io::Read> Iterator for $iterator<'_, R> {
+      type Item = crate::Result<$item>;
+
+      fn next(&mut self) -> Option<Self::Item> {
+        self
+          .reader
+          .next()
+          .map_or_else(|e| Some(Err(e)), |v| v.map(Ok))
+      }
+
+      fn size_hint(&self) -> (usize, Option<usize>) {
+        (
+          self.reader.remaining as usize,
+          Some(self.reader.remaining as usize),
+        )
+      }
+    }
+  };
+}
+
+pub(crate) use element_reader;
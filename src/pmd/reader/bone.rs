@@ -0,0 +1,66 @@
+use crate::{
+  pmd::reader::{element_reader, Counts, MaterialReader, PmdReadHelpers},
+  pmd::{Bone, BoneKind},
+  pmx::error::Section,
+  reader::{helpers::ReadHelpers, limited::LimitedRead},
+  Config, DefaultConfig, Error, Result,
+};
+use byteorder::{ReadBytesExt, LE};
+use std::io::Read;
+
+pub struct BoneReader<R> {
+  pub version: f32,
+  pub count: i32,
+  pub remaining: i32,
+  pub(crate) read: LimitedRead<R>,
+  pub(crate) poison: bool,
+  pub(crate) counts: Counts,
+}
+
+impl<R: Read> BoneReader<R> {
+  pub fn new(mut m: MaterialReader<R>) -> Result<BoneReader<R>> {
+    if m.poison {
+      return Err(Error::Poisoned);
+    }
+    while m.remaining > 0 {
+      m.next::<DefaultConfig>()?;
+    }
+    let offset = m.read.position;
+    let count = m
+      .read
+      .read_u16::<LE>()
+      .map_err(|e| Error::from(e).at(Section::Bones, None, offset))?;
+
+    Ok(BoneReader {
+      version: m.version,
+      count: count.into(),
+      remaining: count.into(),
+      read: m.read,
+      poison: false,
+      counts: Counts {
+        bones: count.into(),
+        ..m.counts
+      },
+    })
+  }
+
+  fn next_impl<C: Config>(&mut self) -> Result<Option<Bone<C>>> {
+    if self.remaining <= 0 {
+      return Ok(None);
+    }
+    let bone = Bone {
+      local_name: self.read.read_name(20)?,
+      universal_name: String::new(),
+      parent: self.read.read_u16_index()?,
+      tail: self.read.read_u16_index()?,
+      kind: BoneKind::from(self.read.read_u8()?),
+      ik_parent: self.read.read_u16_index()?,
+      position: self.read.read_vec3::<C>()?,
+    };
+
+    self.remaining -= 1;
+    Ok(Some(bone))
+  }
+}
+
+element_reader!(BoneReader, BoneIterator, Section::Bones, Bone<C>);
@@ -0,0 +1,86 @@
+use crate::{
+  pmd::reader::{element_reader, Counts, MorphReader, PmdReadHelpers},
+  pmd::{BoneDisplay, BoneFrame, DisplayLists},
+  pmx::error::Section,
+  reader::{
+    helpers::{bounded_capacity, ReadHelpers},
+    limited::LimitedRead,
+  },
+  Config, DefaultConfig, Error, Result,
+};
+use byteorder::ReadBytesExt;
+use std::io::Read;
+
+// The morph list, bone frame names and bone list of the facial and bone panels, read as one element
+pub struct DisplayReader<R> {
+  pub version: f32,
+  pub count: i32,
+  pub remaining: i32,
+  pub(crate) read: LimitedRead<R>,
+  pub(crate) poison: bool,
+  pub(crate) counts: Counts,
+}
+
+impl<R: Read> DisplayReader<R> {
+  pub fn new(mut m: MorphReader<R>) -> Result<DisplayReader<R>> {
+    if m.poison {
+      return Err(Error::Poisoned);
+    }
+    while m.remaining > 0 {
+      m.next::<DefaultConfig>()?;
+    }
+
+    Ok(DisplayReader {
+      version: m.version,
+      count: 1,
+      remaining: 1,
+      read: m.read,
+      poison: false,
+      counts: m.counts,
+    })
+  }
+
+  fn next_impl<C: Config>(&mut self) -> Result<Option<DisplayLists<C>>> {
+    if self.remaining <= 0 {
+      return Ok(None);
+    }
+    let count = self.read.read_u8()?;
+    let mut morphs = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+      morphs.push(self.read.read_u16_index()?);
+    }
+
+    let count = self.read.read_u8()?;
+    let mut bone_frames = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+      bone_frames.push(BoneFrame {
+        local_name: self.read.read_name(50)?,
+        universal_name: String::new(),
+      });
+    }
+
+    let count = self.read.read_count()?;
+    let mut bones = Vec::with_capacity(bounded_capacity(count as usize));
+    for _ in 0..count {
+      bones.push(BoneDisplay {
+        bone: self.read.read_u16_index()?,
+        frame: self.read.read_u8()?,
+      });
+    }
+
+    self.counts.bone_frames = bone_frames.len();
+    self.remaining -= 1;
+    Ok(Some(DisplayLists {
+      morphs,
+      bone_frames,
+      bones,
+    }))
+  }
+}
+
+element_reader!(
+  DisplayReader,
+  DisplayIterator,
+  Section::DisplayLists,
+  DisplayLists<C>
+);
@@ -0,0 +1,132 @@
+use crate::{
+  pmd::reader::{element_reader, Counts, DisplayReader, PmdReadHelpers},
+  pmd::EnglishNames,
+  pmx::error::Section,
+  reader::limited::LimitedRead,
+  DefaultConfig, Error, Result,
+};
+use std::io::Read;
+
+// count is 0 when the file ends before the english names
+pub struct EnglishReader<R> {
+  pub version: f32,
+  pub count: i32,
+  pub remaining: i32,
+  pub(crate) read: LimitedRead<R>,
+  pub(crate) poison: bool,
+  pub(crate) counts: Counts,
+}
+
+impl<R: Read> EnglishReader<R> {
+  pub fn new(mut d: DisplayReader<R>) -> Result<EnglishReader<R>> {
+    if d.poison {
+      return Err(Error::Poisoned);
+    }
+    while d.remaining > 0 {
+      d.next::<DefaultConfig>()?;
+    }
+    let offset = d.read.position;
+    let mut flag = [0u8];
+    let count = match d.read.at_end(&mut flag) {
+      Ok(true) => 0,
+      Ok(false) => flag[0].min(1) as i32,
+      Err(e) => return Err(e.at(Section::EnglishNames, None, offset)),
+    };
+
+    Ok(EnglishReader {
+      version: d.version,
+      count,
+      remaining: count,
+      read: d.read,
+      poison: false,
+      counts: d.counts,
+    })
+  }
+
+  fn next_impl(&mut self) -> Result<Option<EnglishNames>> {
+    if self.remaining <= 0 {
+      return Ok(None);
+    }
+    let model_name = self.read.read_name(20)?;
+    let comment = self.read.read_name(256)?;
+    let names = |read: &mut LimitedRead<R>, count: usize, size: usize| {
+      (0..count)
+        .map(|_| read.read_name(size))
+        .collect::<Result<Vec<_>>>()
+    };
+    let bones = names(&mut self.read, self.counts.bones, 20)?;
+    let morphs = names(&mut self.read, self.counts.morphs.saturating_sub(1), 20)?;
+    let bone_frames = names(&mut self.read, self.counts.bone_frames, 50)?;
+
+    self.remaining -= 1;
+    Ok(Some(EnglishNames {
+      model_name,
+      comment,
+      bones,
+      morphs,
+      bone_frames,
+    }))
+  }
+}
+
+element_reader!(
+  EnglishReader,
+  EnglishIterator,
+  Section::EnglishNames,
+  EnglishNames,
+  plain
+);
+
+// The ten toon texture file names, an empty list when the file ends before them
+pub struct ToonReader<R> {
+  pub version: f32,
+  pub count: i32,
+  pub remaining: i32,
+  pub(crate) read: LimitedRead<R>,
+  pub(crate) poison: bool,
+}
+
+impl<R: Read> ToonReader<R> {
+  pub fn new(mut e: EnglishReader<R>) -> Result<ToonReader<R>> {
+    if e.poison {
+      return Err(Error::Poisoned);
+    }
+    while e.remaining > 0 {
+      e.next()?;
+    }
+
+    Ok(ToonReader {
+      version: e.version,
+      count: 1,
+      remaining: 1,
+      read: e.read,
+      poison: false,
+    })
+  }
+
+  fn next_impl(&mut self) -> Result<Option<Vec<String>>> {
+    if self.remaining <= 0 {
+      return Ok(None);
+    }
+    let mut block = [0u8; 1000];
+    let textures = if self.read.at_end(&mut block)? {
+      Vec::new()
+    } else {
+      let mut names = LimitedRead::new(&block[..], self.read.limits);
+      (0..10)
+        .map(|_| names.read_name(100))
+        .collect::<Result<_>>()?
+    };
+
+    self.remaining -= 1;
+    Ok(Some(textures))
+  }
+}
+
+element_reader!(
+  ToonReader,
+  ToonIterator,
+  Section::ToonTextures,
+  Vec<String>,
+  plain
+);
@@ -0,0 +1,49 @@
+use crate::{
+  pmd::reader::PmdReadHelpers, pmx::error::Section, reader::limited::LimitedRead, Error,
+  ParseLimits, Result,
+};
+use byteorder::{ReadBytesExt, LE};
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+
+pub struct HeaderReader<R> {
+  pub version: f32,
+  pub model_name: String,
+  pub comment: String,
+  pub(crate) read: LimitedRead<R>,
+}
+
+impl<R: Read> HeaderReader<R> {
+  pub fn new(read: R) -> Result<HeaderReader<R>> {
+    Self::with_limits(read, ParseLimits::default())
+  }
+
+  pub fn with_limits(read: R, limits: ParseLimits) -> Result<HeaderReader<R>> {
+    Self::read_header(LimitedRead::new(read, limits)).map_err(|e| e.at(Section::Header, None, 0))
+  }
+
+  fn read_header(mut read: LimitedRead<R>) -> Result<HeaderReader<R>> {
+    let mut magic = [0u8; 3];
+    read.read_exact(&mut magic)?;
+    if &magic != b"Pmd" {
+      return Err(Error::WrongPmdSignature(magic));
+    }
+
+    Ok(HeaderReader {
+      version: read.read_f32::<LE>()?,
+      model_name: read.read_name(20)?,
+      comment: read.read_name(256)?,
+      read,
+    })
+  }
+}
+
+impl<R> Display for HeaderReader<R> {
+  fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    writeln!(
+      f,
+      "version: {}, name: {}\nComment\n{}",
+      self.version, self.model_name, self.comment
+    )
+  }
+}
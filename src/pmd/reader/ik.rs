@@ -0,0 +1,75 @@
+use crate::{
+  pmd::reader::{element_reader, BoneReader, Counts, PmdReadHelpers},
+  pmd::Ik,
+  pmx::error::Section,
+  reader::{
+    helpers::{bounded_capacity, ReadHelpers},
+    limited::LimitedRead,
+  },
+  Config, DefaultConfig, Error, Result,
+};
+use byteorder::{ReadBytesExt, LE};
+use std::io::Read;
+
+pub struct IkReader<R> {
+  pub version: f32,
+  pub count: i32,
+  pub remaining: i32,
+  pub(crate) read: LimitedRead<R>,
+  pub(crate) poison: bool,
+  pub(crate) counts: Counts,
+}
+
+impl<R: Read> IkReader<R> {
+  pub fn new(mut b: BoneReader<R>) -> Result<IkReader<R>> {
+    if b.poison {
+      return Err(Error::Poisoned);
+    }
+    while b.remaining > 0 {
+      b.next::<DefaultConfig>()?;
+    }
+    let offset = b.read.position;
+    let count = b
+      .read
+      .read_u16::<LE>()
+      .map_err(|e| Error::from(e).at(Section::Iks, None, offset))?;
+
+    Ok(IkReader {
+      version: b.version,
+      count: count.into(),
+      remaining: count.into(),
+      read: b.read,
+      poison: false,
+      counts: b.counts,
+    })
+  }
+
+  fn next_impl<C: Config>(&mut self) -> Result<Option<Ik<C>>> {
+    if self.remaining <= 0 {
+      return Ok(None);
+    }
+    let bone = self.read.read_u16_index()?;
+    let target = self.read.read_u16_index()?;
+    let links = self.read.read_u8()?;
+    self
+      .read
+      .check_limit("ik links", links as usize, self.read.limits().max_ik_links)?;
+    let iterations = self.read.read_u16::<LE>()?;
+    let control_weight = self.read.read_f32::<LE>()?;
+    let mut chain = Vec::with_capacity(bounded_capacity(links as usize));
+    for _ in 0..links {
+      chain.push(self.read.read_u16_index()?);
+    }
+
+    self.remaining -= 1;
+    Ok(Some(Ik {
+      bone,
+      target,
+      iterations,
+      control_weight,
+      chain,
+    }))
+  }
+}
+
+element_reader!(IkReader, IkIterator, Section::Iks, Ik<C>);
@@ -0,0 +1,64 @@
+use crate::{
+  pmd::reader::{element_reader, PmdReadHelpers, RigidBodyReader},
+  pmd::Joint,
+  pmx::error::Section,
+  reader::{helpers::ReadHelpers, limited::LimitedRead},
+  Config, DefaultConfig, Error, Result,
+};
+use std::io::Read;
+
+pub struct JointReader<R> {
+  pub version: f32,
+  pub count: i32,
+  pub remaining: i32,
+  pub(crate) read: LimitedRead<R>,
+  pub(crate) poison: bool,
+}
+
+impl<R: Read> JointReader<R> {
+  pub fn new(mut r: RigidBodyReader<R>) -> Result<JointReader<R>> {
+    if r.poison {
+      return Err(Error::Poisoned);
+    }
+    while r.remaining > 0 {
+      r.next::<DefaultConfig>()?;
+    }
+    let offset = r.read.position;
+    let count = r
+      .read
+      .read_optional_count()
+      .map_err(|e| e.at(Section::Joints, None, offset))?;
+
+    Ok(JointReader {
+      version: r.version,
+      count,
+      remaining: count,
+      read: r.read,
+      poison: false,
+    })
+  }
+
+  fn next_impl<C: Config>(&mut self) -> Result<Option<Joint<C>>> {
+    if self.remaining <= 0 {
+      return Ok(None);
+    }
+    let joint = Joint {
+      local_name: self.read.read_name(20)?,
+      rigid_body_a: self.read.read_u32_index()?,
+      rigid_body_b: self.read.read_u32_index()?,
+      position: self.read.read_vec3::<C>()?,
+      rotation: self.read.read_vec3::<C>()?,
+      position_min: self.read.read_vec3::<C>()?,
+      position_max: self.read.read_vec3::<C>()?,
+      rotation_min: self.read.read_vec3::<C>()?,
+      rotation_max: self.read.read_vec3::<C>()?,
+      position_spring: self.read.read_vec3::<C>()?,
+      rotation_spring: self.read.read_vec3::<C>()?,
+    };
+
+    self.remaining -= 1;
+    Ok(Some(joint))
+  }
+}
+
+element_reader!(JointReader, JointIterator, Section::Joints, Joint<C>);
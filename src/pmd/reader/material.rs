@@ -0,0 +1,72 @@
+use crate::{
+  pmd::reader::{element_reader, Counts, PmdReadHelpers, SurfaceReader},
+  pmd::Material,
+  pmx::error::Section,
+  reader::{helpers::ReadHelpers, limited::LimitedRead},
+  Config, DefaultConfig, Error, Result,
+};
+use byteorder::{ReadBytesExt, LE};
+use std::io::Read;
+
+pub struct MaterialReader<R> {
+  pub version: f32,
+  pub count: i32,
+  pub remaining: i32,
+  pub(crate) read: LimitedRead<R>,
+  pub(crate) poison: bool,
+  pub(crate) counts: Counts,
+}
+
+impl<R: Read> MaterialReader<R> {
+  pub fn new(mut s: SurfaceReader<R>) -> Result<MaterialReader<R>> {
+    if s.poison {
+      return Err(Error::Poisoned);
+    }
+    while s.remaining > 0 {
+      s.next::<DefaultConfig>()?;
+    }
+    let offset = s.read.position;
+    let count = s
+      .read
+      .read_count()
+      .map_err(|e| e.at(Section::Materials, None, offset))?;
+
+    Ok(MaterialReader {
+      version: s.version,
+      count,
+      remaining: count,
+      read: s.read,
+      poison: false,
+      counts: s.counts,
+    })
+  }
+
+  fn next_impl<C: Config>(&mut self) -> Result<Option<Material<C>>> {
+    if self.remaining <= 0 {
+      return Ok(None);
+    }
+    let material = Material {
+      diffuse_color: self.read.read_vec4::<C>()?,
+      specular_strength: self.read.read_f32::<LE>()?,
+      specular_color: self.read.read_vec3::<C>()?,
+      ambient_color: self.read.read_vec3::<C>()?,
+      toon: match self.read.read_u8()? {
+        0xFF => None,
+        toon => Some(toon),
+      },
+      edge: self.read.read_u8()? != 0,
+      surface_count: self.read.read_count()?,
+      texture: self.read.read_name(20)?,
+    };
+
+    self.remaining -= 1;
+    Ok(Some(material))
+  }
+}
+
+element_reader!(
+  MaterialReader,
+  MaterialIterator,
+  Section::Materials,
+  Material<C>
+);
@@ -0,0 +1,80 @@
+use crate::{
+  pmd::reader::{element_reader, Counts, IkReader, PmdReadHelpers},
+  pmd::{Morph, MorphOffset},
+  pmx::{error::Section, morph::Panel},
+  reader::{
+    helpers::{bounded_capacity, ReadHelpers},
+    limited::LimitedRead,
+  },
+  Config, DefaultConfig, Error, Result,
+};
+use byteorder::{ReadBytesExt, LE};
+use std::io::Read;
+
+pub struct MorphReader<R> {
+  pub version: f32,
+  pub count: i32,
+  pub remaining: i32,
+  pub(crate) read: LimitedRead<R>,
+  pub(crate) poison: bool,
+  pub(crate) counts: Counts,
+}
+
+impl<R: Read> MorphReader<R> {
+  pub fn new(mut i: IkReader<R>) -> Result<MorphReader<R>> {
+    if i.poison {
+      return Err(Error::Poisoned);
+    }
+    while i.remaining > 0 {
+      i.next::<DefaultConfig>()?;
+    }
+    let offset = i.read.position;
+    let count = i
+      .read
+      .read_u16::<LE>()
+      .map_err(|e| Error::from(e).at(Section::Morphs, None, offset))?;
+
+    Ok(MorphReader {
+      version: i.version,
+      count: count.into(),
+      remaining: count.into(),
+      read: i.read,
+      poison: false,
+      counts: Counts {
+        morphs: count.into(),
+        ..i.counts
+      },
+    })
+  }
+
+  fn next_impl<C: Config>(&mut self) -> Result<Option<Morph<C>>> {
+    if self.remaining <= 0 {
+      return Ok(None);
+    }
+    let local_name = self.read.read_name(20)?;
+    let count = self.read.read_count()?;
+    self.read.check_limit(
+      "morph offsets",
+      count as usize,
+      self.read.limits().max_morph_offsets,
+    )?;
+    let panel = Panel::from(self.read.read_u8()?);
+    let mut offsets = Vec::with_capacity(bounded_capacity(count as usize));
+    for _ in 0..count {
+      offsets.push(MorphOffset {
+        vertex: self.read.read_u32_index()?,
+        offset: self.read.read_vec3::<C>()?,
+      });
+    }
+
+    self.remaining -= 1;
+    Ok(Some(Morph {
+      local_name,
+      universal_name: String::new(),
+      panel,
+      offsets,
+    }))
+  }
+}
+
+element_reader!(MorphReader, MorphIterator, Section::Morphs, Morph<C>);
@@ -0,0 +1,75 @@
+use crate::{
+  pmd::reader::{element_reader, PmdReadHelpers, ToonReader},
+  pmd::RigidBody,
+  pmx::{error::Section, rigid_body::*},
+  reader::{helpers::ReadHelpers, limited::LimitedRead},
+  Config, Error, Result,
+};
+use byteorder::{ReadBytesExt, LE};
+use std::convert::TryFrom;
+use std::io::Read;
+
+// count is 0 when the file ends before the physics sections
+pub struct RigidBodyReader<R> {
+  pub version: f32,
+  pub count: i32,
+  pub remaining: i32,
+  pub(crate) read: LimitedRead<R>,
+  pub(crate) poison: bool,
+}
+
+impl<R: Read> RigidBodyReader<R> {
+  pub fn new(mut t: ToonReader<R>) -> Result<RigidBodyReader<R>> {
+    if t.poison {
+      return Err(Error::Poisoned);
+    }
+    while t.remaining > 0 {
+      t.next()?;
+    }
+    let offset = t.read.position;
+    let count = t
+      .read
+      .read_optional_count()
+      .map_err(|e| e.at(Section::RigidBodies, None, offset))?;
+
+    Ok(RigidBodyReader {
+      version: t.version,
+      count,
+      remaining: count,
+      read: t.read,
+      poison: false,
+    })
+  }
+
+  fn next_impl<C: Config>(&mut self) -> Result<Option<RigidBody<C>>> {
+    if self.remaining <= 0 {
+      return Ok(None);
+    }
+    let rigid_body = RigidBody {
+      local_name: self.read.read_name(20)?,
+      bone_index: self.read.read_u16_index()?,
+      group: self.read.read_u8()?,
+      non_collision_mask: self.read.read_u16::<LE>()?,
+      shape: ShapeType::try_from(self.read.read_u8()?)?,
+      shape_size: self.read.read_vec3::<C>()?,
+      shape_position: self.read.read_vec3::<C>()?,
+      shape_rotation: self.read.read_vec3::<C>()?,
+      mass: self.read.read_f32::<LE>()?,
+      move_attenuation: self.read.read_f32::<LE>()?,
+      rotation_damping: self.read.read_f32::<LE>()?,
+      repulsion: self.read.read_f32::<LE>()?,
+      friction: self.read.read_f32::<LE>()?,
+      physics_mode: PhysicsMode::try_from(self.read.read_u8()?)?,
+    };
+
+    self.remaining -= 1;
+    Ok(Some(rigid_body))
+  }
+}
+
+element_reader!(
+  RigidBodyReader,
+  RigidBodyIterator,
+  Section::RigidBodies,
+  RigidBody<C>
+);
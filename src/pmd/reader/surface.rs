@@ -0,0 +1,68 @@
+use crate::{
+  pmd::reader::{element_reader, Counts, VertexReader},
+  pmx::error::Section,
+  reader::{helpers::ReadHelpers, limited::LimitedRead},
+  Config, DefaultConfig, Error, Result,
+};
+use byteorder::{ReadBytesExt, LE};
+use std::convert::TryFrom;
+use std::io::Read;
+
+pub struct SurfaceReader<R> {
+  pub version: f32,
+  pub count: i32,
+  pub remaining: i32,
+  pub(crate) read: LimitedRead<R>,
+  pub(crate) poison: bool,
+  pub(crate) counts: Counts,
+}
+
+impl<R: Read> SurfaceReader<R> {
+  pub fn new(mut v: VertexReader<R>) -> Result<SurfaceReader<R>> {
+    if v.poison {
+      return Err(Error::Poisoned);
+    }
+    while v.remaining > 0 {
+      v.next::<DefaultConfig>()?;
+    }
+    let offset = v.read.position;
+    let count = v
+      .read
+      .read_count()
+      .map_err(|e| e.at(Section::Surfaces, None, offset))?;
+    if count % 3 != 0 {
+      return Err(Error::InvalidSurfaceCount(count).at(Section::Surfaces, None, offset));
+    }
+
+    // counted in triangles, the file stores the number of indices
+    Ok(SurfaceReader {
+      version: v.version,
+      count: count / 3,
+      remaining: count / 3,
+      read: v.read,
+      poison: false,
+      counts: v.counts,
+    })
+  }
+
+  fn next_impl<C: Config>(&mut self) -> Result<Option<[C::VertexIndex; 3]>> {
+    if self.remaining <= 0 {
+      return Ok(None);
+    }
+    let mut index = || -> Result<C::VertexIndex> {
+      let v = self.read.read_u16::<LE>()?;
+      C::VertexIndex::try_from(v).map_err(|_| Error::IndexOverflow(v.into()))
+    };
+    let surface = [index()?, index()?, index()?];
+
+    self.remaining -= 1;
+    Ok(Some(surface))
+  }
+}
+
+element_reader!(
+  SurfaceReader,
+  SurfaceIterator,
+  Section::Surfaces,
+  [C::VertexIndex; 3]
+);
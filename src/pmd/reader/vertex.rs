@@ -0,0 +1,64 @@
+use crate::{
+  pmd::reader::{element_reader, Counts, HeaderReader, PmdReadHelpers},
+  pmd::Vertex,
+  pmx::error::Section,
+  reader::{helpers::ReadHelpers, limited::LimitedRead},
+  Config, Result,
+};
+use byteorder::ReadBytesExt;
+use std::io::Read;
+
+pub struct VertexReader<R> {
+  pub version: f32,
+  pub count: i32,
+  pub remaining: i32,
+  pub(crate) read: LimitedRead<R>,
+  pub(crate) poison: bool,
+  pub(crate) counts: Counts,
+}
+
+impl<R: Read> VertexReader<R> {
+  pub fn new(mut header: HeaderReader<R>) -> Result<VertexReader<R>> {
+    let offset = header.read.position;
+    let count = header
+      .read
+      .read_count()
+      .and_then(|count| {
+        header.read.check_limit(
+          "vertices",
+          count as usize,
+          header.read.limits().max_vertices,
+        )?;
+        Ok(count)
+      })
+      .map_err(|e| e.at(Section::Vertices, None, offset))?;
+
+    Ok(VertexReader {
+      version: header.version,
+      count,
+      remaining: count,
+      read: header.read,
+      poison: false,
+      counts: Counts::default(),
+    })
+  }
+
+  fn next_impl<C: Config>(&mut self) -> Result<Option<Vertex<C>>> {
+    if self.remaining <= 0 {
+      return Ok(None);
+    }
+    let vertex = Vertex {
+      position: self.read.read_vec3::<C>()?,
+      normal: self.read.read_vec3::<C>()?,
+      uv: self.read.read_vec2::<C>()?,
+      bones: [self.read.read_u16_index()?, self.read.read_u16_index()?],
+      weight: self.read.read_u8()?,
+      edge: self.read.read_u8()? == 0,
+    };
+
+    self.remaining -= 1;
+    Ok(Some(vertex))
+  }
+}
+
+element_reader!(VertexReader, VertexIterator, Section::Vertices, Vertex<C>);
@@ -0,0 +1,286 @@
+use crate::pmx::morph::Panel;
+use crate::pmx::rigid_body::{PhysicsMode, ShapeType};
+use crate::Config;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(bound(
+    serialize = "C::BoneIndex: Serialize, C::Vec2: Serialize, C::Vec3: Serialize",
+    deserialize = "C::BoneIndex: Deserialize<'de>, C::Vec2: Deserialize<'de>, C::Vec3: Deserialize<'de>"
+  ))
+)]
+pub struct Vertex<C: Config> {
+  pub position: C::Vec3,
+  pub normal: C::Vec3,
+  pub uv: C::Vec2,
+  pub bones: [C::BoneIndex; 2],
+  // percentage going to the first bone
+  pub weight: u8,
+  pub edge: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(bound(
+    serialize = "C::Vec3: Serialize, C::Vec4: Serialize",
+    deserialize = "C::Vec3: Deserialize<'de>, C::Vec4: Deserialize<'de>"
+  ))
+)]
+pub struct Material<C: Config> {
+  pub diffuse_color: C::Vec4,
+  pub specular_strength: f32,
+  pub specular_color: C::Vec3,
+  pub ambient_color: C::Vec3,
+  // index into toon_textures, None for the shared default
+  pub toon: Option<u8>,
+  pub edge: bool,
+  pub surface_count: i32,
+  // texture and sphere map file names, joined by '*' when both are set
+  pub texture: String,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum BoneKind {
+  Rotate,
+  RotateMove,
+  Ik,
+  Hidden,
+  IkInfluenced,
+  RotationInfluenced,
+  IkTarget,
+  Invisible,
+  Twist,
+  RotationFollow,
+  Unknown(u8),
+}
+
+impl Display for BoneKind {
+  fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+    match self {
+      BoneKind::Rotate => write!(f, "rotate"),
+      BoneKind::RotateMove => write!(f, "rotate and move"),
+      BoneKind::Ik => write!(f, "ik"),
+      BoneKind::Hidden => write!(f, "hidden"),
+      BoneKind::IkInfluenced => write!(f, "ik influenced"),
+      BoneKind::RotationInfluenced => write!(f, "rotation influenced"),
+      BoneKind::IkTarget => write!(f, "ik target"),
+      BoneKind::Invisible => write!(f, "invisible"),
+      BoneKind::Twist => write!(f, "twist"),
+      BoneKind::RotationFollow => write!(f, "rotation follow"),
+      BoneKind::Unknown(kind) => write!(f, "unknown({})", kind),
+    }
+  }
+}
+
+impl From<u8> for BoneKind {
+  fn from(value: u8) -> Self {
+    match value {
+      0 => BoneKind::Rotate,
+      1 => BoneKind::RotateMove,
+      2 => BoneKind::Ik,
+      3 => BoneKind::Hidden,
+      4 => BoneKind::IkInfluenced,
+      5 => BoneKind::RotationInfluenced,
+      6 => BoneKind::IkTarget,
+      7 => BoneKind::Invisible,
+      8 => BoneKind::Twist,
+      9 => BoneKind::RotationFollow,
+      kind => BoneKind::Unknown(kind),
+    }
+  }
+}
+
+impl From<BoneKind> for u8 {
+  fn from(value: BoneKind) -> Self {
+    match value {
+      BoneKind::Rotate => 0,
+      BoneKind::RotateMove => 1,
+      BoneKind::Ik => 2,
+      BoneKind::Hidden => 3,
+      BoneKind::IkInfluenced => 4,
+      BoneKind::RotationInfluenced => 5,
+      BoneKind::IkTarget => 6,
+      BoneKind::Invisible => 7,
+      BoneKind::Twist => 8,
+      BoneKind::RotationFollow => 9,
+      BoneKind::Unknown(kind) => kind,
+    }
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(bound(
+    serialize = "C::BoneIndex: Serialize, C::Vec3: Serialize",
+    deserialize = "C::BoneIndex: Deserialize<'de>, C::Vec3: Deserialize<'de>"
+  ))
+)]
+pub struct Bone<C: Config> {
+  pub local_name: String,
+  pub universal_name: String,
+  pub parent: C::BoneIndex,
+  // for RotationFollow bones this holds the follow rate in percent instead of a bone
+  pub tail: C::BoneIndex,
+  pub kind: BoneKind,
+  // the ik bone for IkInfluenced, the source bone for RotationInfluenced and RotationFollow
+  pub ik_parent: C::BoneIndex,
+  pub position: C::Vec3,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(bound(
+    serialize = "C::BoneIndex: Serialize",
+    deserialize = "C::BoneIndex: Deserialize<'de>"
+  ))
+)]
+pub struct Ik<C: Config> {
+  pub bone: C::BoneIndex,
+  pub target: C::BoneIndex,
+  pub iterations: u16,
+  // maximum rotation per iteration, in units of 4 radians
+  pub control_weight: f32,
+  pub chain: Vec<C::BoneIndex>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(bound(
+    serialize = "C::VertexIndex: Serialize, C::Vec3: Serialize",
+    deserialize = "C::VertexIndex: Deserialize<'de>, C::Vec3: Deserialize<'de>"
+  ))
+)]
+pub struct MorphOffset<C: Config> {
+  // a model vertex for the base morph, an index into the base morph's offsets otherwise
+  pub vertex: C::VertexIndex,
+  pub offset: C::Vec3,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(bound(
+    serialize = "C::VertexIndex: Serialize, C::Vec3: Serialize",
+    deserialize = "C::VertexIndex: Deserialize<'de>, C::Vec3: Deserialize<'de>"
+  ))
+)]
+pub struct Morph<C: Config> {
+  pub local_name: String,
+  pub universal_name: String,
+  // Panel::Hidden marks the base morph holding the absolute positions of all morphed vertices
+  pub panel: Panel,
+  pub offsets: Vec<MorphOffset<C>>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BoneFrame {
+  pub local_name: String,
+  pub universal_name: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(bound(
+    serialize = "C::BoneIndex: Serialize",
+    deserialize = "C::BoneIndex: Deserialize<'de>"
+  ))
+)]
+pub struct BoneDisplay<C: Config> {
+  pub bone: C::BoneIndex,
+  // 1 based index into bone_frames
+  pub frame: u8,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(bound(
+    serialize = "C::BoneIndex: Serialize, C::MorphIndex: Serialize",
+    deserialize = "C::BoneIndex: Deserialize<'de>, C::MorphIndex: Deserialize<'de>"
+  ))
+)]
+pub struct DisplayLists<C: Config> {
+  pub morphs: Vec<C::MorphIndex>,
+  pub bone_frames: Vec<BoneFrame>,
+  pub bones: Vec<BoneDisplay<C>>,
+}
+
+// The optional block of english names, morph names skip the base morph
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EnglishNames {
+  pub model_name: String,
+  pub comment: String,
+  pub bones: Vec<String>,
+  pub morphs: Vec<String>,
+  pub bone_frames: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(bound(
+    serialize = "C::BoneIndex: Serialize, C::Vec3: Serialize",
+    deserialize = "C::BoneIndex: Deserialize<'de>, C::Vec3: Deserialize<'de>"
+  ))
+)]
+pub struct RigidBody<C: Config> {
+  pub local_name: String,
+  pub bone_index: C::BoneIndex,
+  pub group: u8,
+  pub non_collision_mask: u16,
+  pub shape: ShapeType,
+  pub shape_size: C::Vec3,
+  // relative to the bone position, unlike PMX
+  pub shape_position: C::Vec3,
+  pub shape_rotation: C::Vec3,
+  pub mass: f32,
+  pub move_attenuation: f32,
+  pub rotation_damping: f32,
+  pub repulsion: f32,
+  pub friction: f32,
+  pub physics_mode: PhysicsMode,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(bound(
+    serialize = "C::RigidbodyIndex: Serialize, C::Vec3: Serialize",
+    deserialize = "C::RigidbodyIndex: Deserialize<'de>, C::Vec3: Deserialize<'de>"
+  ))
+)]
+pub struct Joint<C: Config> {
+  pub local_name: String,
+  pub rigid_body_a: C::RigidbodyIndex,
+  pub rigid_body_b: C::RigidbodyIndex,
+  pub position: C::Vec3,
+  pub rotation: C::Vec3,
+  pub position_min: C::Vec3,
+  pub position_max: C::Vec3,
+  pub rotation_min: C::Vec3,
+  pub rotation_max: C::Vec3,
+  pub position_spring: C::Vec3,
+  pub rotation_spring: C::Vec3,
+}
@@ -1,9 +1,23 @@
+#[cfg(feature = "async")]
+pub mod async_reader;
 pub mod bone;
+pub mod csv;
+pub mod display_frame;
 pub mod error;
+pub mod index;
+pub mod joint;
+pub mod limits;
 pub mod material;
+pub mod model;
 pub mod morph;
 pub mod reader;
+pub mod rigid_body;
 pub mod settings;
+pub mod soft_body;
+#[cfg(feature = "json")]
+pub mod text;
 pub mod types;
 pub mod vertex;
+pub mod view;
 pub mod weight_deform;
+pub mod writer;
@@ -0,0 +1,289 @@
+mod buffer;
+
+pub use self::buffer::StreamBuffer;
+
+use self::buffer::needs_more;
+use crate::{
+  pmx::{display_frame::DisplayFrame, morph::Morph},
+  reader::{limited::LimitedRead, *},
+  Bone, Config, DefaultConfig, Joint, Material, ParseLimits, Result, RigidBody, SoftBody, Vertex,
+};
+use futures_io::AsyncRead;
+use std::ops::Deref;
+
+// The sync readers run over a StreamBuffer, an element that runs out of buffered bytes
+// is rolled back to where it started and parsed again once more data has arrived.
+pub struct AsyncReader<S, R> {
+  sync: S,
+  source: R,
+}
+
+pub type AsyncHeaderReader<R> = AsyncReader<HeaderReader<StreamBuffer>, R>;
+pub type AsyncVertexReader<R> = AsyncReader<VertexReader<StreamBuffer>, R>;
+pub type AsyncSurfaceReader<R> = AsyncReader<SurfaceReader<StreamBuffer>, R>;
+pub type AsyncTextureReader<R> = AsyncReader<TextureReader<StreamBuffer>, R>;
+pub type AsyncMaterialReader<R> = AsyncReader<MaterialReader<StreamBuffer>, R>;
+pub type AsyncBoneReader<R> = AsyncReader<BoneReader<StreamBuffer>, R>;
+pub type AsyncMorphReader<R> = AsyncReader<MorphReader<StreamBuffer>, R>;
+pub type AsyncDisplayFrameReader<R> = AsyncReader<DisplayFrameReader<StreamBuffer>, R>;
+pub type AsyncRigidBodyReader<R> = AsyncReader<RigidBodyReader<StreamBuffer>, R>;
+pub type AsyncJointReader<R> = AsyncReader<JointReader<StreamBuffer>, R>;
+pub type AsyncSoftBodyReader<R> = AsyncReader<SoftBodyReader<StreamBuffer>, R>;
+
+impl<S, R> Deref for AsyncReader<S, R> {
+  type Target = S;
+
+  fn deref(&self) -> &S {
+    &self.sync
+  }
+}
+
+pub(crate) trait Resumable {
+  fn state(&mut self) -> (&mut i32, &mut bool, &mut LimitedRead<StreamBuffer>);
+}
+
+macro_rules! resumable {
+  ($($reader:ident),*) => {
+    $(
+      impl Resumable for $reader<StreamBuffer> {
+        fn state(&mut self) -> (&mut i32, &mut bool, &mut LimitedRead<StreamBuffer>) {
+          (&mut self.remaining, &mut self.poison, &mut self.read)
+        }
+      }
+    )*
+  };
+}
+
+resumable!(
+  VertexReader,
+  SurfaceReader,
+  TextureReader,
+  MaterialReader,
+  BoneReader,
+  MorphReader,
+  DisplayFrameReader,
+  RigidBodyReader,
+  JointReader,
+  SoftBodyReader
+);
+
+async fn retry<S, R, T, F>(reader: &mut AsyncReader<S, R>, mut next: F) -> Result<T>
+where
+  S: Resumable,
+  R: AsyncRead + Unpin,
+  F: FnMut(&mut S) -> Result<T>,
+{
+  loop {
+    let (remaining, poison, read) = reader.sync.state();
+    let checkpoint = (*remaining, *poison, read.position, read.get_mut().cursor);
+
+    match next(&mut reader.sync) {
+      Err(ref e) if needs_more(e) => {
+        let (remaining, poison, read) = reader.sync.state();
+        *remaining = checkpoint.0;
+        *poison = checkpoint.1;
+        read.position = checkpoint.2;
+        read.get_mut().cursor = checkpoint.3;
+        read.get_mut().fill(&mut reader.source).await;
+      }
+      result => return result,
+    }
+  }
+}
+
+// counts are the only thing read when moving on to the next section
+async fn fill_count<S: Resumable, R: AsyncRead + Unpin>(reader: &mut AsyncReader<S, R>) {
+  let (_, _, read) = reader.sync.state();
+  read.get_mut().fill_to(4, &mut reader.source).await;
+}
+
+impl<R: AsyncRead + Unpin> AsyncHeaderReader<R> {
+  pub async fn new(read: R) -> Result<AsyncHeaderReader<R>> {
+    Self::with_limits(read, ParseLimits::default()).await
+  }
+
+  pub async fn with_limits(mut read: R, limits: ParseLimits) -> Result<AsyncHeaderReader<R>> {
+    // the header is small, so it is simply parsed again from the start
+    let mut buffer = StreamBuffer::default();
+    loop {
+      match HeaderReader::with_limits(buffer.clone(), limits) {
+        Err(ref e) if needs_more(e) => buffer.fill(&mut read).await,
+        result => {
+          return result.map(|sync| AsyncReader { sync, source: read });
+        }
+      }
+    }
+  }
+}
+
+impl<R: AsyncRead + Unpin> AsyncVertexReader<R> {
+  pub async fn new(mut header: AsyncHeaderReader<R>) -> Result<AsyncVertexReader<R>> {
+    header
+      .sync
+      .read
+      .get_mut()
+      .fill_to(4, &mut header.source)
+      .await;
+    Ok(AsyncReader {
+      sync: VertexReader::new(header.sync)?,
+      source: header.source,
+    })
+  }
+
+  pub async fn next<C: Config>(&mut self) -> Result<Option<Vertex<C>>> {
+    retry(self, |r| r.next::<C>()).await
+  }
+}
+
+impl<R: AsyncRead + Unpin> AsyncSurfaceReader<R> {
+  pub async fn new(mut v: AsyncVertexReader<R>) -> Result<AsyncSurfaceReader<R>> {
+    while v.remaining > 0 {
+      v.next::<DefaultConfig>().await?;
+    }
+    fill_count(&mut v).await;
+    Ok(AsyncReader {
+      sync: SurfaceReader::new(v.sync)?,
+      source: v.source,
+    })
+  }
+
+  pub async fn next<C: Config>(&mut self) -> Result<Option<[C::VertexIndex; 3]>> {
+    retry(self, |r| r.next::<C>()).await
+  }
+}
+
+impl<R: AsyncRead + Unpin> AsyncTextureReader<R> {
+  pub async fn new(mut s: AsyncSurfaceReader<R>) -> Result<AsyncTextureReader<R>> {
+    while s.remaining > 0 {
+      s.next::<DefaultConfig>().await?;
+    }
+    fill_count(&mut s).await;
+    Ok(AsyncReader {
+      sync: TextureReader::new(s.sync)?,
+      source: s.source,
+    })
+  }
+
+  pub async fn next(&mut self) -> Result<Option<String>> {
+    retry(self, |r| r.next()).await
+  }
+}
+
+impl<R: AsyncRead + Unpin> AsyncMaterialReader<R> {
+  pub async fn new(mut t: AsyncTextureReader<R>) -> Result<AsyncMaterialReader<R>> {
+    while t.remaining > 0 {
+      t.next().await?;
+    }
+    fill_count(&mut t).await;
+    Ok(AsyncReader {
+      sync: MaterialReader::new(t.sync)?,
+      source: t.source,
+    })
+  }
+
+  pub async fn next<C: Config>(&mut self) -> Result<Option<Material<C>>> {
+    retry(self, |r| r.next::<C>()).await
+  }
+}
+
+impl<R: AsyncRead + Unpin> AsyncBoneReader<R> {
+  pub async fn new(mut m: AsyncMaterialReader<R>) -> Result<AsyncBoneReader<R>> {
+    while m.remaining > 0 {
+      m.next::<DefaultConfig>().await?;
+    }
+    fill_count(&mut m).await;
+    Ok(AsyncReader {
+      sync: BoneReader::new(m.sync)?,
+      source: m.source,
+    })
+  }
+
+  pub async fn next<C: Config>(&mut self) -> Result<Option<Bone<C>>> {
+    retry(self, |r| r.next::<C>()).await
+  }
+}
+
+impl<R: AsyncRead + Unpin> AsyncMorphReader<R> {
+  pub async fn new(mut b: AsyncBoneReader<R>) -> Result<AsyncMorphReader<R>> {
+    while b.remaining > 0 {
+      b.next::<DefaultConfig>().await?;
+    }
+    fill_count(&mut b).await;
+    Ok(AsyncReader {
+      sync: MorphReader::new(b.sync)?,
+      source: b.source,
+    })
+  }
+
+  pub async fn next<C: Config>(&mut self) -> Result<Option<Morph<C>>> {
+    retry(self, |r| r.next::<C>()).await
+  }
+}
+
+impl<R: AsyncRead + Unpin> AsyncDisplayFrameReader<R> {
+  pub async fn new(mut m: AsyncMorphReader<R>) -> Result<AsyncDisplayFrameReader<R>> {
+    while m.remaining > 0 {
+      m.next::<DefaultConfig>().await?;
+    }
+    fill_count(&mut m).await;
+    Ok(AsyncReader {
+      sync: DisplayFrameReader::new(m.sync)?,
+      source: m.source,
+    })
+  }
+
+  pub async fn next<C: Config>(&mut self) -> Result<Option<DisplayFrame<C>>> {
+    retry(self, |r| r.next::<C>()).await
+  }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRigidBodyReader<R> {
+  pub async fn new(mut d: AsyncDisplayFrameReader<R>) -> Result<AsyncRigidBodyReader<R>> {
+    while d.remaining > 0 {
+      d.next::<DefaultConfig>().await?;
+    }
+    fill_count(&mut d).await;
+    Ok(AsyncReader {
+      sync: RigidBodyReader::new(d.sync)?,
+      source: d.source,
+    })
+  }
+
+  pub async fn next<C: Config>(&mut self) -> Result<Option<RigidBody<C>>> {
+    retry(self, |r| r.next::<C>()).await
+  }
+}
+
+impl<R: AsyncRead + Unpin> AsyncJointReader<R> {
+  pub async fn new(mut r: AsyncRigidBodyReader<R>) -> Result<AsyncJointReader<R>> {
+    while r.remaining > 0 {
+      r.next::<DefaultConfig>().await?;
+    }
+    fill_count(&mut r).await;
+    Ok(AsyncReader {
+      sync: JointReader::new(r.sync)?,
+      source: r.source,
+    })
+  }
+
+  pub async fn next<C: Config>(&mut self) -> Result<Option<Joint<C>>> {
+    retry(self, |r| r.next::<C>()).await
+  }
+}
+
+impl<R: AsyncRead + Unpin> AsyncSoftBodyReader<R> {
+  pub async fn new(mut j: AsyncJointReader<R>) -> Result<AsyncSoftBodyReader<R>> {
+    while j.remaining > 0 {
+      j.next::<DefaultConfig>().await?;
+    }
+    fill_count(&mut j).await;
+    Ok(AsyncReader {
+      sync: SoftBodyReader::new(j.sync)?,
+      source: j.source,
+    })
+  }
+
+  pub async fn next<C: Config>(&mut self) -> Result<Option<SoftBody<C>>> {
+    retry(self, |r| r.next::<C>()).await
+  }
+}
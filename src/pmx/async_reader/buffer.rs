@@ -0,0 +1,98 @@
+use crate::Error;
+use futures_io::AsyncRead;
+use std::fmt::{Display, Formatter};
+use std::future::poll_fn;
+use std::io::{self, ErrorKind, Read};
+use std::pin::Pin;
+
+const MIN_CHUNK: usize = 8192;
+
+// Bytes received from an AsyncRead so far, the sync readers parse from it
+// and report NeedMoreData instead of blocking when it runs dry
+#[derive(Clone, Debug, Default)]
+pub struct StreamBuffer {
+  data: Vec<u8>,
+  end: usize,
+  pub(crate) cursor: usize,
+  eof: bool,
+  error: Option<(ErrorKind, String)>,
+}
+
+impl StreamBuffer {
+  pub(crate) fn available(&self) -> usize {
+    self.end - self.cursor
+  }
+
+  pub(crate) fn exhausted(&self) -> bool {
+    self.eof || self.error.is_some()
+  }
+
+  // Bytes before the cursor belong to finished elements, so they are dropped here.
+  // Room grows with the buffered bytes to keep retries of large elements logarithmic.
+  pub(crate) async fn fill<R: AsyncRead + Unpin>(&mut self, source: &mut R) {
+    self.data.copy_within(self.cursor..self.end, 0);
+    self.end -= self.cursor;
+    self.cursor = 0;
+    let room = self.end.max(MIN_CHUNK);
+    if self.data.len() < self.end + room {
+      self.data.resize(self.end + room, 0);
+    }
+    loop {
+      let data = &mut self.data[self.end..];
+      match poll_fn(|cx| Pin::new(&mut *source).poll_read(cx, data)).await {
+        Ok(read) => {
+          self.end += read;
+          self.eof = read == 0;
+          return;
+        }
+        Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+        Err(e) => {
+          // handed to the sync reader on its next read so the error gets its location
+          self.error = Some((e.kind(), e.to_string()));
+          return;
+        }
+      }
+    }
+  }
+
+  pub(crate) async fn fill_to<R: AsyncRead + Unpin>(&mut self, bytes: usize, source: &mut R) {
+    while self.available() < bytes && !self.exhausted() {
+      self.fill(source).await;
+    }
+  }
+}
+
+impl Read for StreamBuffer {
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    if self.available() == 0 && !buf.is_empty() {
+      if let Some((kind, ref message)) = self.error {
+        return Err(io::Error::new(kind, message.clone()));
+      }
+      if !self.eof {
+        return Err(io::Error::new(ErrorKind::WouldBlock, NeedMoreData));
+      }
+    }
+    let read = buf.len().min(self.available());
+    buf[..read].copy_from_slice(&self.data[self.cursor..self.cursor + read]);
+    self.cursor += read;
+    Ok(read)
+  }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct NeedMoreData;
+
+impl Display for NeedMoreData {
+  fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    write!(f, "more data needed")
+  }
+}
+
+impl std::error::Error for NeedMoreData {}
+
+pub(crate) fn needs_more(e: &Error) -> bool {
+  match e.inner() {
+    Error::Io(e) => e.get_ref().is_some_and(|e| e.is::<NeedMoreData>()),
+    _ => false,
+  }
+}
@@ -3,6 +3,8 @@ use itertools::Itertools;
 use std::fmt::{Debug, Display, Formatter};
 
 use crate::{display::DisplayOption, Config};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 #[derive(BitFlags, Copy, Clone, PartialEq, Debug)]
 #[repr(u16)]
@@ -32,6 +34,14 @@ impl Display for BoneFlagsFmt {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(bound(
+    serialize = "C::BoneIndex: Serialize, C::Vec3: Serialize",
+    deserialize = "C::BoneIndex: Deserialize<'de>, C::Vec3: Deserialize<'de>"
+  ))
+)]
 pub enum Connection<C: Config> {
   Index(C::BoneIndex),
   Position(C::Vec3),
@@ -51,6 +61,14 @@ where
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(bound(
+    serialize = "C::BoneIndex: Serialize",
+    deserialize = "C::BoneIndex: Deserialize<'de>"
+  ))
+)]
 pub struct Additional<C: Config> {
   pub parent: C::BoneIndex,
   pub rate: f32,
@@ -66,6 +84,14 @@ where
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(bound(
+    serialize = "C::Vec3: Serialize",
+    deserialize = "C::Vec3: Deserialize<'de>"
+  ))
+)]
 pub struct LocalAxis<C: Config> {
   pub x: C::Vec3,
   pub z: C::Vec3,
@@ -81,6 +107,14 @@ where
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(bound(
+    serialize = "C::BoneIndex: Serialize, C::Vec3: Serialize",
+    deserialize = "C::BoneIndex: Deserialize<'de>, C::Vec3: Deserialize<'de>"
+  ))
+)]
 pub struct InverseKinematics<C: Config> {
   pub ik_bone: C::BoneIndex,
   pub iterations: u32,
@@ -106,9 +140,19 @@ where
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(bound(
+    serialize = "C::BoneIndex: Serialize, C::Vec3: Serialize",
+    deserialize = "C::BoneIndex: Deserialize<'de>, C::Vec3: Deserialize<'de>"
+  ))
+)]
 pub struct IKLink<C: Config> {
   pub ik_bone: C::BoneIndex,
   pub limits: Option<(C::Vec3, C::Vec3)>,
+  // the byte telling whether there are limits, any non zero value means there are
+  pub raw_limits: u8,
 }
 
 impl<C: Config> Display for IKLink<C>
@@ -126,6 +170,15 @@ where
   }
 }
 
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(bound(
+    serialize = "C::BoneIndex: Serialize, C::Vec3: Serialize",
+    deserialize = "C::BoneIndex: Deserialize<'de>, C::Vec3: Deserialize<'de>"
+  ))
+)]
 pub struct Bone<C: Config> {
   pub local_name: String,
   pub universal_name: String,
@@ -133,6 +186,8 @@ pub struct Bone<C: Config> {
   pub parent: C::BoneIndex,
   pub transform_level: i32,
   pub bone_flags: BitFlags<BoneFlags>,
+  // bits not covered by BoneFlags, kept so that writing reproduces the original file
+  pub unknown_flags: u16,
   pub connection: Connection<C>,
   pub additional: Option<Additional<C>>,
   pub fixed_axis: Option<C::Vec3>,
@@ -0,0 +1,87 @@
+mod export;
+mod import;
+mod record;
+
+use crate::{pmx::morph::Offsets, Config, Model, Result};
+use std::convert::TryInto;
+use std::io::{Read, Write};
+
+// PmxEditor's CSV layout: one row per element, references by name and angles in degrees.
+// Names must be unique for references to survive, soft bodies are not converted. Material surface
+// counts have to cover the surfaces exactly, and a material drawn as both points and lines comes
+// back as points only.
+
+pub fn to_writer<C: Config, W: Write>(model: &Model<C>, mut write: W) -> Result<()> {
+  write.write_all(export::export(model)?.as_bytes())?;
+  Ok(())
+}
+
+pub fn to_string<C: Config>(model: &Model<C>) -> Result<String> {
+  export::export(model)
+}
+
+pub fn from_reader<C: Config, R: Read>(mut read: R) -> Result<Model<C>> {
+  let mut text = String::new();
+  read.read_to_string(&mut text)?;
+  from_str(&text)
+}
+
+pub fn from_str<C: Config>(text: &str) -> Result<Model<C>> {
+  let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+  import::import(record::parse(text)?)
+}
+
+fn position<I: TryInto<i32> + Clone>(index: &I) -> Option<usize> {
+  index
+    .clone()
+    .try_into()
+    .ok()
+    .and_then(|i: i32| i.try_into().ok())
+}
+
+fn morph_kind<C: Config>(offsets: &Offsets<C>) -> u8 {
+  match offsets {
+    Offsets::Group(_) => 0,
+    Offsets::Vertex(_) => 1,
+    Offsets::Bone(_) => 2,
+    Offsets::UV(_) => 3,
+    Offsets::AdditionalUV1(_) => 4,
+    Offsets::AdditionalUV2(_) => 5,
+    Offsets::AdditionalUV3(_) => 6,
+    Offsets::AdditionalUV4(_) => 7,
+    Offsets::Material(_) => 8,
+    Offsets::Flip(_) => 9,
+    Offsets::Impulse(_) => 10,
+  }
+}
+
+// Bone morph rotations are edited as euler angles applied in MMD's Z, X, Y order
+fn euler_degrees(q: &[f32]) -> [f32; 3] {
+  let [x, y, z, w] = [q[0] as f64, q[1] as f64, q[2] as f64, q[3] as f64];
+  let m12 = 2.0 * (y * z - w * x);
+  let (rx, ry, rz) = if m12.abs() < 1.0 - 1e-12 {
+    (
+      (-m12).asin(),
+      (2.0 * (x * z + w * y)).atan2(1.0 - 2.0 * (x * x + y * y)),
+      (2.0 * (x * y + w * z)).atan2(1.0 - 2.0 * (x * x + z * z)),
+    )
+  } else {
+    (
+      (-m12).signum() * std::f64::consts::FRAC_PI_2,
+      (-2.0 * (x * z - w * y)).atan2(1.0 - 2.0 * (y * y + z * z)),
+      0.0,
+    )
+  };
+  [rx, ry, rz].map(|a| a.to_degrees() as f32)
+}
+
+fn quaternion(degrees: [f32; 3]) -> [f32; 4] {
+  let [(sx, cx), (sy, cy), (sz, cz)] = degrees.map(|a| ((a as f64).to_radians() / 2.0).sin_cos());
+  [
+    cy * sx * cz + sy * cx * sz,
+    sy * cx * cz - cy * sx * sz,
+    cy * cx * sz - sy * sx * cz,
+    cy * cx * cz + sy * sx * sz,
+  ]
+  .map(|v| v as f32)
+}
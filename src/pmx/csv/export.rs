@@ -0,0 +1,474 @@
+use super::{euler_degrees, morph_kind, position, record::Row};
+use crate::{
+  pmx::bone::{BoneFlags, Connection},
+  pmx::display_frame::FrameElement,
+  pmx::material::{DrawingFlags, Toon},
+  pmx::morph::Offsets,
+  Config, Error, Model, Result, Section, WeightDeform,
+};
+use std::convert::TryInto;
+
+struct Names<'a>(Vec<&'a str>);
+
+impl<'a> Names<'a> {
+  fn get<I: TryInto<i32> + Clone>(&self, index: &I) -> &'a str {
+    position(index)
+      .and_then(|i| self.0.get(i))
+      .copied()
+      .unwrap_or("")
+  }
+}
+
+pub(crate) fn export<C: Config>(model: &Model<C>) -> Result<String> {
+  // faces are listed under their material, any the materials do not cover would be lost
+  let mut covered = 0i64;
+  for material in &model.materials {
+    match material.surface_count {
+      count if count < 0 => return Err(Error::NegativeCount(count)),
+      count if count % 3 != 0 => return Err(Error::InvalidSurfaceCount(count)),
+      count => covered += count as i64,
+    }
+  }
+  let indices = model.surfaces.len() as i64 * 3;
+  if covered != indices {
+    return Err(Error::ElementCountMismatch {
+      section: Section::Surfaces,
+      expected: indices.try_into().unwrap_or(i32::MAX),
+      written: covered.try_into().unwrap_or(i32::MAX),
+    });
+  }
+
+  let textures = Names(model.textures.iter().map(String::as_str).collect());
+  let materials = Names(
+    model
+      .materials
+      .iter()
+      .map(|m| m.local_name.as_str())
+      .collect(),
+  );
+  let bones = Names(model.bones.iter().map(|b| b.local_name.as_str()).collect());
+  let morphs = Names(model.morphs.iter().map(|m| m.local_name.as_str()).collect());
+  let bodies = Names(
+    model
+      .rigid_bodies
+      .iter()
+      .map(|r| r.local_name.as_str())
+      .collect(),
+  );
+  let mut csv = String::new();
+
+  csv.push_str(";PmxHeader,Ver,EncodeType(0:UTF-16LE/1:UTF-8),追加UV数\r\n");
+  csv.push_str(
+    &Row::new("PmxHeader")
+      .value(model.version)
+      .value(model.settings.text_encoding as u8)
+      .value(model.settings.additional_vec4_count)
+      .finish(),
+  );
+  csv.push_str(";PmxModelInfo,モデル名,モデル名(英),コメント,コメント(英)\r\n");
+  csv.push_str(
+    &Row::new("PmxModelInfo")
+      .text(&model.model_local_name)
+      .text(&model.model_universal_name)
+      .text(&model.local_comments)
+      .text(&model.universal_comments)
+      .finish(),
+  );
+
+  csv.push_str(";PmxVertex,頂点Index,位置_x,位置_y,位置_z,法線_x,法線_y,法線_z,エッジ倍率,UV_u,UV_v,追加UV1_x,追加UV1_y,追加UV1_z,追加UV1_w,追加UV2_x,追加UV2_y,追加UV2_z,追加UV2_w,追加UV3_x,追加UV3_y,追加UV3_z,追加UV3_w,追加UV4_x,追加UV4_y,追加UV4_z,追加UV4_w,変形種別(0:BDEF1/1:BDEF2/2:BDEF4/3:SDEF/4:QDEF),ウェイト1_ボーン名,ウェイト1_ウェイト値,ウェイト2_ボーン名,ウェイト2_ウェイト値,ウェイト3_ボーン名,ウェイト3_ウェイト値,ウェイト4_ボーン名,ウェイト4_ウェイト値,C_x,C_y,C_z,R0_x,R0_y,R0_z,R1_x,R1_y,R1_z\r\n");
+  for (i, vertex) in model.vertices.iter().enumerate() {
+    let mut row = Row::new("PmxVertex")
+      .value(i)
+      .vec(vertex.position.as_ref())
+      .vec(vertex.normal.as_ref())
+      .value(vertex.edge_scale)
+      .vec(vertex.uv.as_ref());
+    let additional = vertex.additional.as_ref();
+    for i in 0..4 {
+      row = match additional.get(i) {
+        Some(v) => row.vec(v.as_ref()),
+        None => row.vec(&[0.0; 4]),
+      };
+    }
+
+    let (kind, weights, sdef) = match vertex.weight_deform {
+      WeightDeform::Bdef1(ref w) => (
+        0,
+        [
+          (Some(&w.bone_index), 1.0),
+          (None, 0.0),
+          (None, 0.0),
+          (None, 0.0),
+        ],
+        None,
+      ),
+      WeightDeform::Bdef2(ref w) => (
+        1,
+        [
+          (Some(&w.bone_1_index), w.bone_1_weight),
+          (Some(&w.bone_2_index), 1.0 - w.bone_1_weight),
+          (None, 0.0),
+          (None, 0.0),
+        ],
+        None,
+      ),
+      WeightDeform::Bdef4(ref w) => (
+        2,
+        [
+          (Some(&w.bone_1_index), w.bone_1_weight),
+          (Some(&w.bone_2_index), w.bone_2_weight),
+          (Some(&w.bone_3_index), w.bone_3_weight),
+          (Some(&w.bone_4_index), w.bone_4_weight),
+        ],
+        None,
+      ),
+      WeightDeform::Sdef(ref w) => (
+        3,
+        [
+          (Some(&w.bone_1_index), w.bone_1_weight),
+          (Some(&w.bone_2_index), 1.0 - w.bone_1_weight),
+          (None, 0.0),
+          (None, 0.0),
+        ],
+        Some((&w.c, &w.r0, &w.r1)),
+      ),
+      WeightDeform::Qdef(ref w) => (
+        4,
+        [
+          (Some(&w.bone_1_index), w.bone_1_weight),
+          (Some(&w.bone_2_index), w.bone_2_weight),
+          (Some(&w.bone_3_index), w.bone_3_weight),
+          (Some(&w.bone_4_index), w.bone_4_weight),
+        ],
+        None,
+      ),
+    };
+    row = row.value(kind);
+    for &(bone, weight) in &weights {
+      row = row
+        .text(bone.map(|bone| bones.get(bone)).unwrap_or(""))
+        .value(weight);
+    }
+    row = match sdef {
+      Some((c, r0, r1)) => row.vec(c.as_ref()).vec(r0.as_ref()).vec(r1.as_ref()),
+      None => row.vec(&[0.0; 9]),
+    };
+    csv.push_str(&row.finish());
+  }
+
+  csv.push_str(";PmxFace,親材質名,面Index,頂点Index1,頂点Index2,頂点Index3\r\n");
+  let mut surfaces = model.surfaces.iter();
+  for material in &model.materials {
+    let count = material.surface_count as usize / 3;
+    for (i, surface) in surfaces.by_ref().take(count).enumerate() {
+      let [a, b, c] = surface;
+      csv.push_str(
+        &Row::new("PmxFace")
+          .text(&material.local_name)
+          .value(i)
+          .value(vertex(a))
+          .value(vertex(b))
+          .value(vertex(c))
+          .finish(),
+      );
+    }
+  }
+
+  csv.push_str(";PmxMaterial,材質名,材質名(英),拡散色_R,拡散色_G,拡散色_B,拡散色_A(非透過度),反射色_R,反射色_G,反射色_B,反射強度,環境色_R,環境色_G,環境色_B,両面描画(0/1),地面影(0/1),セルフ影マップ(0/1),セルフ影(0/1),頂点色(0/1),描画(0:Tri/1:Point/2:Line),エッジ(0/1),エッジサイズ,エッジ色_R,エッジ色_G,エッジ色_B,エッジ色_A,基本テクスチャ,スフィアテクスチャ,スフィアモード(0:無効/1:乗算/2:加算/3:サブテクスチャ),Toonテクスチャ,メモ\r\n");
+  for material in &model.materials {
+    let flags = material.draw_flags;
+    // the editor has a single drawing mode, points win when both flags are set
+    let drawing = if flags.contains(DrawingFlags::PointDrawing) {
+      1
+    } else if flags.contains(DrawingFlags::LineDrawing) {
+      2
+    } else {
+      0
+    };
+    let toon = match material.toon {
+      Toon::Texture(ref index) => textures.get(index).to_string(),
+      Toon::Internal(index) => format!("toon{:02}.bmp", index as u32 + 1),
+    };
+    csv.push_str(
+      &Row::new("PmxMaterial")
+        .text(&material.local_name)
+        .text(&material.universal_name)
+        .vec(material.diffuse_color.as_ref())
+        .vec(material.specular_color.as_ref())
+        .value(material.specular_strength)
+        .vec(material.ambient_color.as_ref())
+        .flag(flags.contains(DrawingFlags::NoCull))
+        .flag(flags.contains(DrawingFlags::GroundShadow))
+        .flag(flags.contains(DrawingFlags::DrawShadow))
+        .flag(flags.contains(DrawingFlags::ReceiveShadow))
+        .flag(flags.contains(DrawingFlags::VertexColor))
+        .value(drawing)
+        .flag(flags.contains(DrawingFlags::HasEdge))
+        .value(material.edge_scale)
+        .vec(material.edge_color.as_ref())
+        .text(textures.get(&material.texture_index))
+        .text(textures.get(&material.environment_index))
+        .value(material.environment_blend_mode as u8)
+        .text(&toon)
+        .text(&material.metadata)
+        .finish(),
+    );
+  }
+
+  csv.push_str(";PmxBone,ボーン名,ボーン名(英),変形階層,物理後(0/1),位置_x,位置_y,位置_z,回転(0/1),移動(0/1),IK(0/1),表示(0/1),操作(0/1),親ボーン名,表示先(0:オフセット/1:ボーン),表示先ボーン名,オフセット_x,オフセット_y,オフセット_z,ローカル付与(0/1),回転付与(0/1),移動付与(0/1),付与率,付与親名,軸制限(0/1),制限軸_x,制限軸_y,制限軸_z,ローカル軸(0/1),ローカルX軸_x,ローカルX軸_y,ローカルX軸_z,ローカルZ軸_x,ローカルZ軸_y,ローカルZ軸_z,外部親(0/1),外部親Key,IKTarget名,IKLoop,IK単位角[deg]\r\n");
+  for bone in &model.bones {
+    let flags = bone.bone_flags;
+    let mut row = Row::new("PmxBone")
+      .text(&bone.local_name)
+      .text(&bone.universal_name)
+      .value(bone.transform_level)
+      .flag(flags.contains(BoneFlags::PhysicalTransform))
+      .vec(bone.position.as_ref())
+      .flag(flags.contains(BoneFlags::Rotatable))
+      .flag(flags.contains(BoneFlags::Movable))
+      .flag(flags.contains(BoneFlags::InverseKinematics))
+      .flag(flags.contains(BoneFlags::Display))
+      .flag(flags.contains(BoneFlags::CanOperate))
+      .text(bones.get(&bone.parent));
+    row = match bone.connection {
+      Connection::Index(ref index) => row.value(1).text(bones.get(index)).vec(&[0.0; 3]),
+      Connection::Position(ref offset) => row.value(0).text("").vec(offset.as_ref()),
+    };
+    row = row
+      .flag(flags.contains(BoneFlags::AddLocalDeform))
+      .flag(flags.contains(BoneFlags::AddRotation))
+      .flag(flags.contains(BoneFlags::AddMovement));
+    row = match bone.additional {
+      Some(ref additional) => row
+        .value(additional.rate)
+        .text(bones.get(&additional.parent)),
+      None => row.value(0.0).text(""),
+    };
+    row = match bone.fixed_axis {
+      Some(ref axis) => row.value(1).vec(axis.as_ref()),
+      None => row.value(0).vec(&[0.0; 3]),
+    };
+    row = match bone.local_axis {
+      Some(ref axis) => row.value(1).vec(axis.x.as_ref()).vec(axis.z.as_ref()),
+      None => row.value(0).vec(&[0.0; 6]),
+    };
+    row = match bone.external_parent_transform {
+      Some(key) => row.value(1).value(key),
+      None => row.value(0).value(0),
+    };
+    row = match bone.inverse_kinematics {
+      Some(ref ik) => row
+        .text(bones.get(&ik.ik_bone))
+        .value(ik.iterations)
+        .value(ik.limit_angle.to_degrees()),
+      None => row.text("").value(0).value(0.0),
+    };
+    csv.push_str(&row.finish());
+  }
+
+  csv.push_str(";PmxIKLink,親ボーン名,Linkボーン名,角度制限(0/1),XL[deg],XH[deg],YL[deg],YH[deg],ZL[deg],ZH[deg]\r\n");
+  for bone in &model.bones {
+    let links = bone.inverse_kinematics.iter().flat_map(|ik| &ik.links);
+    for link in links {
+      let row = Row::new("PmxIKLink")
+        .text(&bone.local_name)
+        .text(bones.get(&link.ik_bone));
+      let row = match link.limits {
+        Some((ref low, ref high)) => {
+          let (low, high) = (low.as_ref(), high.as_ref());
+          (0..3).fold(row.value(1), |row, i| row.degrees(&[low[i], high[i]]))
+        }
+        None => row.value(0).vec(&[0.0; 6]),
+      };
+      csv.push_str(&row.finish());
+    }
+  }
+
+  csv.push_str(";PmxMorph,モーフ名,モーフ名(英),パネル(0:無効/1:眉(左下)/2:目(左上)/3:口(右上)/4:その他(右下)),モーフ種類(0:グループモーフ/1:頂点モーフ/2:ボーンモーフ/3:UV(Tex)モーフ/4:追加UV1モーフ/5:追加UV2モーフ/6:追加UV3モーフ/7:追加UV4モーフ/8:材質モーフ/9:フリップモーフ/10:インパルスモーフ)\r\n");
+  for morph in &model.morphs {
+    csv.push_str(
+      &Row::new("PmxMorph")
+        .text(&morph.local_name)
+        .text(&morph.universal_name)
+        .value(u8::from(morph.panel))
+        .value(morph_kind(&morph.offsets))
+        .finish(),
+    );
+  }
+  for morph in &model.morphs {
+    let name = &morph.local_name;
+    match morph.offsets {
+      Offsets::Group(ref offsets) | Offsets::Flip(ref offsets) => {
+        let kind = match morph.offsets {
+          Offsets::Group(_) => "PmxGroupMorph",
+          _ => "PmxFlipMorph",
+        };
+        for offset in offsets {
+          csv.push_str(
+            &Row::new(kind)
+              .text(name)
+              .text(morphs.get(&offset.morph))
+              .value(offset.influence)
+              .finish(),
+          );
+        }
+      }
+      Offsets::Vertex(ref offsets) => {
+        for offset in offsets {
+          csv.push_str(
+            &Row::new("PmxVertexMorph")
+              .text(name)
+              .value(vertex(&offset.vertex))
+              .vec(offset.offset.as_ref())
+              .finish(),
+          );
+        }
+      }
+      Offsets::Bone(ref offsets) => {
+        for offset in offsets {
+          csv.push_str(
+            &Row::new("PmxBoneMorph")
+              .text(name)
+              .text(bones.get(&offset.bone))
+              .vec(offset.translation.as_ref())
+              .vec(&euler_degrees(offset.rotation.as_ref()))
+              .finish(),
+          );
+        }
+      }
+      Offsets::UV(ref offsets)
+      | Offsets::AdditionalUV1(ref offsets)
+      | Offsets::AdditionalUV2(ref offsets)
+      | Offsets::AdditionalUV3(ref offsets)
+      | Offsets::AdditionalUV4(ref offsets) => {
+        let uv = morph_kind(&morph.offsets) - 3;
+        for offset in offsets {
+          csv.push_str(
+            &Row::new("PmxUVMorph")
+              .text(name)
+              .value(uv)
+              .value(vertex(&offset.vertex))
+              .vec(offset.offset.as_ref())
+              .finish(),
+          );
+        }
+      }
+      Offsets::Material(ref offsets) => {
+        for offset in offsets {
+          csv.push_str(
+            &Row::new("PmxMaterialMorph")
+              .text(name)
+              .text(materials.get(&offset.material))
+              .value(offset.method as u8)
+              .vec(offset.diffuse_color.as_ref())
+              .vec(offset.specular_color.as_ref())
+              .value(offset.specular_strength)
+              .vec(offset.ambient_color.as_ref())
+              .vec(offset.edge_color.as_ref())
+              .value(offset.edge_scale)
+              .vec(offset.texture_tint.as_ref())
+              .vec(offset.environment_tint.as_ref())
+              .vec(offset.toon_tint.as_ref())
+              .finish(),
+          );
+        }
+      }
+      Offsets::Impulse(ref offsets) => {
+        for offset in offsets {
+          csv.push_str(
+            &Row::new("PmxImpulseMorph")
+              .text(name)
+              .text(bodies.get(&offset.rigid_body))
+              .flag(offset.local)
+              .vec(offset.velocity.as_ref())
+              .vec(offset.torque.as_ref())
+              .finish(),
+          );
+        }
+      }
+    }
+  }
+
+  csv.push_str(";PmxNode,枠名,枠名(英),特殊枠(0/1)\r\n");
+  for frame in &model.display_frames {
+    csv.push_str(
+      &Row::new("PmxNode")
+        .text(&frame.local_name)
+        .text(&frame.universal_name)
+        .flag(frame.special)
+        .finish(),
+    );
+  }
+  csv.push_str(";PmxNodeItem,親枠名,要素種類(0:ボーン/1:モーフ),要素名\r\n");
+  for frame in &model.display_frames {
+    for element in &frame.elements {
+      let (kind, name) = match element {
+        FrameElement::Bone(index) => (0, bones.get(index)),
+        FrameElement::Morph(index) => (1, morphs.get(index)),
+      };
+      csv.push_str(
+        &Row::new("PmxNodeItem")
+          .text(&frame.local_name)
+          .value(kind)
+          .text(name)
+          .finish(),
+      );
+    }
+  }
+
+  csv.push_str(";PmxBody,剛体名,剛体名(英),関連ボーン名,剛体タイプ(0:Bone/1:物理演算/2:物理演算+ボーン位置合わせ),グループ(0~15),非衝突グループ文字列(ex:1 2 3 4),形状(0:球/1:箱/2:カプセル),サイズ_x,サイズ_y,サイズ_z,位置_x,位置_y,位置_z,回転_x[deg],回転_y[deg],回転_z[deg],質量,移動減衰,回転減衰,反発力,摩擦力\r\n");
+  for body in &model.rigid_bodies {
+    // PMX keeps the groups a body collides with, the editor lists the ones it ignores
+    let non_collision = (0..16)
+      .filter(|group| body.non_collision_mask & 1 << group == 0)
+      .map(|group| (group + 1).to_string())
+      .collect::<Vec<_>>()
+      .join(" ");
+    csv.push_str(
+      &Row::new("PmxBody")
+        .text(&body.local_name)
+        .text(&body.universal_name)
+        .text(bones.get(&body.bone_index))
+        .value(body.physics_mode as u8)
+        .value(body.group)
+        .text(&non_collision)
+        .value(body.shape as u8)
+        .vec(body.shape_size.as_ref())
+        .vec(body.shape_position.as_ref())
+        .degrees(body.shape_rotation.as_ref())
+        .value(body.mass)
+        .value(body.move_attenuation)
+        .value(body.rotation_damping)
+        .value(body.repulsion)
+        .value(body.friction)
+        .finish(),
+    );
+  }
+
+  csv.push_str(";PmxJoint,Joint名,Joint名(英),剛体名A,剛体名B,Jointタイプ(0:バネ付6DOF/1:6DOF/2:P2P/3:ConeTwist/4:Slider/5:Hinge),位置_x,位置_y,位置_z,回転_x[deg],回転_y[deg],回転_z[deg],移動下限_x,移動下限_y,移動下限_z,移動上限_x,移動上限_y,移動上限_z,回転下限_x[deg],回転下限_y[deg],回転下限_z[deg],回転上限_x[deg],回転上限_y[deg],回転上限_z[deg],バネ定数-移動_x,バネ定数-移動_y,バネ定数-移動_z,バネ定数-回転_x,バネ定数-回転_y,バネ定数-回転_z\r\n");
+  for joint in &model.joints {
+    csv.push_str(
+      &Row::new("PmxJoint")
+        .text(&joint.local_name)
+        .text(&joint.universal_name)
+        .text(bodies.get(&joint.rigid_body_a))
+        .text(bodies.get(&joint.rigid_body_b))
+        .value(joint.joint_type as u8)
+        .vec(joint.position.as_ref())
+        .degrees(joint.rotation.as_ref())
+        .vec(joint.position_min.as_ref())
+        .vec(joint.position_max.as_ref())
+        .degrees(joint.rotation_min.as_ref())
+        .degrees(joint.rotation_max.as_ref())
+        .vec(joint.position_spring.as_ref())
+        .vec(joint.rotation_spring.as_ref())
+        .finish(),
+    );
+  }
+
+  Ok(csv)
+}
+
+fn vertex<I: TryInto<i32> + Clone>(index: &I) -> i64 {
+  index.clone().try_into().map(i64::from).unwrap_or(-1)
+}
@@ -0,0 +1,646 @@
+use super::{quaternion, record::Record};
+use crate::{
+  pmx::bone::*,
+  pmx::display_frame::{DisplayFrame, FrameElement},
+  pmx::joint::JointType,
+  pmx::material::{DrawingFlags, EnvironmentBlendMode, Toon},
+  pmx::morph::*,
+  pmx::rigid_body::{PhysicsMode, ShapeType},
+  pmx::weight_deform::*,
+  Config, Error, IndexSize, Joint, Material, Model, Result, RigidBody, Settings, TextEncoding,
+  Vertex, WeightDeform,
+};
+use enumflags2::BitFlags;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+// Rows reference each other by name, a name used twice resolves to its first element
+struct Names(HashMap<String, usize>);
+
+impl Names {
+  fn new<'a, I: Iterator<Item = &'a str>>(names: I) -> Names {
+    let mut map = HashMap::new();
+    for (i, name) in names.enumerate() {
+      map.entry(name.to_string()).or_insert(i);
+    }
+    Names(map)
+  }
+
+  fn position(&self, record: &mut Record, what: &str) -> Result<Option<usize>> {
+    let name = record.text()?;
+    if name.is_empty() {
+      return Ok(None);
+    }
+    match self.0.get(&name) {
+      Some(&i) => Ok(Some(i)),
+      None => Err(record.error(format!("unknown {} {:?}", what, name))),
+    }
+  }
+
+  fn index<I: TryFrom<i32>>(&self, record: &mut Record, what: &str) -> Result<I> {
+    let position = self.position(record, what)?;
+    index(record, position.map_or(-1, |i| i as i64))
+  }
+}
+
+fn index<I: TryFrom<i32>>(record: &Record, value: i64) -> Result<I> {
+  i32::try_from(value)
+    .ok()
+    .and_then(|v| I::try_from(v).ok())
+    .ok_or_else(|| record.error(format!("index {} out of range", value)))
+}
+
+fn vertex_index<C: Config>(record: &mut Record) -> Result<C::VertexIndex> {
+  let value = record.value::<i64>()?;
+  index(record, value)
+}
+
+fn expect_kind(morph: &Morph<impl Config>, record: &Record, kinds: &[u8]) -> Result<()> {
+  if kinds.contains(&super::morph_kind(&morph.offsets)) {
+    Ok(())
+  } else {
+    Err(record.error(format!(
+      "{} does not match morph {:?}",
+      record.kind, morph.local_name
+    )))
+  }
+}
+
+pub(crate) fn import<C: Config>(records: Vec<Record>) -> Result<Model<C>> {
+  let mut rows: HashMap<String, Vec<Record>> = HashMap::new();
+  for record in records {
+    match record.kind.as_str() {
+      "PmxHeader" | "PmxModelInfo" | "PmxVertex" | "PmxFace" | "PmxMaterial" | "PmxBone"
+      | "PmxIKLink" | "PmxMorph" | "PmxGroupMorph" | "PmxVertexMorph" | "PmxBoneMorph"
+      | "PmxUVMorph" | "PmxMaterialMorph" | "PmxFlipMorph" | "PmxImpulseMorph" | "PmxNode"
+      | "PmxNodeItem" | "PmxBody" | "PmxJoint" => {}
+      // soft bodies are not part of the conversion
+      kind if kind.starts_with("PmxSoftBody") => continue,
+      kind => return Err(record.error(format!("unknown row {:?}", kind))),
+    }
+    rows.entry(record.kind.clone()).or_default().push(record);
+  }
+  let mut take = |kind: &str| rows.remove(kind).unwrap_or_default();
+
+  let (mut version, mut text_encoding, mut additional_vec4_count) = (2.0, TextEncoding::UTF16LE, 0);
+  for mut record in take("PmxHeader") {
+    version = record.value()?;
+    text_encoding =
+      TextEncoding::try_from(record.value::<u8>()?).map_err(|e| record.error(e.to_string()))?;
+    additional_vec4_count = record.value::<u8>()?;
+    if additional_vec4_count > 4 {
+      return Err(
+        record.error(Error::InvalidAdditionalVec4Count(additional_vec4_count).to_string()),
+      );
+    }
+  }
+
+  let mut model = Model {
+    version,
+    settings: Settings {
+      text_encoding,
+      additional_vec4_count,
+      vertex_index_size: IndexSize::I32,
+      texture_index_size: IndexSize::I32,
+      material_index_size: IndexSize::I32,
+      bone_index_size: IndexSize::I32,
+      morph_index_size: IndexSize::I32,
+      rigidbody_index_size: IndexSize::I32,
+    },
+    extra_globals: Vec::new(),
+    model_local_name: String::new(),
+    model_universal_name: String::new(),
+    local_comments: String::new(),
+    universal_comments: String::new(),
+    vertices: Vec::new(),
+    surfaces: Vec::new(),
+    textures: Vec::new(),
+    materials: Vec::new(),
+    bones: Vec::new(),
+    morphs: Vec::new(),
+    display_frames: Vec::new(),
+    rigid_bodies: Vec::new(),
+    joints: Vec::new(),
+    soft_bodies: Vec::new(),
+  };
+
+  for mut record in take("PmxModelInfo") {
+    model.model_local_name = record.text()?;
+    model.model_universal_name = record.text()?;
+    model.local_comments = record.text()?;
+    model.universal_comments = record.text()?;
+  }
+
+  let material_rows = take("PmxMaterial");
+  let bone_rows = take("PmxBone");
+  let morph_rows = take("PmxMorph");
+  let body_rows = take("PmxBody");
+  let names = |rows: &[Record]| Names::new(rows.iter().map(Record::name));
+  let materials = names(&material_rows);
+  let bones = names(&bone_rows);
+  let morphs = names(&morph_rows);
+  let bodies = names(&body_rows);
+
+  for mut record in take("PmxVertex") {
+    let _index = record.value::<i64>()?;
+    let position = record.vec::<3>()?.into();
+    let normal = record.vec::<3>()?.into();
+    let edge_scale = record.value()?;
+    let uv = record.vec::<2>()?.into();
+    let additional = [
+      record.vec::<4>()?,
+      record.vec::<4>()?,
+      record.vec::<4>()?,
+      record.vec::<4>()?,
+    ];
+    let kind = record.value::<u8>()?;
+    let mut weights = Vec::with_capacity(4);
+    for _ in 0..4 {
+      let bone = bones.index::<C::BoneIndex>(&mut record, "bone")?;
+      weights.push((bone, record.value::<f32>()?));
+    }
+    let [c, r0, r1] = [record.vec::<3>()?, record.vec::<3>()?, record.vec::<3>()?];
+    let mut weights = weights.into_iter();
+    let mut next = || weights.next().unwrap();
+    let weight_deform = match kind {
+      0 => WeightDeform::Bdef1(Bdef1 {
+        bone_index: next().0,
+      }),
+      1 | 3 => {
+        let (bone_1_index, bone_1_weight) = next();
+        let bone_2_index = next().0;
+        if kind == 1 {
+          WeightDeform::Bdef2(Bdef2 {
+            bone_1_index,
+            bone_2_index,
+            bone_1_weight,
+          })
+        } else {
+          WeightDeform::Sdef(Sdef {
+            bone_1_index,
+            bone_2_index,
+            bone_1_weight,
+            c: c.into(),
+            r0: r0.into(),
+            r1: r1.into(),
+          })
+        }
+      }
+      2 | 4 => {
+        let (bone_1_index, bone_1_weight) = next();
+        let (bone_2_index, bone_2_weight) = next();
+        let (bone_3_index, bone_3_weight) = next();
+        let (bone_4_index, bone_4_weight) = next();
+        if kind == 2 {
+          WeightDeform::Bdef4(Bdef4 {
+            bone_1_index,
+            bone_2_index,
+            bone_3_index,
+            bone_4_index,
+            bone_1_weight,
+            bone_2_weight,
+            bone_3_weight,
+            bone_4_weight,
+          })
+        } else {
+          WeightDeform::Qdef(Qdef {
+            bone_1_index,
+            bone_2_index,
+            bone_3_index,
+            bone_4_index,
+            bone_1_weight,
+            bone_2_weight,
+            bone_3_weight,
+            bone_4_weight,
+          })
+        }
+      }
+      e => return Err(record.error(Error::UnknownWeightType(e).to_string())),
+    };
+    model.vertices.push(Vertex {
+      position,
+      normal,
+      uv,
+      additional: additional
+        .iter()
+        .take(additional_vec4_count as usize)
+        .map(|&v| v.into())
+        .collect(),
+      weight_deform,
+      edge_scale,
+    });
+  }
+
+  let mut faces = vec![Vec::new(); material_rows.len()];
+  for mut record in take("PmxFace") {
+    let material = materials
+      .position(&mut record, "material")?
+      .ok_or_else(|| record.error("face without material"))?;
+    let _index = record.value::<i64>()?;
+    faces[material].push([
+      vertex_index::<C>(&mut record)?,
+      vertex_index::<C>(&mut record)?,
+      vertex_index::<C>(&mut record)?,
+    ]);
+  }
+
+  let mut textures = HashMap::new();
+  let mut texture =
+    |model: &mut Model<C>, record: &Record, path: String| -> Result<C::TextureIndex> {
+      if path.is_empty() {
+        return index(record, -1);
+      }
+      let next = model.textures.len();
+      let i = *textures.entry(path.clone()).or_insert(next);
+      if i == next {
+        model.textures.push(path);
+      }
+      index(record, i as i64)
+    };
+  for (mut record, faces) in material_rows.into_iter().zip(faces) {
+    let local_name = record.text()?;
+    let universal_name = record.text()?;
+    let diffuse_color = record.vec::<4>()?.into();
+    let specular_color = record.vec::<3>()?.into();
+    let specular_strength = record.value()?;
+    let ambient_color = record.vec::<3>()?.into();
+    let mut draw_flags = BitFlags::empty();
+    for &flag in &[
+      DrawingFlags::NoCull,
+      DrawingFlags::GroundShadow,
+      DrawingFlags::DrawShadow,
+      DrawingFlags::ReceiveShadow,
+      DrawingFlags::VertexColor,
+    ] {
+      if record.flag()? {
+        draw_flags |= flag;
+      }
+    }
+    match record.value::<u8>()? {
+      0 => {}
+      1 => draw_flags |= DrawingFlags::PointDrawing,
+      2 => draw_flags |= DrawingFlags::LineDrawing,
+      e => return Err(record.error(format!("invalid drawing mode {}", e))),
+    }
+    if record.flag()? {
+      draw_flags |= DrawingFlags::HasEdge;
+    }
+    let edge_scale = record.value()?;
+    let edge_color = record.vec::<4>()?.into();
+    let path = record.text()?;
+    let texture_index = texture(&mut model, &record, path)?;
+    let path = record.text()?;
+    let environment_index = texture(&mut model, &record, path)?;
+    let environment_blend_mode = EnvironmentBlendMode::try_from(record.value::<u8>()?)
+      .map_err(|e| record.error(e.to_string()))?;
+    let path = record.text()?;
+    let toon = match internal_toon(&path) {
+      Some(i) => Toon::Internal(i),
+      None => Toon::Texture(texture(&mut model, &record, path)?),
+    };
+    let metadata = record.text()?;
+    let surface_count =
+      i32::try_from(faces.len() * 3).map_err(|_| record.error("too many faces"))?;
+    model.surfaces.extend(faces);
+    model.materials.push(Material {
+      local_name,
+      universal_name,
+      diffuse_color,
+      specular_color,
+      specular_strength,
+      ambient_color,
+      draw_flags,
+      edge_color,
+      edge_scale,
+      texture_index,
+      environment_index,
+      environment_blend_mode,
+      toon,
+      metadata,
+      surface_count,
+    });
+  }
+
+  for mut record in bone_rows {
+    let local_name = record.text()?;
+    let universal_name = record.text()?;
+    let transform_level = record.value()?;
+    let mut bone_flags = BitFlags::empty();
+    let mut set = |record: &mut Record, flag: BoneFlags| -> Result<bool> {
+      let on = record.flag()?;
+      if on {
+        bone_flags |= flag;
+      }
+      Ok(on)
+    };
+    set(&mut record, BoneFlags::PhysicalTransform)?;
+    let position = record.vec::<3>()?.into();
+    set(&mut record, BoneFlags::Rotatable)?;
+    set(&mut record, BoneFlags::Movable)?;
+    let has_ik = set(&mut record, BoneFlags::InverseKinematics)?;
+    set(&mut record, BoneFlags::Display)?;
+    set(&mut record, BoneFlags::CanOperate)?;
+    let parent = bones.index(&mut record, "bone")?;
+    let connection = if set(&mut record, BoneFlags::Connection)? {
+      let index = bones.index(&mut record, "bone")?;
+      record.vec::<3>()?;
+      Connection::Index(index)
+    } else {
+      record.text()?;
+      Connection::Position(record.vec::<3>()?.into())
+    };
+    set(&mut record, BoneFlags::AddLocalDeform)?;
+    let add_rotation = set(&mut record, BoneFlags::AddRotation)?;
+    let add_movement = set(&mut record, BoneFlags::AddMovement)?;
+    let rate = record.value()?;
+    let additional_parent = bones.index(&mut record, "bone")?;
+    let fixed = set(&mut record, BoneFlags::FixedAxis)?;
+    let fixed_axis = record.vec::<3>()?;
+    let local = set(&mut record, BoneFlags::LocalAxis)?;
+    let local_axis = [record.vec::<3>()?, record.vec::<3>()?];
+    let external = set(&mut record, BoneFlags::ExternalParentTransform)?;
+    let external_key = record.value()?;
+    let ik_bone = bones.index(&mut record, "bone")?;
+    let iterations = record.value()?;
+    let limit_angle = record.value::<f32>()?.to_radians();
+
+    model.bones.push(Bone {
+      local_name,
+      universal_name,
+      position,
+      parent,
+      transform_level,
+      bone_flags,
+      unknown_flags: 0,
+      connection,
+      additional: (add_rotation || add_movement).then(|| Additional {
+        parent: additional_parent,
+        rate,
+      }),
+      fixed_axis: fixed.then(|| fixed_axis.into()),
+      local_axis: local.then(|| LocalAxis {
+        x: local_axis[0].into(),
+        z: local_axis[1].into(),
+      }),
+      external_parent_transform: external.then_some(external_key),
+      inverse_kinematics: has_ik.then(|| InverseKinematics {
+        ik_bone,
+        iterations,
+        limit_angle,
+        links: Vec::new(),
+      }),
+    });
+  }
+
+  for mut record in take("PmxIKLink") {
+    let bone = bones
+      .position(&mut record, "bone")?
+      .ok_or_else(|| record.error("IK link without bone"))?;
+    let ik_bone = bones.index(&mut record, "bone")?;
+    let limited = record.flag()?;
+    let [xl, xh, yl, yh, zl, zh] = record.degrees::<6>()?;
+    let limits = limited.then(|| ([xl, yl, zl].into(), [xh, yh, zh].into()));
+    match model.bones[bone].inverse_kinematics {
+      Some(ref mut ik) => ik.links.push(IKLink {
+        ik_bone,
+        limits,
+        raw_limits: limited as u8,
+      }),
+      None => return Err(record.error(format!("bone {} has no IK", bone))),
+    }
+  }
+
+  for mut record in morph_rows {
+    let local_name = record.text()?;
+    let universal_name = record.text()?;
+    let panel = record.value::<u8>()?.into();
+    let offsets = match record.value::<u8>()? {
+      0 => Offsets::Group(Vec::new()),
+      1 => Offsets::Vertex(Vec::new()),
+      2 => Offsets::Bone(Vec::new()),
+      3 => Offsets::UV(Vec::new()),
+      4 => Offsets::AdditionalUV1(Vec::new()),
+      5 => Offsets::AdditionalUV2(Vec::new()),
+      6 => Offsets::AdditionalUV3(Vec::new()),
+      7 => Offsets::AdditionalUV4(Vec::new()),
+      8 => Offsets::Material(Vec::new()),
+      9 => Offsets::Flip(Vec::new()),
+      10 => Offsets::Impulse(Vec::new()),
+      e => return Err(record.error(Error::InvalidMorphType(e).to_string())),
+    };
+    model.morphs.push(Morph {
+      local_name,
+      universal_name,
+      panel,
+      offsets,
+    });
+  }
+
+  for kind in &[
+    "PmxGroupMorph",
+    "PmxVertexMorph",
+    "PmxBoneMorph",
+    "PmxUVMorph",
+    "PmxMaterialMorph",
+    "PmxFlipMorph",
+    "PmxImpulseMorph",
+  ] {
+    for mut record in take(kind) {
+      let morph = morphs
+        .position(&mut record, "morph")?
+        .ok_or_else(|| record.error("offset without morph"))?;
+      let morph = &mut model.morphs[morph];
+      match *kind {
+        "PmxGroupMorph" | "PmxFlipMorph" => {
+          expect_kind(
+            morph,
+            &record,
+            if *kind == "PmxGroupMorph" { &[0] } else { &[9] },
+          )?;
+          let offset = GroupOffset {
+            morph: morphs.index(&mut record, "morph")?,
+            influence: record.value()?,
+          };
+          if let Offsets::Group(ref mut offsets) | Offsets::Flip(ref mut offsets) = morph.offsets {
+            offsets.push(offset);
+          }
+        }
+        "PmxVertexMorph" => {
+          expect_kind(morph, &record, &[1])?;
+          let offset = VertexOffset {
+            vertex: vertex_index::<C>(&mut record)?,
+            offset: record.vec::<3>()?.into(),
+          };
+          if let Offsets::Vertex(ref mut offsets) = morph.offsets {
+            offsets.push(offset);
+          }
+        }
+        "PmxBoneMorph" => {
+          expect_kind(morph, &record, &[2])?;
+          let offset = BoneOffset {
+            bone: bones.index(&mut record, "bone")?,
+            translation: record.vec::<3>()?.into(),
+            rotation: quaternion(record.vec::<3>()?).into(),
+          };
+          if let Offsets::Bone(ref mut offsets) = morph.offsets {
+            offsets.push(offset);
+          }
+        }
+        "PmxUVMorph" => {
+          expect_kind(morph, &record, &[3, 4, 5, 6, 7])?;
+          // the UV number repeats the morph type
+          record.value::<u8>()?;
+          let offset = UVOffset {
+            vertex: vertex_index::<C>(&mut record)?,
+            offset: record.vec::<4>()?.into(),
+          };
+          match morph.offsets {
+            Offsets::UV(ref mut offsets)
+            | Offsets::AdditionalUV1(ref mut offsets)
+            | Offsets::AdditionalUV2(ref mut offsets)
+            | Offsets::AdditionalUV3(ref mut offsets)
+            | Offsets::AdditionalUV4(ref mut offsets) => offsets.push(offset),
+            _ => {}
+          }
+        }
+        "PmxMaterialMorph" => {
+          expect_kind(morph, &record, &[8])?;
+          let material = materials.index(&mut record, "material")?;
+          let method = OffsetMethod::try_from(record.value::<u8>()?)
+            .map_err(|e| record.error(e.to_string()))?;
+          let offset = MaterialOffset {
+            material,
+            method,
+            diffuse_color: record.vec::<4>()?.into(),
+            specular_color: record.vec::<3>()?.into(),
+            specular_strength: record.value()?,
+            ambient_color: record.vec::<3>()?.into(),
+            edge_color: record.vec::<4>()?.into(),
+            edge_scale: record.value()?,
+            texture_tint: record.vec::<4>()?.into(),
+            environment_tint: record.vec::<4>()?.into(),
+            toon_tint: record.vec::<4>()?.into(),
+          };
+          if let Offsets::Material(ref mut offsets) = morph.offsets {
+            offsets.push(offset);
+          }
+        }
+        _ => {
+          expect_kind(morph, &record, &[10])?;
+          let rigid_body = bodies.index(&mut record, "rigid body")?;
+          let local = record.flag()?;
+          let offset = ImpulseOffset {
+            rigid_body,
+            local,
+            raw_local: local as u8,
+            velocity: record.vec::<3>()?.into(),
+            torque: record.vec::<3>()?.into(),
+          };
+          if let Offsets::Impulse(ref mut offsets) = morph.offsets {
+            offsets.push(offset);
+          }
+        }
+      }
+    }
+  }
+
+  let node_rows = take("PmxNode");
+  let nodes = names(&node_rows);
+  for mut record in node_rows {
+    let (local_name, universal_name) = (record.text()?, record.text()?);
+    let special = record.flag()?;
+    model.display_frames.push(DisplayFrame {
+      local_name,
+      universal_name,
+      special,
+      raw_special: special as u8,
+      elements: Vec::new(),
+    });
+  }
+  for mut record in take("PmxNodeItem") {
+    let frame = nodes
+      .position(&mut record, "display frame")?
+      .ok_or_else(|| record.error("element without display frame"))?;
+    let element = match record.value::<u8>()? {
+      0 => FrameElement::Bone(bones.index(&mut record, "bone")?),
+      1 => FrameElement::Morph(morphs.index(&mut record, "morph")?),
+      e => return Err(record.error(Error::InvalidFrameElementType(e).to_string())),
+    };
+    model.display_frames[frame].elements.push(element);
+  }
+
+  for mut record in body_rows {
+    let local_name = record.text()?;
+    let universal_name = record.text()?;
+    let bone_index = bones.index(&mut record, "bone")?;
+    let physics_mode =
+      PhysicsMode::try_from(record.value::<u8>()?).map_err(|e| record.error(e.to_string()))?;
+    let group = record.value()?;
+    let mut non_collision_mask = u16::MAX;
+    for group in record.text()?.split_whitespace() {
+      match group.parse::<u16>() {
+        Ok(group @ 1..=16) => non_collision_mask &= !(1 << (group - 1)),
+        _ => return Err(record.error(format!("invalid collision group {:?}", group))),
+      }
+    }
+    let shape =
+      ShapeType::try_from(record.value::<u8>()?).map_err(|e| record.error(e.to_string()))?;
+    model.rigid_bodies.push(RigidBody {
+      local_name,
+      universal_name,
+      bone_index,
+      group,
+      non_collision_mask,
+      shape,
+      shape_size: record.vec::<3>()?.into(),
+      shape_position: record.vec::<3>()?.into(),
+      shape_rotation: record.degrees::<3>()?.into(),
+      mass: record.value()?,
+      move_attenuation: record.value()?,
+      rotation_damping: record.value()?,
+      repulsion: record.value()?,
+      friction: record.value()?,
+      physics_mode,
+    });
+  }
+
+  for mut record in take("PmxJoint") {
+    let local_name = record.text()?;
+    let universal_name = record.text()?;
+    let rigid_body_a = bodies.index(&mut record, "rigid body")?;
+    let rigid_body_b = bodies.index(&mut record, "rigid body")?;
+    let joint_type =
+      JointType::try_from(record.value::<u8>()?).map_err(|e| record.error(e.to_string()))?;
+    model.joints.push(Joint {
+      local_name,
+      universal_name,
+      joint_type,
+      rigid_body_a,
+      rigid_body_b,
+      position: record.vec::<3>()?.into(),
+      rotation: record.degrees::<3>()?.into(),
+      position_min: record.vec::<3>()?.into(),
+      position_max: record.vec::<3>()?.into(),
+      rotation_min: record.degrees::<3>()?.into(),
+      rotation_max: record.degrees::<3>()?.into(),
+      position_spring: record.vec::<3>()?.into(),
+      rotation_spring: record.vec::<3>()?.into(),
+    });
+  }
+
+  let settings = model.optimal_settings();
+  model.settings = Settings {
+    text_encoding,
+    additional_vec4_count,
+    ..settings
+  };
+  Ok(model)
+}
+
+fn internal_toon(path: &str) -> Option<u8> {
+  let number = path.strip_prefix("toon")?.strip_suffix(".bmp")?;
+  match number.parse::<u8>() {
+    Ok(n @ 1..=10) if number.len() == 2 => Some(n - 1),
+    _ => None,
+  }
+}
@@ -0,0 +1,154 @@
+use crate::{Error, Result};
+use std::fmt::Display;
+use std::str::FromStr;
+
+pub(crate) struct Row(String);
+
+impl Row {
+  pub fn new(kind: &str) -> Row {
+    Row(kind.to_string())
+  }
+
+  pub fn text(mut self, text: &str) -> Row {
+    self.0.push_str(",\"");
+    self.0.push_str(&text.replace('"', "\"\""));
+    self.0.push('"');
+    self
+  }
+
+  pub fn value<T: Display>(mut self, value: T) -> Row {
+    self.0.push(',');
+    self.0.push_str(&value.to_string());
+    self
+  }
+
+  pub fn flag(self, flag: bool) -> Row {
+    self.value(flag as u8)
+  }
+
+  pub fn vec(self, values: &[f32]) -> Row {
+    values.iter().fold(self, |row, v| row.value(v))
+  }
+
+  pub fn degrees(self, values: &[f32]) -> Row {
+    values.iter().fold(self, |row, v| row.value(v.to_degrees()))
+  }
+
+  pub fn finish(mut self) -> String {
+    self.0.push_str("\r\n");
+    self.0
+  }
+}
+
+pub(crate) struct Record {
+  pub line: usize,
+  pub kind: String,
+  fields: Vec<String>,
+  next: usize,
+}
+
+impl Record {
+  pub fn error<S: Into<String>>(&self, message: S) -> Error {
+    Error::Csv {
+      line: self.line,
+      message: message.into(),
+    }
+  }
+
+  pub fn name(&self) -> &str {
+    self.fields.get(1).map_or("", String::as_str)
+  }
+
+  pub fn text(&mut self) -> Result<String> {
+    let field = self
+      .fields
+      .get(self.next)
+      .cloned()
+      .ok_or_else(|| self.error(format!("{} has too few fields", self.kind)))?;
+    self.next += 1;
+    Ok(field)
+  }
+
+  pub fn value<T: FromStr>(&mut self) -> Result<T> {
+    let field = self.text()?;
+    field
+      .trim()
+      .parse()
+      .map_err(|_| self.error(format!("invalid number {:?}", field)))
+  }
+
+  pub fn flag(&mut self) -> Result<bool> {
+    Ok(self.value::<u8>()? != 0)
+  }
+
+  pub fn vec<const N: usize>(&mut self) -> Result<[f32; N]> {
+    let mut values = [0.0; N];
+    for v in values.iter_mut() {
+      *v = self.value()?;
+    }
+    Ok(values)
+  }
+
+  pub fn degrees<const N: usize>(&mut self) -> Result<[f32; N]> {
+    Ok(self.vec::<N>()?.map(f32::to_radians))
+  }
+}
+
+// RFC 4180 style records, comment rows start with ';' and blank lines are skipped
+pub(crate) fn parse(text: &str) -> Result<Vec<Record>> {
+  let mut records = Vec::new();
+  let mut chars = text.chars().peekable();
+  let mut line = 1;
+
+  while chars.peek().is_some() {
+    let start = line;
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    loop {
+      match chars.next() {
+        Some('"') if quoted => {
+          if chars.peek() == Some(&'"') {
+            chars.next();
+            field.push('"');
+          } else {
+            quoted = false;
+          }
+        }
+        Some('"') if field.is_empty() => quoted = true,
+        Some('\n') if quoted => {
+          line += 1;
+          field.push('\n');
+        }
+        Some(c) if quoted => field.push(c),
+        Some(',') => fields.push(std::mem::take(&mut field)),
+        Some('\r') if chars.peek() == Some(&'\n') => {}
+        Some('\n') => {
+          line += 1;
+          break;
+        }
+        Some(c) => field.push(c),
+        None if quoted => {
+          return Err(Error::Csv {
+            line: start,
+            message: "unterminated quoted field".to_string(),
+          })
+        }
+        None => break,
+      }
+    }
+    fields.push(field);
+
+    if fields.len() == 1 && fields[0].trim().is_empty() || fields[0].starts_with(';') {
+      continue;
+    }
+    records.push(Record {
+      line: start,
+      kind: fields[0].trim().to_string(),
+      fields,
+      next: 1,
+    });
+  }
+
+  Ok(records)
+}
@@ -0,0 +1,68 @@
+use crate::Config;
+use itertools::Itertools;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(bound(
+    serialize = "C::BoneIndex: Serialize, C::MorphIndex: Serialize",
+    deserialize = "C::BoneIndex: Deserialize<'de>, C::MorphIndex: Deserialize<'de>"
+  ))
+)]
+pub enum FrameElement<C: Config> {
+  Bone(C::BoneIndex),
+  Morph(C::MorphIndex),
+}
+
+impl<C: Config> Display for FrameElement<C>
+where
+  C::BoneIndex: Display,
+  C::MorphIndex: Display,
+{
+  fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+    match self {
+      FrameElement::Bone(i) => write!(f, "bone({})", i),
+      FrameElement::Morph(i) => write!(f, "morph({})", i),
+    }
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(bound(
+    serialize = "C::BoneIndex: Serialize, C::MorphIndex: Serialize",
+    deserialize = "C::BoneIndex: Deserialize<'de>, C::MorphIndex: Deserialize<'de>"
+  ))
+)]
+pub struct DisplayFrame<C: Config> {
+  pub local_name: String,
+  pub universal_name: String,
+  pub special: bool,
+  // the stored byte behind `special`, any non zero value counts as set
+  pub raw_special: u8,
+  pub elements: Vec<FrameElement<C>>,
+}
+
+impl<C: Config> Display for DisplayFrame<C>
+where
+  C::BoneIndex: Display,
+  C::MorphIndex: Display,
+{
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+    write!(
+      f,
+      r"local name: {}, universal name: {}, special: {},
+elements: {}",
+      self.local_name,
+      self.universal_name,
+      self.special,
+      self.elements.iter().map(ToString::to_string).join(", ")
+    )
+  }
+}
@@ -1,20 +1,104 @@
+use crate::pmx::{reader::limited::TotalBytesExceeded, types::IndexSize};
 use err_derive::Error;
 use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub enum Section {
+  Header,
+  Vertices,
+  Surfaces,
+  Textures,
+  Materials,
+  Bones,
+  Morphs,
+  DisplayFrames,
+  RigidBodies,
+  Joints,
+  SoftBodies,
+  // sections only found in PMD files
+  Iks,
+  DisplayLists,
+  EnglishNames,
+  ToonTextures,
+  // sections of VMD motions
+  BoneKeyframes,
+  MorphKeyframes,
+  CameraKeyframes,
+  LightKeyframes,
+  ShadowKeyframes,
+  IkKeyframes,
+}
+
+impl Display for Section {
+  fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    match self {
+      Section::Header => write!(f, "header"),
+      Section::Vertices => write!(f, "vertices"),
+      Section::Surfaces => write!(f, "surfaces"),
+      Section::Textures => write!(f, "textures"),
+      Section::Materials => write!(f, "materials"),
+      Section::Bones => write!(f, "bones"),
+      Section::Morphs => write!(f, "morphs"),
+      Section::DisplayFrames => write!(f, "display frames"),
+      Section::RigidBodies => write!(f, "rigid bodies"),
+      Section::Joints => write!(f, "joints"),
+      Section::SoftBodies => write!(f, "soft bodies"),
+      Section::Iks => write!(f, "iks"),
+      Section::DisplayLists => write!(f, "display lists"),
+      Section::EnglishNames => write!(f, "english names"),
+      Section::ToonTextures => write!(f, "toon textures"),
+      Section::BoneKeyframes => write!(f, "bone keyframes"),
+      Section::MorphKeyframes => write!(f, "morph keyframes"),
+      Section::CameraKeyframes => write!(f, "camera keyframes"),
+      Section::LightKeyframes => write!(f, "light keyframes"),
+      Section::ShadowKeyframes => write!(f, "self shadow keyframes"),
+      Section::IkKeyframes => write!(f, "show and ik keyframes"),
+    }
+  }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub struct Location {
+  pub section: Section,
+  pub element: Option<usize>,
+  pub offset: u64,
+}
+
+impl Display for Location {
+  fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    write!(f, "{}", self.section)?;
+    if let Some(element) = self.element {
+      write!(f, " #{}", element)?;
+    }
+    write!(f, " at byte {}", self.offset)
+  }
+}
 
 #[derive(Debug, Error)]
 pub enum Error {
   #[error(display = "{}", _0)]
-  Io(#[error(source)] std::io::Error),
+  Io(#[error(source, no_from)] std::io::Error),
+  #[error(display = "{} in {}", _1, _0)]
+  At(Location, #[error(source, no_from)] Box<Error>),
   #[error(display = "Wrong signature {:?}", _0)]
   WrongSignature([u8; 4]),
+  #[error(display = "Wrong PMD signature {:?}", _0)]
+  WrongPmdSignature([u8; 3]),
+  #[error(display = "Wrong VMD signature {:?}", _0)]
+  WrongVmdSignature([u8; 30]),
   #[error(display = "Globals count less than 8 {}", _0)]
   GlobalsCountLessThan8(u8),
+  #[error(display = "Invalid additional vec4 count {}", _0)]
+  InvalidAdditionalVec4Count(u8),
   #[error(display = "Unknown index size {}", _0)]
   UnknownIndexSize(u8),
   #[error(display = "Unknown text encoding {}", _0)]
   UnknownTextEncoding(u8),
   #[error(display = "Decode text {}", _0)]
   DecodeText(Cow<'static, str>),
+  #[error(display = "Encode text {}", _0)]
+  EncodeText(Cow<'static, str>),
   #[error(display = "Unknown weigh type {}", _0)]
   UnknownWeightType(u8),
   #[error(display = "Index overflow {}", _0)]
@@ -27,6 +111,115 @@ pub enum Error {
   InvalidMorphType(u8),
   #[error(display = "Invalid material offset method {}", _0)]
   InvalidMaterialOffsetMethod(u8),
+  #[error(display = "Invalid display frame element type {}", _0)]
+  InvalidFrameElementType(u8),
+  #[error(display = "Invalid rigid body shape {}", _0)]
+  InvalidRigidBodyShape(u8),
+  #[error(display = "Invalid physics mode {}", _0)]
+  InvalidPhysicsMode(u8),
+  #[error(display = "Invalid joint type {}", _0)]
+  InvalidJointType(u8),
+  #[error(display = "Invalid soft body shape {}", _0)]
+  InvalidSoftBodyShape(u8),
+  #[error(display = "Invalid aero model {}", _0)]
+  InvalidAeroModel(i32),
+  #[error(display = "Negative count {}", _0)]
+  NegativeCount(i32),
+  #[error(display = "Negative text size {}", _0)]
+  NegativeTextSize(i32),
+  #[error(display = "Surface index count {} is not a multiple of 3", _0)]
+  InvalidSurfaceCount(i32),
+  #[error(display = "Reader is poisoned by a previous error")]
+  Poisoned,
+  #[error(display = "No element {} in {} of {}", index, section, count)]
+  ElementOutOfRange {
+    section: Section,
+    index: usize,
+    count: i32,
+  },
+  #[error(
+    display = "Limit of {} exceeded: requested {}, limit {}",
+    what,
+    requested,
+    limit
+  )]
+  LimitExceeded {
+    what: &'static str,
+    requested: u64,
+    limit: u64,
+  },
+  #[error(display = "Index {} does not fit into {} index", _0, _1)]
+  IndexDoesNotFit(String, IndexSize),
+  #[error(
+    display = "Expected {} elements in {}, got {}",
+    expected,
+    section,
+    written
+  )]
+  ElementCountMismatch {
+    section: Section,
+    expected: i32,
+    written: i32,
+  },
+  #[error(
+    display = "Vertex has {} additional vec4s, settings declare {}",
+    found,
+    expected
+  )]
+  AdditionalVec4CountMismatch { expected: u8, found: usize },
+  #[error(display = "Bone flags {:016b} do not match bone data", _0)]
+  InconsistentBoneFlags(u16),
+  #[error(display = "PMX {} does not support {}", version, section)]
+  UnsupportedSection { section: Section, version: f32 },
+  #[error(display = "CSV line {}: {}", line, message)]
+  Csv { line: usize, message: String },
+  #[error(display = "VPD line {}: {}", line, message)]
+  Vpd { line: usize, message: String },
+  #[error(display = "Text format {}", _0)]
+  Text(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl Error {
+  pub(crate) fn at(self, section: Section, element: Option<usize>, offset: u64) -> Error {
+    Error::At(
+      Location {
+        section,
+        element,
+        offset,
+      },
+      Box::new(self),
+    )
+  }
+
+  pub fn location(&self) -> Option<&Location> {
+    match self {
+      Error::At(location, _) => Some(location),
+      _ => None,
+    }
+  }
+
+  pub fn inner(&self) -> &Error {
+    match self {
+      Error::At(_, e) => e.inner(),
+      e => e,
+    }
+  }
+}
+
+impl From<std::io::Error> for Error {
+  fn from(e: std::io::Error) -> Self {
+    match e
+      .get_ref()
+      .and_then(|inner| inner.downcast_ref::<TotalBytesExceeded>())
+    {
+      Some(&TotalBytesExceeded { requested, limit }) => Error::LimitExceeded {
+        what: "total bytes",
+        requested,
+        limit,
+      },
+      None => Error::Io(e),
+    }
+  }
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,257 @@
+use crate::{
+  pmx::{error::Section, morph::Morph, reader::limited::LimitedRead},
+  reader::*,
+  Bone, Config, Error, Material, ParseLimits, Result, Settings,
+};
+use std::io::{Read, Seek, SeekFrom};
+use std::ops::Range;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SectionIndex {
+  pub offset: u64,
+  pub count: i32,
+  pub elements: Option<Vec<u64>>,
+}
+
+impl SectionIndex {
+  fn new(start: u64, position: u64, count: i32) -> Self {
+    SectionIndex {
+      offset: start + position,
+      count,
+      elements: None,
+    }
+  }
+
+  fn check(&self, section: Section, range: &Range<usize>) -> Result<()> {
+    let count = self.count as usize;
+    if range.end > count {
+      return Err(Error::ElementOutOfRange {
+        section,
+        index: range.end - 1,
+        count: self.count,
+      });
+    }
+    if range.start > range.end {
+      return Err(Error::ElementOutOfRange {
+        section,
+        index: range.start,
+        count: self.count,
+      });
+    }
+    Ok(())
+  }
+
+  // Returns the offset to start decoding from and the ordinal of the element found there
+  fn locate(&self, index: usize) -> (u64, usize) {
+    match self.elements {
+      Some(ref elements) if index < elements.len() => (elements[index], index),
+      _ => (self.offset, 0),
+    }
+  }
+}
+
+#[derive(Clone, Debug)]
+pub struct PmxIndex {
+  pub version: f32,
+  pub settings: Settings,
+  pub limits: ParseLimits,
+  pub vertices: SectionIndex,
+  pub surfaces: SectionIndex,
+  pub textures: SectionIndex,
+  pub materials: SectionIndex,
+  pub bones: SectionIndex,
+  pub morphs: SectionIndex,
+  pub display_frames: SectionIndex,
+  pub rigid_bodies: SectionIndex,
+  pub joints: SectionIndex,
+  pub soft_bodies: SectionIndex,
+}
+
+impl PmxIndex {
+  pub fn new<R: Read + Seek>(read: R, element_offsets: bool) -> Result<PmxIndex> {
+    Self::with_limits(read, element_offsets, ParseLimits::default())
+  }
+
+  pub fn with_limits<R: Read + Seek>(
+    mut read: R,
+    element_offsets: bool,
+    limits: ParseLimits,
+  ) -> Result<PmxIndex> {
+    let start = read.stream_position()?;
+    let header = HeaderReader::with_limits(&mut read, limits)?;
+    let version = header.version;
+    let settings = header.settings;
+
+    let mut v = VertexReader::new(header)?;
+    let vertices = SectionIndex::new(start, v.read.position, v.count);
+    v.skip()?;
+
+    let mut s = SurfaceReader::new(v)?;
+    let surfaces = SectionIndex::new(start, s.read.position, s.count);
+    s.skip()?;
+
+    let mut t = TextureReader::new(s)?;
+    let textures = SectionIndex::new(start, t.read.position, t.count);
+    t.skip()?;
+
+    let mut m = MaterialReader::new(t)?;
+    let mut materials = SectionIndex::new(start, m.read.position, m.count);
+    if element_offsets {
+      let mut offsets = Vec::new();
+      while m.remaining > 0 {
+        offsets.push(start + m.read.position);
+        m.skip_next()?;
+      }
+      materials.elements = Some(offsets);
+    }
+    m.skip()?;
+
+    let mut b = BoneReader::new(m)?;
+    let mut bones = SectionIndex::new(start, b.read.position, b.count);
+    if element_offsets {
+      let mut offsets = Vec::new();
+      while b.remaining > 0 {
+        offsets.push(start + b.read.position);
+        b.skip_next()?;
+      }
+      bones.elements = Some(offsets);
+    }
+    b.skip()?;
+
+    let mut m = MorphReader::new(b)?;
+    let mut morphs = SectionIndex::new(start, m.read.position, m.count);
+    if element_offsets {
+      let mut offsets = Vec::new();
+      while m.remaining > 0 {
+        offsets.push(start + m.read.position);
+        m.skip_next()?;
+      }
+      morphs.elements = Some(offsets);
+    }
+    m.skip()?;
+
+    let mut d = DisplayFrameReader::new(m)?;
+    let display_frames = SectionIndex::new(start, d.read.position, d.count);
+    d.skip()?;
+
+    let mut r = RigidBodyReader::new(d)?;
+    let rigid_bodies = SectionIndex::new(start, r.read.position, r.count);
+    r.skip()?;
+
+    let mut j = JointReader::new(r)?;
+    let joints = SectionIndex::new(start, j.read.position, j.count);
+    j.skip()?;
+
+    let s = SoftBodyReader::new(j)?;
+    let soft_bodies = SectionIndex::new(start, s.read.position, s.count);
+
+    Ok(PmxIndex {
+      version,
+      settings,
+      limits,
+      vertices,
+      surfaces,
+      textures,
+      materials,
+      bones,
+      morphs,
+      display_frames,
+      rigid_bodies,
+      joints,
+      soft_bodies,
+    })
+  }
+
+  fn seek<'a, R: Read + Seek>(
+    &self,
+    read: &'a mut R,
+    section: &SectionIndex,
+    index: usize,
+  ) -> Result<(LimitedRead<&'a mut R>, i32)> {
+    let (offset, found) = section.locate(index);
+    read.seek(SeekFrom::Start(offset))?;
+    Ok((
+      LimitedRead::at(read, self.limits, offset),
+      section.count - found as i32,
+    ))
+  }
+
+  pub fn material<C: Config, R: Read + Seek>(
+    &self,
+    read: &mut R,
+    index: usize,
+  ) -> Result<Material<C>> {
+    Ok(self.materials_range(read, index..index + 1)?.remove(0))
+  }
+
+  pub fn materials_range<C: Config, R: Read + Seek>(
+    &self,
+    read: &mut R,
+    range: Range<usize>,
+  ) -> Result<Vec<Material<C>>> {
+    self.materials.check(Section::Materials, &range)?;
+    let (read, remaining) = self.seek(read, &self.materials, range.start)?;
+    let mut reader = MaterialReader {
+      version: self.version,
+      settings: self.settings,
+      count: self.materials.count,
+      remaining,
+      read,
+      poison: false,
+    };
+    while ((reader.count - reader.remaining) as usize) < range.start {
+      reader.skip_next()?;
+    }
+    reader.iter::<C>().take(range.len()).collect()
+  }
+
+  pub fn bone<C: Config, R: Read + Seek>(&self, read: &mut R, index: usize) -> Result<Bone<C>> {
+    Ok(self.bones_range(read, index..index + 1)?.remove(0))
+  }
+
+  pub fn bones_range<C: Config, R: Read + Seek>(
+    &self,
+    read: &mut R,
+    range: Range<usize>,
+  ) -> Result<Vec<Bone<C>>> {
+    self.bones.check(Section::Bones, &range)?;
+    let (read, remaining) = self.seek(read, &self.bones, range.start)?;
+    let mut reader = BoneReader {
+      version: self.version,
+      settings: self.settings,
+      count: self.bones.count,
+      remaining,
+      read,
+      poison: false,
+    };
+    while ((reader.count - reader.remaining) as usize) < range.start {
+      reader.skip_next()?;
+    }
+    reader.iter::<C>().take(range.len()).collect()
+  }
+
+  pub fn morph<C: Config, R: Read + Seek>(&self, read: &mut R, index: usize) -> Result<Morph<C>> {
+    Ok(self.morphs_range(read, index..index + 1)?.remove(0))
+  }
+
+  pub fn morphs_range<C: Config, R: Read + Seek>(
+    &self,
+    read: &mut R,
+    range: Range<usize>,
+  ) -> Result<Vec<Morph<C>>> {
+    self.morphs.check(Section::Morphs, &range)?;
+    let (read, remaining) = self.seek(read, &self.morphs, range.start)?;
+    let mut reader = MorphReader {
+      version: self.version,
+      settings: self.settings,
+      count: self.morphs.count,
+      remaining,
+      read,
+      poison: false,
+    };
+    while ((reader.count - reader.remaining) as usize) < range.start {
+      reader.skip_next()?;
+    }
+    reader.iter::<C>().take(range.len()).collect()
+  }
+}
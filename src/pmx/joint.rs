@@ -0,0 +1,100 @@
+use crate::{Config, Error};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u8)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum JointType {
+  Spring6Dof = 0,
+  SixDof = 1,
+  PointToPoint = 2,
+  ConeTwist = 3,
+  Slider = 4,
+  Hinge = 5,
+}
+
+impl Display for JointType {
+  fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+    match self {
+      JointType::Spring6Dof => write!(f, "spring 6dof"),
+      JointType::SixDof => write!(f, "6dof"),
+      JointType::PointToPoint => write!(f, "point to point"),
+      JointType::ConeTwist => write!(f, "cone twist"),
+      JointType::Slider => write!(f, "slider"),
+      JointType::Hinge => write!(f, "hinge"),
+    }
+  }
+}
+
+impl TryFrom<u8> for JointType {
+  type Error = Error;
+
+  fn try_from(value: u8) -> Result<Self, Self::Error> {
+    Ok(match value {
+      0 => JointType::Spring6Dof,
+      1 => JointType::SixDof,
+      2 => JointType::PointToPoint,
+      3 => JointType::ConeTwist,
+      4 => JointType::Slider,
+      5 => JointType::Hinge,
+      e => return Err(Error::InvalidJointType(e)),
+    })
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(bound(
+    serialize = "C::RigidbodyIndex: Serialize, C::Vec3: Serialize",
+    deserialize = "C::RigidbodyIndex: Deserialize<'de>, C::Vec3: Deserialize<'de>"
+  ))
+)]
+pub struct Joint<C: Config> {
+  pub local_name: String,
+  pub universal_name: String,
+  pub joint_type: JointType,
+  pub rigid_body_a: C::RigidbodyIndex,
+  pub rigid_body_b: C::RigidbodyIndex,
+  pub position: C::Vec3,
+  pub rotation: C::Vec3,
+  pub position_min: C::Vec3,
+  pub position_max: C::Vec3,
+  pub rotation_min: C::Vec3,
+  pub rotation_max: C::Vec3,
+  pub position_spring: C::Vec3,
+  pub rotation_spring: C::Vec3,
+}
+
+impl<C: Config> Display for Joint<C>
+where
+  C::RigidbodyIndex: Display,
+  C::Vec3: Display,
+{
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+    write!(
+      f,
+      r"local name: {}, universal name: {}, type: {}, rigid bodies: {} - {},
+position: {}, rotation: {},
+position limits: [{} - {}], rotation limits: [{} - {}],
+position spring: {}, rotation spring: {}",
+      self.local_name,
+      self.universal_name,
+      self.joint_type,
+      self.rigid_body_a,
+      self.rigid_body_b,
+      self.position,
+      self.rotation,
+      self.position_min,
+      self.position_max,
+      self.rotation_min,
+      self.rotation_max,
+      self.position_spring,
+      self.rotation_spring
+    )
+  }
+}
@@ -0,0 +1,36 @@
+use std::fmt::{Display, Formatter};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseLimits {
+  pub max_text_bytes: usize,
+  pub max_vertices: usize,
+  pub max_morph_offsets: usize,
+  pub max_ik_links: usize,
+  pub max_total_bytes: u64,
+}
+
+impl Default for ParseLimits {
+  fn default() -> Self {
+    ParseLimits {
+      max_text_bytes: usize::MAX,
+      max_vertices: usize::MAX,
+      max_morph_offsets: usize::MAX,
+      max_ik_links: usize::MAX,
+      max_total_bytes: u64::MAX,
+    }
+  }
+}
+
+impl Display for ParseLimits {
+  fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+    writeln!(
+      f,
+      "text bytes: {}, vertices: {}, morph offsets: {}, ik links: {}, total bytes: {}",
+      self.max_text_bytes,
+      self.max_vertices,
+      self.max_morph_offsets,
+      self.max_ik_links,
+      self.max_total_bytes
+    )
+  }
+}
@@ -1,6 +1,8 @@
 use crate::{Config, Error};
 use enumflags2::BitFlags;
 use itertools::Itertools;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::convert::TryFrom;
 use std::fmt::{Debug, Display, Formatter};
 
@@ -27,6 +29,7 @@ impl Display for DrawingFlagsFmt {
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[repr(u8)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum EnvironmentBlendMode {
   Disabled = 0,
   Multiply = 1,
@@ -60,6 +63,14 @@ impl TryFrom<u8> for EnvironmentBlendMode {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(bound(
+    serialize = "C::TextureIndex: Serialize",
+    deserialize = "C::TextureIndex: Deserialize<'de>"
+  ))
+)]
 pub enum Toon<C: Config> {
   Texture(C::TextureIndex),
   Internal(u8),
@@ -77,6 +88,15 @@ where
   }
 }
 
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(bound(
+    serialize = "C::TextureIndex: Serialize, C::Vec3: Serialize, C::Vec4: Serialize",
+    deserialize = "C::TextureIndex: Deserialize<'de>, C::Vec3: Deserialize<'de>, C::Vec4: Deserialize<'de>"
+  ))
+)]
 pub struct Material<C: Config> {
   pub local_name: String,
   pub universal_name: String,
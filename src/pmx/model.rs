@@ -0,0 +1,391 @@
+#[cfg(feature = "async")]
+use crate::pmx::async_reader::*;
+use crate::{
+  pmx::{display_frame::DisplayFrame, morph::Morph},
+  reader::*,
+  writer::*,
+  Bone, Config, IndexSize, Joint, Material, ParseLimits, Result, RigidBody, Settings, SoftBody,
+  TextEncoding, Vertex,
+};
+#[cfg(feature = "async")]
+use futures_io::AsyncRead;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(bound(
+    serialize = "C::VertexIndex: Serialize, C::TextureIndex: Serialize, C::MaterialIndex: Serialize, C::BoneIndex: Serialize, C::MorphIndex: Serialize, C::RigidbodyIndex: Serialize, C::Vec2: Serialize, C::Vec3: Serialize, C::Vec4: Serialize, C::AdditionalVec4s: Serialize",
+    deserialize = "C::VertexIndex: Deserialize<'de>, C::TextureIndex: Deserialize<'de>, C::MaterialIndex: Deserialize<'de>, C::BoneIndex: Deserialize<'de>, C::MorphIndex: Deserialize<'de>, C::RigidbodyIndex: Deserialize<'de>, C::Vec2: Deserialize<'de>, C::Vec3: Deserialize<'de>, C::Vec4: Deserialize<'de>, C::AdditionalVec4s: Deserialize<'de>"
+  ))
+)]
+pub struct Model<C: Config> {
+  pub version: f32,
+  pub settings: Settings,
+  pub extra_globals: Vec<u8>,
+  pub model_local_name: String,
+  pub model_universal_name: String,
+  pub local_comments: String,
+  pub universal_comments: String,
+  pub vertices: Vec<Vertex<C>>,
+  pub surfaces: Vec<[C::VertexIndex; 3]>,
+  pub textures: Vec<String>,
+  pub materials: Vec<Material<C>>,
+  pub bones: Vec<Bone<C>>,
+  pub morphs: Vec<Morph<C>>,
+  pub display_frames: Vec<DisplayFrame<C>>,
+  pub rigid_bodies: Vec<RigidBody<C>>,
+  pub joints: Vec<Joint<C>>,
+  pub soft_bodies: Vec<SoftBody<C>>,
+}
+
+impl<C: Config> Model<C> {
+  pub fn open<P: AsRef<Path>>(path: P) -> Result<Model<C>> {
+    Self::read(BufReader::new(File::open(path)?))
+  }
+
+  pub fn read<R: Read>(read: R) -> Result<Model<C>> {
+    Self::read_with_limits(read, ParseLimits::default())
+  }
+
+  pub fn read_with_limits<R: Read>(read: R, limits: ParseLimits) -> Result<Model<C>> {
+    let header = HeaderReader::with_limits(read, limits)?;
+    let version = header.version;
+    let settings = header.settings;
+    let extra_globals = header.extra_globals.clone();
+    let model_local_name = header.model_local_name.clone();
+    let model_universal_name = header.model_universal_name.clone();
+    let local_comments = header.local_comments.clone();
+    let universal_comments = header.universal_comments.clone();
+
+    let mut vertices = VertexReader::new(header)?;
+    let vertex_list = vertices.iter::<C>().collect::<Result<_>>()?;
+
+    let mut surfaces = SurfaceReader::new(vertices)?;
+    let surface_list = surfaces.iter::<C>().collect::<Result<_>>()?;
+
+    let mut textures = TextureReader::new(surfaces)?;
+    let texture_list = textures.iter().collect::<Result<_>>()?;
+
+    let mut materials = MaterialReader::new(textures)?;
+    let material_list = materials.iter::<C>().collect::<Result<_>>()?;
+
+    let mut bones = BoneReader::new(materials)?;
+    let bone_list = bones.iter::<C>().collect::<Result<_>>()?;
+
+    let mut morphs = MorphReader::new(bones)?;
+    let morph_list = morphs.iter::<C>().collect::<Result<_>>()?;
+
+    let mut display_frames = DisplayFrameReader::new(morphs)?;
+    let display_frame_list = display_frames.iter::<C>().collect::<Result<_>>()?;
+
+    let mut rigid_bodies = RigidBodyReader::new(display_frames)?;
+    let rigid_body_list = rigid_bodies.iter::<C>().collect::<Result<_>>()?;
+
+    let mut joints = JointReader::new(rigid_bodies)?;
+    let joint_list = joints.iter::<C>().collect::<Result<_>>()?;
+
+    let mut soft_bodies = SoftBodyReader::new(joints)?;
+    let soft_body_list = soft_bodies.iter::<C>().collect::<Result<_>>()?;
+
+    Ok(Model {
+      version,
+      settings,
+      extra_globals,
+      model_local_name,
+      model_universal_name,
+      local_comments,
+      universal_comments,
+      vertices: vertex_list,
+      surfaces: surface_list,
+      textures: texture_list,
+      materials: material_list,
+      bones: bone_list,
+      morphs: morph_list,
+      display_frames: display_frame_list,
+      rigid_bodies: rigid_body_list,
+      joints: joint_list,
+      soft_bodies: soft_body_list,
+    })
+  }
+}
+
+#[cfg(feature = "async")]
+impl<C: Config> Model<C> {
+  pub async fn read_async<R: AsyncRead + Unpin>(read: R) -> Result<Model<C>> {
+    Self::read_async_with_limits(read, ParseLimits::default()).await
+  }
+
+  pub async fn read_async_with_limits<R: AsyncRead + Unpin>(
+    read: R,
+    limits: ParseLimits,
+  ) -> Result<Model<C>> {
+    let header = AsyncHeaderReader::with_limits(read, limits).await?;
+    let mut model = Model {
+      version: header.version,
+      settings: header.settings,
+      extra_globals: header.extra_globals.clone(),
+      model_local_name: header.model_local_name.clone(),
+      model_universal_name: header.model_universal_name.clone(),
+      local_comments: header.local_comments.clone(),
+      universal_comments: header.universal_comments.clone(),
+      vertices: Vec::new(),
+      surfaces: Vec::new(),
+      textures: Vec::new(),
+      materials: Vec::new(),
+      bones: Vec::new(),
+      morphs: Vec::new(),
+      display_frames: Vec::new(),
+      rigid_bodies: Vec::new(),
+      joints: Vec::new(),
+      soft_bodies: Vec::new(),
+    };
+
+    let mut vertices = AsyncVertexReader::new(header).await?;
+    while let Some(vertex) = vertices.next::<C>().await? {
+      model.vertices.push(vertex);
+    }
+
+    let mut surfaces = AsyncSurfaceReader::new(vertices).await?;
+    while let Some(surface) = surfaces.next::<C>().await? {
+      model.surfaces.push(surface);
+    }
+
+    let mut textures = AsyncTextureReader::new(surfaces).await?;
+    while let Some(texture) = textures.next().await? {
+      model.textures.push(texture);
+    }
+
+    let mut materials = AsyncMaterialReader::new(textures).await?;
+    while let Some(material) = materials.next::<C>().await? {
+      model.materials.push(material);
+    }
+
+    let mut bones = AsyncBoneReader::new(materials).await?;
+    while let Some(bone) = bones.next::<C>().await? {
+      model.bones.push(bone);
+    }
+
+    let mut morphs = AsyncMorphReader::new(bones).await?;
+    while let Some(morph) = morphs.next::<C>().await? {
+      model.morphs.push(morph);
+    }
+
+    let mut display_frames = AsyncDisplayFrameReader::new(morphs).await?;
+    while let Some(display_frame) = display_frames.next::<C>().await? {
+      model.display_frames.push(display_frame);
+    }
+
+    let mut rigid_bodies = AsyncRigidBodyReader::new(display_frames).await?;
+    while let Some(rigid_body) = rigid_bodies.next::<C>().await? {
+      model.rigid_bodies.push(rigid_body);
+    }
+
+    let mut joints = AsyncJointReader::new(rigid_bodies).await?;
+    while let Some(joint) = joints.next::<C>().await? {
+      model.joints.push(joint);
+    }
+
+    let mut soft_bodies = AsyncSoftBodyReader::new(joints).await?;
+    while let Some(soft_body) = soft_bodies.next::<C>().await? {
+      model.soft_bodies.push(soft_body);
+    }
+
+    Ok(model)
+  }
+}
+
+impl<C: Config> Model<C> {
+  pub fn optimal_settings(&self) -> Settings {
+    let additional_vec4_count = self
+      .vertices
+      .iter()
+      .map(|v| v.additional.as_ref().len())
+      .max()
+      .unwrap_or(0);
+    // both encodings represent any string, so pick the one producing fewer bytes
+    let utf8 = self
+      .texts()
+      .map(|t| TextEncoding::UTF8.encoded_len(t))
+      .sum::<usize>();
+    let utf16 = self
+      .texts()
+      .map(|t| TextEncoding::UTF16LE.encoded_len(t))
+      .sum::<usize>();
+
+    Settings {
+      text_encoding: if utf8 <= utf16 {
+        TextEncoding::UTF8
+      } else {
+        TextEncoding::UTF16LE
+      },
+      additional_vec4_count: additional_vec4_count as u8,
+      vertex_index_size: IndexSize::for_vertex_count(self.vertices.len()),
+      texture_index_size: IndexSize::for_count(self.textures.len()),
+      material_index_size: IndexSize::for_count(self.materials.len()),
+      bone_index_size: IndexSize::for_count(self.bones.len()),
+      morph_index_size: IndexSize::for_count(self.morphs.len()),
+      rigidbody_index_size: IndexSize::for_count(self.rigid_bodies.len()),
+    }
+  }
+
+  pub fn optimize(&mut self) {
+    self.settings = self.optimal_settings();
+  }
+
+  pub fn reencode(&mut self, text_encoding: TextEncoding) {
+    // text is decoded on read, so only the encoding used by the writer changes
+    self.settings.text_encoding = text_encoding;
+  }
+
+  fn texts(&self) -> impl Iterator<Item = &str> {
+    let header = vec![
+      &self.model_local_name,
+      &self.model_universal_name,
+      &self.local_comments,
+      &self.universal_comments,
+    ];
+    let materials = self
+      .materials
+      .iter()
+      .flat_map(|m| vec![&m.local_name, &m.universal_name, &m.metadata]);
+    let bones = self
+      .bones
+      .iter()
+      .flat_map(|b| vec![&b.local_name, &b.universal_name]);
+    let morphs = self
+      .morphs
+      .iter()
+      .flat_map(|m| vec![&m.local_name, &m.universal_name]);
+    let display_frames = self
+      .display_frames
+      .iter()
+      .flat_map(|d| vec![&d.local_name, &d.universal_name]);
+    let rigid_bodies = self
+      .rigid_bodies
+      .iter()
+      .flat_map(|r| vec![&r.local_name, &r.universal_name]);
+    let joints = self
+      .joints
+      .iter()
+      .flat_map(|j| vec![&j.local_name, &j.universal_name]);
+    let soft_bodies = self
+      .soft_bodies
+      .iter()
+      .flat_map(|s| vec![&s.local_name, &s.universal_name]);
+
+    header
+      .into_iter()
+      .chain(self.textures.iter())
+      .chain(materials)
+      .chain(bones)
+      .chain(morphs)
+      .chain(display_frames)
+      .chain(rigid_bodies)
+      .chain(joints)
+      .chain(soft_bodies)
+      .map(String::as_str)
+  }
+
+  pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+    self.write(BufWriter::new(File::create(path)?))?;
+    Ok(())
+  }
+
+  pub fn write<W: Write>(&self, write: W) -> Result<W> {
+    let mut header = HeaderWriter::new(write, self.version, self.settings);
+    header.extra_globals = self.extra_globals.clone();
+    header.model_local_name = self.model_local_name.clone();
+    header.model_universal_name = self.model_universal_name.clone();
+    header.local_comments = self.local_comments.clone();
+    header.universal_comments = self.universal_comments.clone();
+
+    let mut vertices = VertexWriter::new(header, self.vertices.len())?;
+    for vertex in &self.vertices {
+      vertices.write(vertex)?;
+    }
+
+    let mut surfaces = SurfaceWriter::new(vertices, self.surfaces.len() * 3)?;
+    for surface in &self.surfaces {
+      surfaces.write::<C>(surface)?;
+    }
+
+    let mut textures = TextureWriter::new(surfaces, self.textures.len())?;
+    for texture in &self.textures {
+      textures.write(texture)?;
+    }
+
+    let mut materials = MaterialWriter::new(textures, self.materials.len())?;
+    for material in &self.materials {
+      materials.write(material)?;
+    }
+
+    let mut bones = BoneWriter::new(materials, self.bones.len())?;
+    for bone in &self.bones {
+      bones.write(bone)?;
+    }
+
+    let mut morphs = MorphWriter::new(bones, self.morphs.len())?;
+    for morph in &self.morphs {
+      morphs.write(morph)?;
+    }
+
+    let mut display_frames = DisplayFrameWriter::new(morphs, self.display_frames.len())?;
+    for display_frame in &self.display_frames {
+      display_frames.write(display_frame)?;
+    }
+
+    let mut rigid_bodies = RigidBodyWriter::new(display_frames, self.rigid_bodies.len())?;
+    for rigid_body in &self.rigid_bodies {
+      rigid_bodies.write(rigid_body)?;
+    }
+
+    let mut joints = JointWriter::new(rigid_bodies, self.joints.len())?;
+    for joint in &self.joints {
+      joints.write(joint)?;
+    }
+
+    let mut soft_bodies = SoftBodyWriter::new(joints, self.soft_bodies.len())?;
+    for soft_body in &self.soft_bodies {
+      soft_bodies.write(soft_body)?;
+    }
+
+    soft_bodies.finish()
+  }
+}
+
+impl<C: Config> Display for Model<C> {
+  fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    write!(f, "version: {}, ", self.version)?;
+    self.settings.fmt(f)?;
+    writeln!(
+      f,
+      "local name: {}, universal name: {}\nLocal comments\n{}\nUniversal comments\n{}",
+      self.model_local_name,
+      self.model_universal_name,
+      self.local_comments,
+      self.universal_comments
+    )?;
+    write!(
+      f,
+      r"vertices: {}, surfaces: {}, textures: {}, materials: {}, bones: {}, morphs: {},
+display frames: {}, rigid bodies: {}, joints: {}, soft bodies: {}",
+      self.vertices.len(),
+      self.surfaces.len(),
+      self.textures.len(),
+      self.materials.len(),
+      self.bones.len(),
+      self.morphs.len(),
+      self.display_frames.len(),
+      self.rigid_bodies.len(),
+      self.joints.len(),
+      self.soft_bodies.len()
+    )
+  }
+}
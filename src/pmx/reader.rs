@@ -1,16 +1,25 @@
 pub mod bone;
+pub mod display_frame;
 pub mod header;
-mod helpers;
+pub(crate) mod helpers;
+pub mod joint;
+pub(crate) mod limited;
 pub mod material;
 pub mod morph;
+pub mod rigid_body;
+pub mod soft_body;
 pub mod surface;
 pub mod texture;
 pub mod vertex;
 
 pub use bone::BoneReader;
+pub use display_frame::DisplayFrameReader;
 pub use header::HeaderReader;
+pub use joint::JointReader;
 pub use material::MaterialReader;
 pub use morph::MorphReader;
+pub use rigid_body::RigidBodyReader;
+pub use soft_body::SoftBodyReader;
 pub use surface::SurfaceReader;
 pub use texture::TextureReader;
 pub use vertex::VertexReader;
@@ -1,30 +1,43 @@
 use crate::{
   pmx::bone::*,
-  reader::{helpers::ReadHelpers, MaterialReader},
+  pmx::error::Section,
+  reader::{
+    helpers::{bounded_capacity, ReadHelpers},
+    limited::LimitedRead,
+    MaterialReader,
+  },
   Config, DefaultConfig, Error, Result, Settings,
 };
 use byteorder::{ReadBytesExt, LE};
 use enumflags2::BitFlags;
+use std::io::{Read, Seek};
 use std::marker::PhantomData;
-use std::{io::Read, usize};
 
 pub struct BoneReader<R> {
+  pub version: f32,
   pub settings: Settings,
   pub count: i32,
   pub remaining: i32,
-  pub(crate) read: R,
+  pub(crate) read: LimitedRead<R>,
   pub(crate) poison: bool,
 }
 
 impl<R: Read> BoneReader<R> {
   pub fn new(mut m: MaterialReader<R>) -> Result<BoneReader<R>> {
-    assert!(!m.poison);
+    if m.poison {
+      return Err(Error::Poisoned);
+    }
     while m.remaining > 0 {
       m.next::<DefaultConfig>()?;
     }
-    let count = m.read.read_i32::<LE>()?;
+    let offset = m.read.position;
+    let count = m
+      .read
+      .read_count()
+      .map_err(|e| e.at(Section::Bones, None, offset))?;
 
     Ok(BoneReader {
+      version: m.version,
       settings: m.settings,
       count,
       remaining: count,
@@ -34,12 +47,16 @@ impl<R: Read> BoneReader<R> {
   }
 
   pub fn next<C: Config>(&mut self) -> Result<Option<Bone<C>>> {
-    assert!(!self.poison);
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    let element = (self.count - self.remaining) as usize;
+    let offset = self.read.position;
     let result = self.next_impl::<C>();
     if result.is_err() {
       self.poison = true;
     }
-    result
+    result.map_err(|e| e.at(Section::Bones, Some(element), offset))
   }
 
   fn next_impl<C: Config>(&mut self) -> Result<Option<Bone<C>>> {
@@ -54,7 +71,9 @@ impl<R: Read> BoneReader<R> {
     let position = self.read.read_vec3::<C>()?;
     let parent = self.read.read_index(self.settings.bone_index_size)?;
     let transform_level = self.read.read_i32::<LE>()?;
-    let bone_flags = BitFlags::from_bits(self.read.read_u16::<LE>()?).unwrap();
+    let raw_flags = self.read.read_u16::<LE>()?;
+    let bone_flags = BitFlags::<BoneFlags>::from_bits_truncate(raw_flags);
+    let unknown_flags = raw_flags & !bone_flags.bits();
 
     let connection = bone_flags
       .contains(BoneFlags::Connection)
@@ -100,17 +119,25 @@ impl<R: Read> BoneReader<R> {
       let iterations = self.read.read_u32::<LE>()?;
       let limit_angle = self.read.read_f32::<LE>()?;
       let link_count = self.read.read_u32::<LE>()? as usize;
-      let mut links = Vec::with_capacity(link_count);
+      self
+        .read
+        .check_limit("ik links", link_count, self.read.limits().max_ik_links)?;
+      let mut links = Vec::with_capacity(bounded_capacity(link_count));
       for _i in 0..link_count {
         let ik_bone = self
           .read
           .read_index::<C::BoneIndex>(self.settings.bone_index_size)?;
-        let limits = if self.read.read_u8()? != 0 {
+        let raw_limits = self.read.read_u8()?;
+        let limits = if raw_limits != 0 {
           Some((self.read.read_vec3::<C>()?, self.read.read_vec3::<C>()?))
         } else {
           None
         };
-        links.push(IKLink { ik_bone, limits })
+        links.push(IKLink {
+          ik_bone,
+          limits,
+          raw_limits,
+        })
       }
 
       Some(InverseKinematics {
@@ -130,6 +157,7 @@ impl<R: Read> BoneReader<R> {
       parent,
       transform_level,
       bone_flags,
+      unknown_flags,
       connection,
       additional,
       fixed_axis,
@@ -147,6 +175,73 @@ impl<R: Read> BoneReader<R> {
   }
 }
 
+impl<R: Read + Seek> BoneReader<R> {
+  pub fn skip(&mut self) -> Result<()> {
+    while self.remaining > 0 {
+      self.skip_next()?;
+    }
+    Ok(())
+  }
+
+  pub(crate) fn skip_next(&mut self) -> Result<()> {
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    let element = (self.count - self.remaining) as usize;
+    let offset = self.read.position;
+    let result = self.skip_impl();
+    if result.is_err() {
+      self.poison = true;
+    }
+    result.map_err(|e| e.at(Section::Bones, Some(element), offset))
+  }
+
+  fn skip_impl(&mut self) -> Result<()> {
+    let index = self.settings.bone_index_size as u64;
+    self.read.skip_text()?;
+    self.read.skip_text()?;
+    self.read.skip(12 + index + 4)?;
+    let flags = self.read.read_u16::<LE>()?;
+    let has = |flag: BoneFlags| flags & flag as u16 != 0;
+
+    let mut size = if has(BoneFlags::Connection) {
+      index
+    } else {
+      12
+    };
+    if has(BoneFlags::AddRotation) || has(BoneFlags::AddMovement) {
+      size += index + 4;
+    }
+    if has(BoneFlags::FixedAxis) {
+      size += 12;
+    }
+    if has(BoneFlags::LocalAxis) {
+      size += 24;
+    }
+    if has(BoneFlags::ExternalParentTransform) {
+      size += 4;
+    }
+    if has(BoneFlags::InverseKinematics) {
+      self.read.skip(size + index + 4 + 4)?;
+      let link_count = self.read.read_u32::<LE>()? as usize;
+      self
+        .read
+        .check_limit("ik links", link_count, self.read.limits().max_ik_links)?;
+      for _ in 0..link_count {
+        self.read.skip(index)?;
+        if self.read.read_u8()? != 0 {
+          self.read.skip(24)?;
+        }
+      }
+    } else {
+      self.read.skip(size)?;
+    }
+
+    self.remaining -= 1;
+    Ok(())
+  }
+}
+
 pub struct BoneIterator<'a, R, C = DefaultConfig> {
   reader: &'a mut BoneReader<R>,
   phantom: PhantomData<C>,
@@ -0,0 +1,165 @@
+use crate::{
+  pmx::display_frame::*,
+  pmx::error::Section,
+  reader::{
+    helpers::{bounded_capacity, ReadHelpers},
+    limited::LimitedRead,
+    MorphReader,
+  },
+  Config, DefaultConfig, Error, Result, Settings,
+};
+use byteorder::ReadBytesExt;
+use std::io::{Read, Seek};
+use std::marker::PhantomData;
+
+pub struct DisplayFrameReader<R> {
+  pub version: f32,
+  pub settings: Settings,
+  pub count: i32,
+  pub remaining: i32,
+  pub(crate) read: LimitedRead<R>,
+  pub(crate) poison: bool,
+}
+
+impl<R: Read> DisplayFrameReader<R> {
+  pub fn new(mut m: MorphReader<R>) -> Result<DisplayFrameReader<R>> {
+    if m.poison {
+      return Err(Error::Poisoned);
+    }
+    while m.remaining > 0 {
+      m.next::<DefaultConfig>()?;
+    }
+    let offset = m.read.position;
+    let count = m
+      .read
+      .read_count()
+      .map_err(|e| e.at(Section::DisplayFrames, None, offset))?;
+
+    Ok(DisplayFrameReader {
+      version: m.version,
+      settings: m.settings,
+      count,
+      remaining: count,
+      read: m.read,
+      poison: false,
+    })
+  }
+
+  pub fn next<C: Config>(&mut self) -> Result<Option<DisplayFrame<C>>> {
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    let element = (self.count - self.remaining) as usize;
+    let offset = self.read.position;
+    let result = self.next_impl::<C>();
+    if result.is_err() {
+      self.poison = true;
+    }
+    result.map_err(|e| e.at(Section::DisplayFrames, Some(element), offset))
+  }
+
+  fn next_impl<C: Config>(&mut self) -> Result<Option<DisplayFrame<C>>> {
+    if self.remaining <= 0 {
+      return Ok(None);
+    }
+
+    self.remaining -= 1;
+
+    let local_name = self.read.read_text(self.settings.text_encoding)?;
+    let universal_name = self.read.read_text(self.settings.text_encoding)?;
+    let raw_special = self.read.read_u8()?;
+    let element_count = self.read.read_count()?;
+
+    let mut elements = Vec::with_capacity(bounded_capacity(element_count as usize));
+    for _ in 0..element_count {
+      elements.push(match self.read.read_u8()? {
+        0 => FrameElement::Bone(self.read.read_index(self.settings.bone_index_size)?),
+        1 => FrameElement::Morph(self.read.read_index(self.settings.morph_index_size)?),
+        e => return Err(Error::InvalidFrameElementType(e)),
+      });
+    }
+
+    Ok(Some(DisplayFrame {
+      local_name,
+      universal_name,
+      special: raw_special != 0,
+      raw_special,
+      elements,
+    }))
+  }
+
+  pub fn iter<C>(&mut self) -> DisplayFrameIterator<'_, R, C> {
+    DisplayFrameIterator {
+      reader: self,
+      phantom: PhantomData,
+    }
+  }
+}
+
+impl<R: Read + Seek> DisplayFrameReader<R> {
+  pub fn skip(&mut self) -> Result<()> {
+    while self.remaining > 0 {
+      self.skip_next()?;
+    }
+    Ok(())
+  }
+
+  pub(crate) fn skip_next(&mut self) -> Result<()> {
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    let element = (self.count - self.remaining) as usize;
+    let offset = self.read.position;
+    let result = self.skip_impl();
+    if result.is_err() {
+      self.poison = true;
+    }
+    result.map_err(|e| e.at(Section::DisplayFrames, Some(element), offset))
+  }
+
+  fn skip_impl(&mut self) -> Result<()> {
+    self.read.skip_text()?;
+    self.read.skip_text()?;
+    self.read.skip(1)?;
+    let element_count = self.read.read_count()?;
+    for _ in 0..element_count {
+      match self.read.read_u8()? {
+        0 => self.read.skip(self.settings.bone_index_size as u64)?,
+        1 => self.read.skip(self.settings.morph_index_size as u64)?,
+        e => return Err(Error::InvalidFrameElementType(e)),
+      }
+    }
+
+    self.remaining -= 1;
+    Ok(())
+  }
+}
+
+pub struct DisplayFrameIterator<'a, R, C = DefaultConfig> {
+  reader: &'a mut DisplayFrameReader<R>,
+  phantom: PhantomData<C>,
+}
+
+impl<R: Read, C: Config> Iterator for DisplayFrameIterator<'_, R, C> {
+  type Item = Result<DisplayFrame<C>>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self
+      .reader
+      .next()
+      .map_or_else(|e| Some(Err(e)), |v| v.map(Ok))
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (
+      self.reader.remaining as usize,
+      Some(self.reader.remaining as usize),
+    )
+  }
+}
+
+impl<R: Read, C: Config> ExactSizeIterator for DisplayFrameIterator<'_, R, C> {
+  fn len(&self) -> usize {
+    self.reader.remaining as usize
+  }
+}
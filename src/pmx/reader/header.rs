@@ -1,4 +1,8 @@
-use crate::{pmx::types::*, reader::helpers::ReadHelpers, Error, Settings};
+use crate::{
+  pmx::{error::Section, types::*},
+  reader::{helpers::ReadHelpers, limited::LimitedRead},
+  Error, ParseLimits, Settings,
+};
 use byteorder::{ReadBytesExt, LE};
 use std::convert::TryFrom;
 use std::fmt::{Display, Formatter};
@@ -7,15 +11,25 @@ use std::io::Read;
 pub struct HeaderReader<R> {
   pub version: f32,
   pub settings: Settings,
+  // globals beyond the 8 known ones, unused by current PMX versions
+  pub extra_globals: Vec<u8>,
   pub model_local_name: String,
   pub model_universal_name: String,
   pub local_comments: String,
   pub universal_comments: String,
-  pub(crate) read: R,
+  pub(crate) read: LimitedRead<R>,
 }
 
 impl<R: Read> HeaderReader<R> {
-  pub fn new(mut read: R) -> Result<HeaderReader<R>, Error> {
+  pub fn new(read: R) -> Result<HeaderReader<R>, Error> {
+    Self::with_limits(read, ParseLimits::default())
+  }
+
+  pub fn with_limits(read: R, limits: ParseLimits) -> Result<HeaderReader<R>, Error> {
+    Self::read_header(LimitedRead::new(read, limits)).map_err(|e| e.at(Section::Header, None, 0))
+  }
+
+  fn read_header(mut read: LimitedRead<R>) -> Result<HeaderReader<R>, Error> {
     let mut magic = [0u8; 4];
     read.read_exact(&mut magic)?;
     if magic != [0x50, 0x4D, 0x58, 0x20] {
@@ -32,20 +46,11 @@ impl<R: Read> HeaderReader<R> {
     globals.resize(globals_count as usize, 0u8);
     read.read_exact(&mut globals)?;
 
-    let settings = Settings {
-      text_encoding: TextEncoding::try_from(globals[0])?,
-      additional_vec4_count: globals[1],
-      vertex_index_size: IndexSize::try_from(globals[2])?,
-      texture_index_size: IndexSize::try_from(globals[3])?,
-      material_index_size: IndexSize::try_from(globals[4])?,
-      bone_index_size: IndexSize::try_from(globals[5])?,
-      morph_index_size: IndexSize::try_from(globals[6])?,
-      rigidbody_index_size: IndexSize::try_from(globals[7])?,
-    };
-
+    let settings = settings(&globals)?;
     Ok(HeaderReader::<R> {
       version,
       settings,
+      extra_globals: globals.split_off(8),
       model_local_name: read.read_text(settings.text_encoding)?,
       model_universal_name: read.read_text(settings.text_encoding)?,
       local_comments: read.read_text(settings.text_encoding)?,
@@ -55,6 +60,23 @@ impl<R: Read> HeaderReader<R> {
   }
 }
 
+pub(crate) fn settings(globals: &[u8]) -> Result<Settings, Error> {
+  if globals[1] > 4 {
+    return Err(Error::InvalidAdditionalVec4Count(globals[1]));
+  }
+
+  Ok(Settings {
+    text_encoding: TextEncoding::try_from(globals[0])?,
+    additional_vec4_count: globals[1],
+    vertex_index_size: IndexSize::try_from(globals[2])?,
+    texture_index_size: IndexSize::try_from(globals[3])?,
+    material_index_size: IndexSize::try_from(globals[4])?,
+    bone_index_size: IndexSize::try_from(globals[5])?,
+    morph_index_size: IndexSize::try_from(globals[6])?,
+    rigidbody_index_size: IndexSize::try_from(globals[7])?,
+  })
+}
+
 impl<R> Display for HeaderReader<R> {
   fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
     write!(f, "version: {}, ", self.version)?;
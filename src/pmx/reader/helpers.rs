@@ -1,15 +1,50 @@
-use crate::{pmx::types::*, Error, Result};
+use crate::{pmx::types::*, reader::limited::LimitedRead, Error, ParseLimits, Result};
 use byteorder::{ReadBytesExt, LE};
 use encoding::all::{UTF_16LE, UTF_8};
 use encoding::{DecoderTrap, Encoding};
-use std::io::Read;
+use std::io::{ErrorKind, Read};
+
+// Counts come straight from the file, so never trust them for more than this
+// many elements up front and let the vectors grow as the data actually arrives.
+const MAX_PREALLOCATED: usize = 4096;
+
+pub(crate) fn bounded_capacity(count: usize) -> usize {
+  count.min(MAX_PREALLOCATED)
+}
 
 pub(crate) trait ReadHelpers: Read {
+  fn limits(&self) -> &ParseLimits;
+
+  fn check_limit(&self, what: &'static str, requested: usize, limit: usize) -> Result<()> {
+    if requested > limit {
+      return Err(Error::LimitExceeded {
+        what,
+        requested: requested as u64,
+        limit: limit as u64,
+      });
+    }
+    Ok(())
+  }
+
+  fn read_count(&mut self) -> Result<i32> {
+    let count = self.read_i32::<LE>()?;
+    if count < 0 {
+      return Err(Error::NegativeCount(count));
+    }
+    Ok(count)
+  }
+
   fn read_text(&mut self, encoding: TextEncoding) -> Result<String> {
     let size = self.read_i32::<LE>()?;
-    let mut buf = Vec::with_capacity(size as usize);
-    buf.resize(size as usize, 0u8);
-    self.read_exact(&mut buf)?;
+    if size < 0 {
+      return Err(Error::NegativeTextSize(size));
+    }
+    self.check_limit("text bytes", size as usize, self.limits().max_text_bytes)?;
+    let mut buf = Vec::with_capacity(bounded_capacity(size as usize));
+    self.take(size as u64).read_to_end(&mut buf)?;
+    if buf.len() != size as usize {
+      return Err(Error::Io(ErrorKind::UnexpectedEof.into()));
+    }
     (match encoding {
       TextEncoding::UTF8 => UTF_8.decode(&buf, DecoderTrap::Strict),
       TextEncoding::UTF16LE => UTF_16LE.decode(&buf, DecoderTrap::Strict),
@@ -79,4 +114,8 @@ pub(crate) trait ReadHelpers: Read {
   }
 }
 
-impl<R: Read> ReadHelpers for R {}
+impl<R: Read> ReadHelpers for LimitedRead<R> {
+  fn limits(&self) -> &ParseLimits {
+    &self.limits
+  }
+}
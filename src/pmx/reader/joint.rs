@@ -0,0 +1,151 @@
+use crate::{
+  pmx::error::Section,
+  pmx::joint::*,
+  reader::{helpers::ReadHelpers, limited::LimitedRead, RigidBodyReader},
+  Config, DefaultConfig, Error, Result, Settings,
+};
+use byteorder::ReadBytesExt;
+use std::convert::TryFrom;
+use std::io::{Read, Seek};
+use std::marker::PhantomData;
+
+pub struct JointReader<R> {
+  pub version: f32,
+  pub settings: Settings,
+  pub count: i32,
+  pub remaining: i32,
+  pub(crate) read: LimitedRead<R>,
+  pub(crate) poison: bool,
+}
+
+impl<R: Read> JointReader<R> {
+  pub fn new(mut r: RigidBodyReader<R>) -> Result<JointReader<R>> {
+    if r.poison {
+      return Err(Error::Poisoned);
+    }
+    while r.remaining > 0 {
+      r.next::<DefaultConfig>()?;
+    }
+    let offset = r.read.position;
+    let count = r
+      .read
+      .read_count()
+      .map_err(|e| e.at(Section::Joints, None, offset))?;
+
+    Ok(JointReader {
+      version: r.version,
+      settings: r.settings,
+      count,
+      remaining: count,
+      read: r.read,
+      poison: false,
+    })
+  }
+
+  pub fn next<C: Config>(&mut self) -> Result<Option<Joint<C>>> {
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    let element = (self.count - self.remaining) as usize;
+    let offset = self.read.position;
+    let result = self.next_impl::<C>();
+    if result.is_err() {
+      self.poison = true;
+    }
+    result.map_err(|e| e.at(Section::Joints, Some(element), offset))
+  }
+
+  fn next_impl<C: Config>(&mut self) -> Result<Option<Joint<C>>> {
+    if self.remaining <= 0 {
+      return Ok(None);
+    }
+
+    self.remaining -= 1;
+
+    Ok(Some(Joint {
+      local_name: self.read.read_text(self.settings.text_encoding)?,
+      universal_name: self.read.read_text(self.settings.text_encoding)?,
+      joint_type: JointType::try_from(self.read.read_u8()?)?,
+      rigid_body_a: self.read.read_index(self.settings.rigidbody_index_size)?,
+      rigid_body_b: self.read.read_index(self.settings.rigidbody_index_size)?,
+      position: self.read.read_vec3::<C>()?,
+      rotation: self.read.read_vec3::<C>()?,
+      position_min: self.read.read_vec3::<C>()?,
+      position_max: self.read.read_vec3::<C>()?,
+      rotation_min: self.read.read_vec3::<C>()?,
+      rotation_max: self.read.read_vec3::<C>()?,
+      position_spring: self.read.read_vec3::<C>()?,
+      rotation_spring: self.read.read_vec3::<C>()?,
+    }))
+  }
+
+  pub fn iter<C>(&mut self) -> JointIterator<'_, R, C> {
+    JointIterator {
+      reader: self,
+      phantom: PhantomData,
+    }
+  }
+}
+
+impl<R: Read + Seek> JointReader<R> {
+  pub fn skip(&mut self) -> Result<()> {
+    while self.remaining > 0 {
+      self.skip_next()?;
+    }
+    Ok(())
+  }
+
+  pub(crate) fn skip_next(&mut self) -> Result<()> {
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    let element = (self.count - self.remaining) as usize;
+    let offset = self.read.position;
+    let result = self.skip_impl();
+    if result.is_err() {
+      self.poison = true;
+    }
+    result.map_err(|e| e.at(Section::Joints, Some(element), offset))
+  }
+
+  fn skip_impl(&mut self) -> Result<()> {
+    self.read.skip_text()?;
+    self.read.skip_text()?;
+    // joint type, both rigid bodies and eight vectors
+    self
+      .read
+      .skip(1 + 2 * self.settings.rigidbody_index_size as u64 + 8 * 12)?;
+
+    self.remaining -= 1;
+    Ok(())
+  }
+}
+
+pub struct JointIterator<'a, R, C = DefaultConfig> {
+  reader: &'a mut JointReader<R>,
+  phantom: PhantomData<C>,
+}
+
+impl<R: Read, C: Config> Iterator for JointIterator<'_, R, C> {
+  type Item = Result<Joint<C>>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self
+      .reader
+      .next()
+      .map_or_else(|e| Some(Err(e)), |v| v.map(Ok))
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (
+      self.reader.remaining as usize,
+      Some(self.reader.remaining as usize),
+    )
+  }
+}
+
+impl<R: Read, C: Config> ExactSizeIterator for JointIterator<'_, R, C> {
+  fn len(&self) -> usize {
+    self.reader.remaining as usize
+  }
+}
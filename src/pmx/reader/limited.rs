@@ -0,0 +1,102 @@
+use crate::{Error, ParseLimits, Result};
+use byteorder::{ReadBytesExt, LE};
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+use std::io::{self, ErrorKind, Read, Seek, SeekFrom};
+
+// Short gaps are cheaper to read through than to seek over,
+// seeking drops the buffer of a BufReader
+const SEEK_THRESHOLD: u64 = 4096;
+
+pub(crate) struct LimitedRead<R> {
+  inner: R,
+  pub(crate) position: u64,
+  pub(crate) limits: ParseLimits,
+}
+
+impl<R> LimitedRead<R> {
+  pub(crate) fn new(inner: R, limits: ParseLimits) -> Self {
+    Self::at(inner, limits, 0)
+  }
+
+  pub(crate) fn at(inner: R, limits: ParseLimits, position: u64) -> Self {
+    LimitedRead {
+      inner,
+      position,
+      limits,
+    }
+  }
+
+  #[cfg(feature = "async")]
+  pub(crate) fn get_mut(&mut self) -> &mut R {
+    &mut self.inner
+  }
+}
+
+impl<R: Read + Seek> LimitedRead<R> {
+  pub(crate) fn skip(&mut self, bytes: u64) -> Result<()> {
+    if bytes < SEEK_THRESHOLD {
+      if io::copy(&mut self.by_ref().take(bytes), &mut io::sink())? != bytes {
+        return Err(Error::Io(ErrorKind::UnexpectedEof.into()));
+      }
+      return Ok(());
+    }
+
+    let requested = self.position.saturating_add(bytes);
+    if requested > self.limits.max_total_bytes {
+      return Err(Error::LimitExceeded {
+        what: "total bytes",
+        requested,
+        limit: self.limits.max_total_bytes,
+      });
+    }
+    let offset = i64::try_from(bytes).map_err(|_| Error::Io(ErrorKind::InvalidInput.into()))?;
+    self.inner.seek(SeekFrom::Current(offset))?;
+    self.position = requested;
+    Ok(())
+  }
+
+  pub(crate) fn skip_text(&mut self) -> Result<()> {
+    let size = self.read_i32::<LE>()?;
+    if size < 0 {
+      return Err(Error::NegativeTextSize(size));
+    }
+    self.skip(size as u64)
+  }
+}
+
+impl<R: Read> Read for LimitedRead<R> {
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    let available = self.limits.max_total_bytes.saturating_sub(self.position);
+    if available == 0 && !buf.is_empty() {
+      return Err(io::Error::other(TotalBytesExceeded {
+        requested: self.position.saturating_add(buf.len() as u64),
+        limit: self.limits.max_total_bytes,
+      }));
+    }
+    let len = buf.len().min(available.min(usize::MAX as u64) as usize);
+    let read = self.inner.read(&mut buf[..len])?;
+    self.position += read as u64;
+    Ok(read)
+  }
+}
+
+// Travels inside io::Error through byteorder and read_exact,
+// Error::from unpacks it back into Error::LimitExceeded
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct TotalBytesExceeded {
+  pub(crate) requested: u64,
+  pub(crate) limit: u64,
+}
+
+impl Display for TotalBytesExceeded {
+  fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    write!(
+      f,
+      "requested {} bytes, limit {}",
+      self.requested, self.limit
+    )
+  }
+}
+
+impl std::error::Error for TotalBytesExceeded {}
@@ -1,31 +1,40 @@
 use crate::{
+  pmx::error::Section,
   pmx::material::*,
-  reader::{helpers::ReadHelpers, TextureReader},
+  reader::{helpers::ReadHelpers, limited::LimitedRead, TextureReader},
   Config, DefaultConfig, Error, Result, Settings,
 };
 use byteorder::{ReadBytesExt, LE};
 use enumflags2::BitFlags;
 use std::convert::TryFrom;
-use std::io::Read;
+use std::io::{Read, Seek};
 use std::marker::PhantomData;
 
 pub struct MaterialReader<R> {
+  pub version: f32,
   pub settings: Settings,
   pub count: i32,
   pub remaining: i32,
-  pub(crate) read: R,
+  pub(crate) read: LimitedRead<R>,
   pub(crate) poison: bool,
 }
 
 impl<R: Read> MaterialReader<R> {
   pub fn new(mut t: TextureReader<R>) -> Result<MaterialReader<R>> {
-    assert!(!t.poison);
+    if t.poison {
+      return Err(Error::Poisoned);
+    }
     while t.remaining > 0 {
       t.next()?;
     }
-    let count = t.read.read_i32::<LE>()?;
+    let offset = t.read.position;
+    let count = t
+      .read
+      .read_count()
+      .map_err(|e| e.at(Section::Materials, None, offset))?;
 
     Ok(MaterialReader {
+      version: t.version,
       settings: t.settings,
       count,
       remaining: count,
@@ -35,12 +44,16 @@ impl<R: Read> MaterialReader<R> {
   }
 
   pub fn next<C: Config>(&mut self) -> Result<Option<Material<C>>> {
-    assert!(!self.poison);
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    let element = (self.count - self.remaining) as usize;
+    let offset = self.read.position;
     let result = self.next_impl::<C>();
     if result.is_err() {
       self.poison = true;
     }
-    result
+    result.map_err(|e| e.at(Section::Materials, Some(element), offset))
   }
 
   fn next_impl<C: Config>(&mut self) -> Result<Option<Material<C>>> {
@@ -50,14 +63,23 @@ impl<R: Read> MaterialReader<R> {
 
     self.remaining -= 1;
 
+    let local_name = self.read.read_text(self.settings.text_encoding)?;
+    let universal_name = self.read.read_text(self.settings.text_encoding)?;
+    let diffuse_color = self.read.read_vec4::<C>()?;
+    let specular_color = self.read.read_vec3::<C>()?;
+    let specular_strength = self.read.read_f32::<LE>()?;
+    let ambient_color = self.read.read_vec3::<C>()?;
+    // every bit is a known flag, nothing is lost
+    let draw_flags = BitFlags::from_bits_truncate(self.read.read_u8()?);
+
     Ok(Some(Material {
-      local_name: self.read.read_text(self.settings.text_encoding)?,
-      universal_name: self.read.read_text(self.settings.text_encoding)?,
-      diffuse_color: self.read.read_vec4::<C>()?,
-      specular_color: self.read.read_vec3::<C>()?,
-      specular_strength: self.read.read_f32::<LE>()?,
-      ambient_color: self.read.read_vec3::<C>()?,
-      draw_flags: BitFlags::from_bits(self.read.read_u8()?).unwrap(),
+      local_name,
+      universal_name,
+      diffuse_color,
+      specular_color,
+      specular_strength,
+      ambient_color,
+      draw_flags,
       edge_color: self.read.read_vec4::<C>()?,
       edge_scale: self.read.read_f32::<LE>()?,
       texture_index: self.read.read_index(self.settings.texture_index_size)?,
@@ -81,6 +103,46 @@ impl<R: Read> MaterialReader<R> {
   }
 }
 
+impl<R: Read + Seek> MaterialReader<R> {
+  pub fn skip(&mut self) -> Result<()> {
+    while self.remaining > 0 {
+      self.skip_next()?;
+    }
+    Ok(())
+  }
+
+  pub(crate) fn skip_next(&mut self) -> Result<()> {
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    let element = (self.count - self.remaining) as usize;
+    let offset = self.read.position;
+    let result = self.skip_impl();
+    if result.is_err() {
+      self.poison = true;
+    }
+    result.map_err(|e| e.at(Section::Materials, Some(element), offset))
+  }
+
+  fn skip_impl(&mut self) -> Result<()> {
+    let texture_index = self.settings.texture_index_size as u64;
+    self.read.skip_text()?;
+    self.read.skip_text()?;
+    // colors and flags, edge, texture and environment indices, blend mode
+    self.read.skip(44 + 1 + 20 + 2 * texture_index + 1)?;
+    match self.read.read_u8()? {
+      0 => self.read.skip(texture_index)?,
+      1 => self.read.skip(1)?,
+      e => return Err(Error::InvalidToonReference(e)),
+    }
+    self.read.skip_text()?;
+    self.read.skip(4)?;
+
+    self.remaining -= 1;
+    Ok(())
+  }
+}
+
 pub struct MaterialIterator<'a, R, C = DefaultConfig> {
   reader: &'a mut MaterialReader<R>,
   phantom: PhantomData<C>,
@@ -1,30 +1,43 @@
 use crate::{
+  pmx::error::Section,
   pmx::morph::*,
-  reader::{helpers::ReadHelpers, BoneReader},
+  reader::{
+    helpers::{bounded_capacity, ReadHelpers},
+    limited::LimitedRead,
+    BoneReader,
+  },
   Config, DefaultConfig, Error, Result, Settings,
 };
 use byteorder::{ReadBytesExt, LE};
 use std::convert::TryFrom;
-use std::io::Read;
+use std::io::{Read, Seek};
 use std::marker::PhantomData;
 
 pub struct MorphReader<R> {
+  pub version: f32,
   pub settings: Settings,
   pub count: i32,
   pub remaining: i32,
-  pub(crate) read: R,
+  pub(crate) read: LimitedRead<R>,
   pub(crate) poison: bool,
 }
 
 impl<R: Read> MorphReader<R> {
   pub fn new(mut b: BoneReader<R>) -> Result<MorphReader<R>> {
-    assert!(!b.poison);
+    if b.poison {
+      return Err(Error::Poisoned);
+    }
     while b.remaining > 0 {
       b.next::<DefaultConfig>()?;
     }
-    let count = b.read.read_i32::<LE>()?;
+    let offset = b.read.position;
+    let count = b
+      .read
+      .read_count()
+      .map_err(|e| e.at(Section::Morphs, None, offset))?;
 
     Ok(MorphReader {
+      version: b.version,
       settings: b.settings,
       count,
       remaining: count,
@@ -34,12 +47,16 @@ impl<R: Read> MorphReader<R> {
   }
 
   pub fn next<C: Config>(&mut self) -> Result<Option<Morph<C>>> {
-    assert!(!self.poison);
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    let element = (self.count - self.remaining) as usize;
+    let offset = self.read.position;
     let result = self.next_impl::<C>();
     if result.is_err() {
       self.poison = true;
     }
-    result
+    result.map_err(|e| e.at(Section::Morphs, Some(element), offset))
   }
 
   fn next_impl<C: Config>(&mut self) -> Result<Option<Morph<C>>> {
@@ -54,6 +71,11 @@ impl<R: Read> MorphReader<R> {
     let panel = Panel::from(self.read.read_u8()?);
     let morph_type = self.read.read_u8()?;
     let morph_count = self.read.read_u32::<LE>()?;
+    self.read.check_limit(
+      "morph offsets",
+      morph_count as usize,
+      self.read.limits().max_morph_offsets,
+    )?;
 
     let offsets = match morph_type {
       0 => Offsets::Group(self.next_morph_offsets(morph_count)?),
@@ -86,7 +108,7 @@ impl<R: Read> MorphReader<R> {
   }
 
   fn next_morph_offsets<C: Config>(&mut self, count: u32) -> Result<Vec<GroupOffset<C>>> {
-    let mut offsets = Vec::with_capacity(count as usize);
+    let mut offsets = Vec::with_capacity(bounded_capacity(count as usize));
 
     for _ in 0..count {
       offsets.push(GroupOffset {
@@ -99,7 +121,7 @@ impl<R: Read> MorphReader<R> {
   }
 
   fn next_vertex_offsets<C: Config>(&mut self, count: u32) -> Result<Vec<VertexOffset<C>>> {
-    let mut offsets = Vec::with_capacity(count as usize);
+    let mut offsets = Vec::with_capacity(bounded_capacity(count as usize));
 
     for _ in 0..count {
       offsets.push(VertexOffset {
@@ -114,7 +136,7 @@ impl<R: Read> MorphReader<R> {
   }
 
   fn next_bone_offsets<C: Config>(&mut self, count: u32) -> Result<Vec<BoneOffset<C>>> {
-    let mut offsets = Vec::with_capacity(count as usize);
+    let mut offsets = Vec::with_capacity(bounded_capacity(count as usize));
 
     for _ in 0..count {
       offsets.push(BoneOffset {
@@ -128,7 +150,7 @@ impl<R: Read> MorphReader<R> {
   }
 
   fn next_uv_offsets<C: Config>(&mut self, count: u32) -> Result<Vec<UVOffset<C>>> {
-    let mut offsets = Vec::with_capacity(count as usize);
+    let mut offsets = Vec::with_capacity(bounded_capacity(count as usize));
 
     for _ in 0..count {
       offsets.push(UVOffset {
@@ -143,7 +165,7 @@ impl<R: Read> MorphReader<R> {
   }
 
   fn next_material_offsets<C: Config>(&mut self, count: u32) -> Result<Vec<MaterialOffset<C>>> {
-    let mut offsets = Vec::with_capacity(count as usize);
+    let mut offsets = Vec::with_capacity(bounded_capacity(count as usize));
 
     for _ in 0..count {
       offsets.push(MaterialOffset {
@@ -165,12 +187,15 @@ impl<R: Read> MorphReader<R> {
   }
 
   fn next_impulse_offsets<C: Config>(&mut self, count: u32) -> Result<Vec<ImpulseOffset<C>>> {
-    let mut offsets = Vec::with_capacity(count as usize);
+    let mut offsets = Vec::with_capacity(bounded_capacity(count as usize));
 
     for _ in 0..count {
+      let rigid_body = self.read.read_index(self.settings.rigidbody_index_size)?;
+      let raw_local = self.read.read_u8()?;
       offsets.push(ImpulseOffset {
-        rigid_body: self.read.read_index(self.settings.rigidbody_index_size)?,
-        local: self.read.read_u8()? != 0,
+        rigid_body,
+        local: raw_local != 0,
+        raw_local,
         velocity: self.read.read_vec3::<C>()?,
         torque: self.read.read_vec3::<C>()?,
       })
@@ -180,6 +205,56 @@ impl<R: Read> MorphReader<R> {
   }
 }
 
+impl<R: Read + Seek> MorphReader<R> {
+  pub fn skip(&mut self) -> Result<()> {
+    while self.remaining > 0 {
+      self.skip_next()?;
+    }
+    Ok(())
+  }
+
+  pub(crate) fn skip_next(&mut self) -> Result<()> {
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    let element = (self.count - self.remaining) as usize;
+    let offset = self.read.position;
+    let result = self.skip_impl();
+    if result.is_err() {
+      self.poison = true;
+    }
+    result.map_err(|e| e.at(Section::Morphs, Some(element), offset))
+  }
+
+  fn skip_impl(&mut self) -> Result<()> {
+    self.read.skip_text()?;
+    self.read.skip_text()?;
+    self.read.skip(1)?;
+    let morph_type = self.read.read_u8()?;
+    let morph_count = self.read.read_u32::<LE>()?;
+    self.read.check_limit(
+      "morph offsets",
+      morph_count as usize,
+      self.read.limits().max_morph_offsets,
+    )?;
+
+    let size = match morph_type {
+      0 | 9 => self.settings.morph_index_size as u64 + 4,
+      1 => self.settings.vertex_index_size as u64 + 12,
+      2 => self.settings.bone_index_size as u64 + 12 + 16,
+      3..=7 => self.settings.vertex_index_size as u64 + 16,
+      // method, diffuse, specular, strength, ambient, edge color and scale, three tints
+      8 => self.settings.material_index_size as u64 + 1 + 16 + 12 + 4 + 12 + 16 + 4 + 3 * 16,
+      10 => self.settings.rigidbody_index_size as u64 + 1 + 24,
+      e => return Err(Error::InvalidMorphType(e)),
+    };
+    self.read.skip(morph_count as u64 * size)?;
+
+    self.remaining -= 1;
+    Ok(())
+  }
+}
+
 pub struct MorphIterator<'a, R, C> {
   reader: &'a mut MorphReader<R>,
   phantom: PhantomData<C>,
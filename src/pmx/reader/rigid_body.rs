@@ -0,0 +1,154 @@
+use crate::{
+  pmx::error::Section,
+  pmx::rigid_body::*,
+  reader::{helpers::ReadHelpers, limited::LimitedRead, DisplayFrameReader},
+  Config, DefaultConfig, Error, Result, Settings,
+};
+use byteorder::{ReadBytesExt, LE};
+use std::convert::TryFrom;
+use std::io::{Read, Seek};
+use std::marker::PhantomData;
+
+pub struct RigidBodyReader<R> {
+  pub version: f32,
+  pub settings: Settings,
+  pub count: i32,
+  pub remaining: i32,
+  pub(crate) read: LimitedRead<R>,
+  pub(crate) poison: bool,
+}
+
+impl<R: Read> RigidBodyReader<R> {
+  pub fn new(mut d: DisplayFrameReader<R>) -> Result<RigidBodyReader<R>> {
+    if d.poison {
+      return Err(Error::Poisoned);
+    }
+    while d.remaining > 0 {
+      d.next::<DefaultConfig>()?;
+    }
+    let offset = d.read.position;
+    let count = d
+      .read
+      .read_count()
+      .map_err(|e| e.at(Section::RigidBodies, None, offset))?;
+
+    Ok(RigidBodyReader {
+      version: d.version,
+      settings: d.settings,
+      count,
+      remaining: count,
+      read: d.read,
+      poison: false,
+    })
+  }
+
+  pub fn next<C: Config>(&mut self) -> Result<Option<RigidBody<C>>> {
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    let element = (self.count - self.remaining) as usize;
+    let offset = self.read.position;
+    let result = self.next_impl::<C>();
+    if result.is_err() {
+      self.poison = true;
+    }
+    result.map_err(|e| e.at(Section::RigidBodies, Some(element), offset))
+  }
+
+  fn next_impl<C: Config>(&mut self) -> Result<Option<RigidBody<C>>> {
+    if self.remaining <= 0 {
+      return Ok(None);
+    }
+
+    self.remaining -= 1;
+
+    Ok(Some(RigidBody {
+      local_name: self.read.read_text(self.settings.text_encoding)?,
+      universal_name: self.read.read_text(self.settings.text_encoding)?,
+      bone_index: self.read.read_index(self.settings.bone_index_size)?,
+      group: self.read.read_u8()?,
+      non_collision_mask: self.read.read_u16::<LE>()?,
+      shape: ShapeType::try_from(self.read.read_u8()?)?,
+      shape_size: self.read.read_vec3::<C>()?,
+      shape_position: self.read.read_vec3::<C>()?,
+      shape_rotation: self.read.read_vec3::<C>()?,
+      mass: self.read.read_f32::<LE>()?,
+      move_attenuation: self.read.read_f32::<LE>()?,
+      rotation_damping: self.read.read_f32::<LE>()?,
+      repulsion: self.read.read_f32::<LE>()?,
+      friction: self.read.read_f32::<LE>()?,
+      physics_mode: PhysicsMode::try_from(self.read.read_u8()?)?,
+    }))
+  }
+
+  pub fn iter<C>(&mut self) -> RigidBodyIterator<'_, R, C> {
+    RigidBodyIterator {
+      reader: self,
+      phantom: PhantomData,
+    }
+  }
+}
+
+impl<R: Read + Seek> RigidBodyReader<R> {
+  pub fn skip(&mut self) -> Result<()> {
+    while self.remaining > 0 {
+      self.skip_next()?;
+    }
+    Ok(())
+  }
+
+  pub(crate) fn skip_next(&mut self) -> Result<()> {
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    let element = (self.count - self.remaining) as usize;
+    let offset = self.read.position;
+    let result = self.skip_impl();
+    if result.is_err() {
+      self.poison = true;
+    }
+    result.map_err(|e| e.at(Section::RigidBodies, Some(element), offset))
+  }
+
+  fn skip_impl(&mut self) -> Result<()> {
+    self.read.skip_text()?;
+    self.read.skip_text()?;
+    // group, mask and shape, shape size, position and rotation,
+    // five physical parameters and physics mode
+    self
+      .read
+      .skip(self.settings.bone_index_size as u64 + 4 + 36 + 20 + 1)?;
+
+    self.remaining -= 1;
+    Ok(())
+  }
+}
+
+pub struct RigidBodyIterator<'a, R, C = DefaultConfig> {
+  reader: &'a mut RigidBodyReader<R>,
+  phantom: PhantomData<C>,
+}
+
+impl<R: Read, C: Config> Iterator for RigidBodyIterator<'_, R, C> {
+  type Item = Result<RigidBody<C>>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self
+      .reader
+      .next()
+      .map_or_else(|e| Some(Err(e)), |v| v.map(Ok))
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (
+      self.reader.remaining as usize,
+      Some(self.reader.remaining as usize),
+    )
+  }
+}
+
+impl<R: Read, C: Config> ExactSizeIterator for RigidBodyIterator<'_, R, C> {
+  fn len(&self) -> usize {
+    self.reader.remaining as usize
+  }
+}
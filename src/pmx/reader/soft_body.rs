@@ -0,0 +1,210 @@
+use crate::{
+  pmx::error::Section,
+  pmx::soft_body::*,
+  reader::{
+    helpers::{bounded_capacity, ReadHelpers},
+    limited::LimitedRead,
+    JointReader,
+  },
+  Config, DefaultConfig, Error, Result, Settings,
+};
+use byteorder::{ReadBytesExt, LE};
+use enumflags2::BitFlags;
+use std::convert::TryFrom;
+use std::io::Read;
+use std::marker::PhantomData;
+
+pub struct SoftBodyReader<R> {
+  pub version: f32,
+  pub settings: Settings,
+  pub count: i32,
+  pub remaining: i32,
+  pub(crate) read: LimitedRead<R>,
+  pub(crate) poison: bool,
+}
+
+impl<R: Read> SoftBodyReader<R> {
+  pub fn new(mut j: JointReader<R>) -> Result<SoftBodyReader<R>> {
+    if j.poison {
+      return Err(Error::Poisoned);
+    }
+    while j.remaining > 0 {
+      j.next::<DefaultConfig>()?;
+    }
+    // soft bodies were introduced in PMX 2.1, older files end after joints
+    let offset = j.read.position;
+    let count = if j.version >= 2.1 {
+      j.read
+        .read_count()
+        .map_err(|e| e.at(Section::SoftBodies, None, offset))?
+    } else {
+      0
+    };
+
+    Ok(SoftBodyReader {
+      version: j.version,
+      settings: j.settings,
+      count,
+      remaining: count,
+      read: j.read,
+      poison: false,
+    })
+  }
+
+  pub fn next<C: Config>(&mut self) -> Result<Option<SoftBody<C>>> {
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    let element = (self.count - self.remaining) as usize;
+    let offset = self.read.position;
+    let result = self.next_impl::<C>();
+    if result.is_err() {
+      self.poison = true;
+    }
+    result.map_err(|e| e.at(Section::SoftBodies, Some(element), offset))
+  }
+
+  fn next_impl<C: Config>(&mut self) -> Result<Option<SoftBody<C>>> {
+    if self.remaining <= 0 {
+      return Ok(None);
+    }
+
+    self.remaining -= 1;
+
+    let local_name = self.read.read_text(self.settings.text_encoding)?;
+    let universal_name = self.read.read_text(self.settings.text_encoding)?;
+    let shape = SoftBodyShape::try_from(self.read.read_u8()?)?;
+    let material_index = self.read.read_index(self.settings.material_index_size)?;
+    let group = self.read.read_u8()?;
+    let non_collision_mask = self.read.read_u16::<LE>()?;
+    let raw_flags = self.read.read_u8()?;
+    let flags = BitFlags::<SoftBodyFlags>::from_bits_truncate(raw_flags);
+    let unknown_flags = raw_flags & !flags.bits();
+    let b_link_distance = self.read.read_i32::<LE>()?;
+    let cluster_count = self.read.read_i32::<LE>()?;
+    let total_mass = self.read.read_f32::<LE>()?;
+    let collision_margin = self.read.read_f32::<LE>()?;
+    let aero_model = AeroModel::try_from(self.read.read_i32::<LE>()?)?;
+
+    let coefficients = SoftBodyCoefficients {
+      velocity_correction: self.read.read_f32::<LE>()?,
+      damping: self.read.read_f32::<LE>()?,
+      drag: self.read.read_f32::<LE>()?,
+      lift: self.read.read_f32::<LE>()?,
+      pressure: self.read.read_f32::<LE>()?,
+      volume_conservation: self.read.read_f32::<LE>()?,
+      dynamic_friction: self.read.read_f32::<LE>()?,
+      pose_matching: self.read.read_f32::<LE>()?,
+      rigid_contact_hardness: self.read.read_f32::<LE>()?,
+      kinetic_contact_hardness: self.read.read_f32::<LE>()?,
+      soft_contact_hardness: self.read.read_f32::<LE>()?,
+      anchor_hardness: self.read.read_f32::<LE>()?,
+    };
+
+    let cluster = SoftBodyCluster {
+      soft_rigid_hardness: self.read.read_f32::<LE>()?,
+      soft_kinetic_hardness: self.read.read_f32::<LE>()?,
+      soft_soft_hardness: self.read.read_f32::<LE>()?,
+      soft_rigid_impulse_split: self.read.read_f32::<LE>()?,
+      soft_kinetic_impulse_split: self.read.read_f32::<LE>()?,
+      soft_soft_impulse_split: self.read.read_f32::<LE>()?,
+    };
+
+    let iterations = SoftBodyIterations {
+      velocity: self.read.read_i32::<LE>()?,
+      position: self.read.read_i32::<LE>()?,
+      drift: self.read.read_i32::<LE>()?,
+      cluster: self.read.read_i32::<LE>()?,
+    };
+
+    let material = SoftBodyMaterial {
+      linear_stiffness: self.read.read_f32::<LE>()?,
+      area_stiffness: self.read.read_f32::<LE>()?,
+      volume_stiffness: self.read.read_f32::<LE>()?,
+    };
+
+    let anchor_count = self.read.read_count()?;
+    let mut anchors = Vec::with_capacity(bounded_capacity(anchor_count as usize));
+    for _ in 0..anchor_count {
+      let rigid_body = self.read.read_index(self.settings.rigidbody_index_size)?;
+      let vertex = self
+        .read
+        .read_vertex_index(self.settings.vertex_index_size)?;
+      let raw_near_mode = self.read.read_u8()?;
+      anchors.push(SoftBodyAnchor {
+        rigid_body,
+        vertex,
+        near_mode: raw_near_mode != 0,
+        raw_near_mode,
+      })
+    }
+
+    let pin_count = self.read.read_count()?;
+    let mut pin_vertices = Vec::with_capacity(bounded_capacity(pin_count as usize));
+    for _ in 0..pin_count {
+      pin_vertices.push(
+        self
+          .read
+          .read_vertex_index(self.settings.vertex_index_size)?,
+      )
+    }
+
+    Ok(Some(SoftBody {
+      local_name,
+      universal_name,
+      shape,
+      material_index,
+      group,
+      non_collision_mask,
+      flags,
+      unknown_flags,
+      b_link_distance,
+      cluster_count,
+      total_mass,
+      collision_margin,
+      aero_model,
+      coefficients,
+      cluster,
+      iterations,
+      material,
+      anchors,
+      pin_vertices,
+    }))
+  }
+
+  pub fn iter<C>(&mut self) -> SoftBodyIterator<'_, R, C> {
+    SoftBodyIterator {
+      reader: self,
+      phantom: PhantomData,
+    }
+  }
+}
+
+pub struct SoftBodyIterator<'a, R, C = DefaultConfig> {
+  reader: &'a mut SoftBodyReader<R>,
+  phantom: PhantomData<C>,
+}
+
+impl<R: Read, C: Config> Iterator for SoftBodyIterator<'_, R, C> {
+  type Item = Result<SoftBody<C>>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self
+      .reader
+      .next()
+      .map_or_else(|e| Some(Err(e)), |v| v.map(Ok))
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (
+      self.reader.remaining as usize,
+      Some(self.reader.remaining as usize),
+    )
+  }
+}
+
+impl<R: Read, C: Config> ExactSizeIterator for SoftBodyIterator<'_, R, C> {
+  fn len(&self) -> usize {
+    self.reader.remaining as usize
+  }
+}
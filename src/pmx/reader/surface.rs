@@ -1,28 +1,39 @@
 use crate::{
-  reader::{helpers::ReadHelpers, VertexReader},
-  Config, DefaultConfig, Result, Settings,
+  pmx::error::Section,
+  reader::{helpers::ReadHelpers, limited::LimitedRead, VertexReader},
+  Config, DefaultConfig, Error, Result, Settings,
 };
-use byteorder::{ReadBytesExt, LE};
-use std::io::Read;
+use std::io::{Read, Seek};
 use std::marker::PhantomData;
 
 pub struct SurfaceReader<R> {
+  pub version: f32,
   pub settings: Settings,
   pub count: i32,
   pub remaining: i32,
-  pub(crate) read: R,
+  pub(crate) read: LimitedRead<R>,
   pub(crate) poison: bool,
 }
 
 impl<R: Read> SurfaceReader<R> {
   pub fn new(mut v: VertexReader<R>) -> Result<SurfaceReader<R>> {
-    assert!(!v.poison);
+    if v.poison {
+      return Err(Error::Poisoned);
+    }
     while v.remaining > 0 {
       v.next::<DefaultConfig>()?;
     }
-    let count = v.read.read_i32::<LE>()?;
+    let offset = v.read.position;
+    let count = v
+      .read
+      .read_count()
+      .map_err(|e| e.at(Section::Surfaces, None, offset))?;
+    if count % 3 != 0 {
+      return Err(Error::InvalidSurfaceCount(count).at(Section::Surfaces, None, offset));
+    }
 
     Ok(SurfaceReader {
+      version: v.version,
       settings: v.settings,
       count,
       remaining: count,
@@ -32,12 +43,16 @@ impl<R: Read> SurfaceReader<R> {
   }
 
   pub fn next<C: Config>(&mut self) -> Result<Option<[C::VertexIndex; 3]>> {
-    assert!(!self.poison);
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    let element = ((self.count - self.remaining) / 3) as usize;
+    let offset = self.read.position;
     let result = self.next_impl::<C>();
     if result.is_err() {
       self.poison = true;
     }
-    result
+    result.map_err(|e| e.at(Section::Surfaces, Some(element), offset))
   }
 
   fn next_impl<C: Config>(&mut self) -> Result<Option<[C::VertexIndex; 3]>> {
@@ -67,6 +82,37 @@ impl<R: Read> SurfaceReader<R> {
   }
 }
 
+impl<R: Read + Seek> SurfaceReader<R> {
+  pub fn skip(&mut self) -> Result<()> {
+    while self.remaining > 0 {
+      self.skip_next()?;
+    }
+    Ok(())
+  }
+
+  pub(crate) fn skip_next(&mut self) -> Result<()> {
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    let element = ((self.count - self.remaining) / 3) as usize;
+    let offset = self.read.position;
+    let result = self.skip_impl();
+    if result.is_err() {
+      self.poison = true;
+    }
+    result.map_err(|e| e.at(Section::Surfaces, Some(element), offset))
+  }
+
+  fn skip_impl(&mut self) -> Result<()> {
+    self
+      .read
+      .skip(self.remaining as u64 * self.settings.vertex_index_size as u64)?;
+
+    self.remaining = 0;
+    Ok(())
+  }
+}
+
 pub struct SurfaceIterator<'a, R, C = DefaultConfig> {
   reader: &'a mut SurfaceReader<R>,
   phantom: PhantomData<C>,
@@ -1,27 +1,35 @@
 use crate::{
-  reader::{helpers::ReadHelpers, SurfaceReader},
-  DefaultConfig, Result, Settings,
+  pmx::error::Section,
+  reader::{helpers::ReadHelpers, limited::LimitedRead, SurfaceReader},
+  DefaultConfig, Error, Result, Settings,
 };
-use byteorder::{ReadBytesExt, LE};
-use std::io::Read;
+use std::io::{Read, Seek};
 
 pub struct TextureReader<R> {
+  pub version: f32,
   pub settings: Settings,
   pub count: i32,
   pub remaining: i32,
-  pub(crate) read: R,
+  pub(crate) read: LimitedRead<R>,
   pub(crate) poison: bool,
 }
 
 impl<R: Read> TextureReader<R> {
   pub fn new(mut s: SurfaceReader<R>) -> Result<TextureReader<R>> {
-    assert!(!s.poison);
+    if s.poison {
+      return Err(Error::Poisoned);
+    }
     while s.remaining > 0 {
       s.next::<DefaultConfig>()?;
     }
-    let count = s.read.read_i32::<LE>()?;
+    let offset = s.read.position;
+    let count = s
+      .read
+      .read_count()
+      .map_err(|e| e.at(Section::Textures, None, offset))?;
 
     Ok(TextureReader {
+      version: s.version,
       settings: s.settings,
       count,
       remaining: count,
@@ -31,12 +39,16 @@ impl<R: Read> TextureReader<R> {
   }
 
   pub fn next(&mut self) -> Result<Option<String>> {
-    assert!(!self.poison);
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    let element = (self.count - self.remaining) as usize;
+    let offset = self.read.position;
     let result = self.next_impl();
     if result.is_err() {
       self.poison = true;
     }
-    result
+    result.map_err(|e| e.at(Section::Textures, Some(element), offset))
   }
 
   fn next_impl(&mut self) -> Result<Option<String>> {
@@ -54,6 +66,35 @@ impl<R: Read> TextureReader<R> {
   }
 }
 
+impl<R: Read + Seek> TextureReader<R> {
+  pub fn skip(&mut self) -> Result<()> {
+    while self.remaining > 0 {
+      self.skip_next()?;
+    }
+    Ok(())
+  }
+
+  pub(crate) fn skip_next(&mut self) -> Result<()> {
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    let element = (self.count - self.remaining) as usize;
+    let offset = self.read.position;
+    let result = self.skip_impl();
+    if result.is_err() {
+      self.poison = true;
+    }
+    result.map_err(|e| e.at(Section::Textures, Some(element), offset))
+  }
+
+  fn skip_impl(&mut self) -> Result<()> {
+    self.read.skip_text()?;
+
+    self.remaining -= 1;
+    Ok(())
+  }
+}
+
 pub struct TextureIterator<'a, R> {
   reader: &'a mut TextureReader<R>,
 }
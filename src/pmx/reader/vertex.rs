@@ -1,24 +1,40 @@
 use crate::{
+  pmx::error::Section,
   pmx::weight_deform::*,
-  reader::{helpers::ReadHelpers, HeaderReader},
+  reader::{helpers::ReadHelpers, limited::LimitedRead, HeaderReader},
   Config, DefaultConfig, Error, Result, Settings, Vertex,
 };
 use byteorder::{ReadBytesExt, LE};
+use std::io::{Read, Seek};
 use std::marker::PhantomData;
-use std::{io::Read, usize};
 
 pub struct VertexReader<R> {
+  pub version: f32,
   pub settings: Settings,
   pub count: i32,
   pub remaining: i32,
-  pub(crate) read: R,
+  pub(crate) read: LimitedRead<R>,
   pub(crate) poison: bool,
 }
 
 impl<R: Read> VertexReader<R> {
   pub fn new(mut header: HeaderReader<R>) -> Result<VertexReader<R>> {
-    let count = header.read.read_i32::<LE>()?;
+    let offset = header.read.position;
+    let count = header
+      .read
+      .read_count()
+      .and_then(|count| {
+        header.read.check_limit(
+          "vertices",
+          count as usize,
+          header.read.limits().max_vertices,
+        )?;
+        Ok(count)
+      })
+      .map_err(|e| e.at(Section::Vertices, None, offset))?;
+
     Ok(VertexReader {
+      version: header.version,
       settings: header.settings,
       count,
       remaining: count,
@@ -28,12 +44,16 @@ impl<R: Read> VertexReader<R> {
   }
 
   pub fn next<C: Config>(&mut self) -> Result<Option<Vertex<C>>> {
-    assert!(!self.poison);
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    let element = (self.count - self.remaining) as usize;
+    let offset = self.read.position;
     let result = self.next_impl::<C>();
     if result.is_err() {
       self.poison = true;
     }
-    result
+    result.map_err(|e| e.at(Section::Vertices, Some(element), offset))
   }
 
   fn next_impl<C: Config>(&mut self) -> Result<Option<Vertex<C>>> {
@@ -106,6 +126,46 @@ impl<R: Read> VertexReader<R> {
   }
 }
 
+impl<R: Read + Seek> VertexReader<R> {
+  pub fn skip(&mut self) -> Result<()> {
+    while self.remaining > 0 {
+      self.skip_next()?;
+    }
+    Ok(())
+  }
+
+  pub(crate) fn skip_next(&mut self) -> Result<()> {
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    let element = (self.count - self.remaining) as usize;
+    let offset = self.read.position;
+    let result = self.skip_impl();
+    if result.is_err() {
+      self.poison = true;
+    }
+    result.map_err(|e| e.at(Section::Vertices, Some(element), offset))
+  }
+
+  fn skip_impl(&mut self) -> Result<()> {
+    let fixed = (3 + 3 + 2 + 4 * self.settings.additional_vec4_count as u64) * 4;
+    let index = self.settings.bone_index_size as u64;
+    self.read.skip(fixed)?;
+    let weights = match self.read.read_u8()? {
+      0 => index,
+      1 => 2 * index + 4,
+      2 => 4 * index + 4 * 4,
+      3 => 2 * index + 4 + 3 * 3 * 4,
+      4 => 4 * index + 4 * 4,
+      e => return Err(Error::UnknownWeightType(e)),
+    };
+    self.read.skip(weights + 4)?;
+
+    self.remaining -= 1;
+    Ok(())
+  }
+}
+
 pub struct VertexIterator<'a, R, C = DefaultConfig> {
   reader: &'a mut VertexReader<R>,
   phantom: PhantomData<C>,
@@ -0,0 +1,127 @@
+use crate::{Config, Error};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u8)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ShapeType {
+  Sphere = 0,
+  Box = 1,
+  Capsule = 2,
+}
+
+impl Display for ShapeType {
+  fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+    match self {
+      ShapeType::Sphere => write!(f, "sphere"),
+      ShapeType::Box => write!(f, "box"),
+      ShapeType::Capsule => write!(f, "capsule"),
+    }
+  }
+}
+
+impl TryFrom<u8> for ShapeType {
+  type Error = Error;
+
+  fn try_from(value: u8) -> Result<Self, Self::Error> {
+    Ok(match value {
+      0 => ShapeType::Sphere,
+      1 => ShapeType::Box,
+      2 => ShapeType::Capsule,
+      e => return Err(Error::InvalidRigidBodyShape(e)),
+    })
+  }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u8)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PhysicsMode {
+  FollowBone = 0,
+  Physics = 1,
+  PhysicsWithBone = 2,
+}
+
+impl Display for PhysicsMode {
+  fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+    match self {
+      PhysicsMode::FollowBone => write!(f, "follow bone"),
+      PhysicsMode::Physics => write!(f, "physics"),
+      PhysicsMode::PhysicsWithBone => write!(f, "physics with bone"),
+    }
+  }
+}
+
+impl TryFrom<u8> for PhysicsMode {
+  type Error = Error;
+
+  fn try_from(value: u8) -> Result<Self, Self::Error> {
+    Ok(match value {
+      0 => PhysicsMode::FollowBone,
+      1 => PhysicsMode::Physics,
+      2 => PhysicsMode::PhysicsWithBone,
+      e => return Err(Error::InvalidPhysicsMode(e)),
+    })
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(bound(
+    serialize = "C::BoneIndex: Serialize, C::Vec3: Serialize",
+    deserialize = "C::BoneIndex: Deserialize<'de>, C::Vec3: Deserialize<'de>"
+  ))
+)]
+pub struct RigidBody<C: Config> {
+  pub local_name: String,
+  pub universal_name: String,
+  pub bone_index: C::BoneIndex,
+  pub group: u8,
+  pub non_collision_mask: u16,
+  pub shape: ShapeType,
+  pub shape_size: C::Vec3,
+  pub shape_position: C::Vec3,
+  pub shape_rotation: C::Vec3,
+  pub mass: f32,
+  pub move_attenuation: f32,
+  pub rotation_damping: f32,
+  pub repulsion: f32,
+  pub friction: f32,
+  pub physics_mode: PhysicsMode,
+}
+
+impl<C: Config> Display for RigidBody<C>
+where
+  C::BoneIndex: Display,
+  C::Vec3: Display,
+{
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+    write!(
+      f,
+      r"local name: {}, universal name: {}, bone: {},
+group: {}, non collision mask: {:016b}, shape: {} size: {}, position: {}, rotation: {},
+mass: {}, move attenuation: {}, rotation damping: {}, repulsion: {}, friction: {},
+physics mode: {}",
+      self.local_name,
+      self.universal_name,
+      self.bone_index,
+      self.group,
+      self.non_collision_mask,
+      self.shape,
+      self.shape_size,
+      self.shape_position,
+      self.shape_rotation,
+      self.mass,
+      self.move_attenuation,
+      self.rotation_damping,
+      self.repulsion,
+      self.friction,
+      self.physics_mode
+    )
+  }
+}
@@ -0,0 +1,229 @@
+use crate::{Config, Error};
+use enumflags2::BitFlags;
+use itertools::Itertools;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::fmt::{Debug, Display, Formatter};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u8)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SoftBodyShape {
+  TriMesh = 0,
+  Rope = 1,
+}
+
+impl Display for SoftBodyShape {
+  fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+    match self {
+      SoftBodyShape::TriMesh => write!(f, "tri mesh"),
+      SoftBodyShape::Rope => write!(f, "rope"),
+    }
+  }
+}
+
+impl TryFrom<u8> for SoftBodyShape {
+  type Error = Error;
+
+  fn try_from(value: u8) -> Result<Self, Self::Error> {
+    Ok(match value {
+      0 => SoftBodyShape::TriMesh,
+      1 => SoftBodyShape::Rope,
+      e => return Err(Error::InvalidSoftBodyShape(e)),
+    })
+  }
+}
+
+#[derive(BitFlags, Copy, Clone, PartialEq, Debug)]
+#[repr(u8)]
+pub enum SoftBodyFlags {
+  BLink = 0b00000001,
+  ClusterCreation = 0b00000010,
+  LinkCrossing = 0b00000100,
+}
+
+struct SoftBodyFlagsFmt(BitFlags<SoftBodyFlags>);
+
+impl Display for SoftBodyFlagsFmt {
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+    write!(f, "{}", self.0.iter().map(|v| format!("{:?}", v)).join("|"))
+  }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(i32)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AeroModel {
+  VertexPoint = 0,
+  VertexTwoSided = 1,
+  VertexOneSided = 2,
+  FaceTwoSided = 3,
+  FaceOneSided = 4,
+}
+
+impl Display for AeroModel {
+  fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+    match self {
+      AeroModel::VertexPoint => write!(f, "vertex point"),
+      AeroModel::VertexTwoSided => write!(f, "vertex two sided"),
+      AeroModel::VertexOneSided => write!(f, "vertex one sided"),
+      AeroModel::FaceTwoSided => write!(f, "face two sided"),
+      AeroModel::FaceOneSided => write!(f, "face one sided"),
+    }
+  }
+}
+
+impl TryFrom<i32> for AeroModel {
+  type Error = Error;
+
+  fn try_from(value: i32) -> Result<Self, Self::Error> {
+    Ok(match value {
+      0 => AeroModel::VertexPoint,
+      1 => AeroModel::VertexTwoSided,
+      2 => AeroModel::VertexOneSided,
+      3 => AeroModel::FaceTwoSided,
+      4 => AeroModel::FaceOneSided,
+      e => return Err(Error::InvalidAeroModel(e)),
+    })
+  }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SoftBodyCoefficients {
+  pub velocity_correction: f32,
+  pub damping: f32,
+  pub drag: f32,
+  pub lift: f32,
+  pub pressure: f32,
+  pub volume_conservation: f32,
+  pub dynamic_friction: f32,
+  pub pose_matching: f32,
+  pub rigid_contact_hardness: f32,
+  pub kinetic_contact_hardness: f32,
+  pub soft_contact_hardness: f32,
+  pub anchor_hardness: f32,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SoftBodyCluster {
+  pub soft_rigid_hardness: f32,
+  pub soft_kinetic_hardness: f32,
+  pub soft_soft_hardness: f32,
+  pub soft_rigid_impulse_split: f32,
+  pub soft_kinetic_impulse_split: f32,
+  pub soft_soft_impulse_split: f32,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SoftBodyIterations {
+  pub velocity: i32,
+  pub position: i32,
+  pub drift: i32,
+  pub cluster: i32,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SoftBodyMaterial {
+  pub linear_stiffness: f32,
+  pub area_stiffness: f32,
+  pub volume_stiffness: f32,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(bound(
+    serialize = "C::VertexIndex: Serialize, C::RigidbodyIndex: Serialize",
+    deserialize = "C::VertexIndex: Deserialize<'de>, C::RigidbodyIndex: Deserialize<'de>"
+  ))
+)]
+pub struct SoftBodyAnchor<C: Config> {
+  pub rigid_body: C::RigidbodyIndex,
+  pub vertex: C::VertexIndex,
+  pub near_mode: bool,
+  // the stored byte behind `near_mode`, any non zero value counts as set
+  pub raw_near_mode: u8,
+}
+
+impl<C: Config> Display for SoftBodyAnchor<C>
+where
+  C::RigidbodyIndex: Display,
+  C::VertexIndex: Display,
+{
+  fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+    write!(f, "{} to vertex {}", self.rigid_body, self.vertex)?;
+    if self.near_mode {
+      write!(f, " near")?;
+    }
+    Ok(())
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(bound(
+    serialize = "C::VertexIndex: Serialize, C::MaterialIndex: Serialize, C::RigidbodyIndex: Serialize",
+    deserialize = "C::VertexIndex: Deserialize<'de>, C::MaterialIndex: Deserialize<'de>, C::RigidbodyIndex: Deserialize<'de>"
+  ))
+)]
+pub struct SoftBody<C: Config> {
+  pub local_name: String,
+  pub universal_name: String,
+  pub shape: SoftBodyShape,
+  pub material_index: C::MaterialIndex,
+  pub group: u8,
+  pub non_collision_mask: u16,
+  pub flags: BitFlags<SoftBodyFlags>,
+  pub unknown_flags: u8,
+  pub b_link_distance: i32,
+  pub cluster_count: i32,
+  pub total_mass: f32,
+  pub collision_margin: f32,
+  pub aero_model: AeroModel,
+  pub coefficients: SoftBodyCoefficients,
+  pub cluster: SoftBodyCluster,
+  pub iterations: SoftBodyIterations,
+  pub material: SoftBodyMaterial,
+  pub anchors: Vec<SoftBodyAnchor<C>>,
+  pub pin_vertices: Vec<C::VertexIndex>,
+}
+
+impl<C: Config> Display for SoftBody<C>
+where
+  C::MaterialIndex: Display,
+  C::RigidbodyIndex: Display,
+  C::VertexIndex: Display,
+{
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+    write!(
+      f,
+      r"local name: {}, universal name: {}, shape: {}, material: {},
+group: {}, non collision mask: {:016b}, flags: {},
+b-link distance: {}, clusters: {}, total mass: {}, collision margin: {}, aero model: {},
+anchors: {}
+pin vertices: {}",
+      self.local_name,
+      self.universal_name,
+      self.shape,
+      self.material_index,
+      self.group,
+      self.non_collision_mask,
+      SoftBodyFlagsFmt(self.flags),
+      self.b_link_distance,
+      self.cluster_count,
+      self.total_mass,
+      self.collision_margin,
+      self.aero_model,
+      self.anchors.iter().map(ToString::to_string).join(", "),
+      self.pin_vertices.iter().map(ToString::to_string).join(", ")
+    )
+  }
+}
@@ -0,0 +1,63 @@
+mod floats;
+
+use self::floats::{FloatDeserializer, FloatSerializer};
+use crate::{DefaultConfig, Error, Model, Result};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+
+// The text form is the pretty printed JSON of Model, non-finite floats are written as
+// "inf", "-inf", "NaN" or "NaN:<hex bits>" strings since JSON has no numbers for them.
+
+fn text_error<E: std::error::Error + Send + Sync + 'static>(e: E) -> Error {
+  Error::Text(Box::new(e))
+}
+
+pub fn to_writer<C, W: Write>(model: &Model<C>, write: W) -> Result<()>
+where
+  C: crate::Config,
+  Model<C>: Serialize,
+{
+  let mut serializer = serde_json::Serializer::pretty(write);
+  model
+    .serialize(FloatSerializer(&mut serializer))
+    .map_err(text_error)?;
+  serializer.into_inner().write_all(b"\n")?;
+  Ok(())
+}
+
+pub fn to_string<C>(model: &Model<C>) -> Result<String>
+where
+  C: crate::Config,
+  Model<C>: Serialize,
+{
+  let mut text = Vec::new();
+  to_writer(model, &mut text)?;
+  String::from_utf8(text).map_err(text_error)
+}
+
+pub fn from_reader<C, R: Read>(read: R) -> Result<Model<C>>
+where
+  C: crate::Config,
+  Model<C>: for<'de> Deserialize<'de>,
+{
+  let mut deserializer = serde_json::Deserializer::from_reader(read);
+  let model = Model::deserialize(FloatDeserializer(&mut deserializer)).map_err(text_error)?;
+  deserializer.end().map_err(text_error)?;
+  Ok(model)
+}
+
+pub fn from_str<C>(text: &str) -> Result<Model<C>>
+where
+  C: crate::Config,
+  Model<C>: for<'de> Deserialize<'de>,
+{
+  from_reader(text.as_bytes())
+}
+
+pub fn pmx_to_text<R: Read, W: Write>(read: R, write: W) -> Result<()> {
+  to_writer(&Model::<DefaultConfig>::read(read)?, write)
+}
+
+pub fn text_to_pmx<R: Read, W: Write>(read: R, write: W) -> Result<W> {
+  from_reader::<DefaultConfig, _>(read)?.write(write)
+}
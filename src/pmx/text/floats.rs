@@ -0,0 +1,565 @@
+// JSON has no representation for NaN and infinities, serde_json writes them as null.
+// These adapters wrap a Serializer/Deserializer and store non-finite f32 values as strings,
+// keeping NaN payloads, so every float bit pattern of a binary file survives the text form.
+use serde::de::{self, DeserializeSeed, Deserializer, Visitor};
+use serde::ser::{self, Serialize, Serializer};
+use std::fmt;
+
+pub(crate) fn encode(v: f32) -> Option<String> {
+  if v.is_finite() {
+    None
+  } else if v == f32::INFINITY {
+    Some("inf".to_string())
+  } else if v == f32::NEG_INFINITY {
+    Some("-inf".to_string())
+  } else if v.to_bits() == f32::NAN.to_bits() {
+    Some("NaN".to_string())
+  } else {
+    Some(format!("NaN:{:08x}", v.to_bits()))
+  }
+}
+
+pub(crate) fn decode(s: &str) -> Option<f32> {
+  match s {
+    "inf" => Some(f32::INFINITY),
+    "-inf" => Some(f32::NEG_INFINITY),
+    "NaN" => Some(f32::NAN),
+    _ => s
+      .strip_prefix("NaN:")
+      .and_then(|bits| u32::from_str_radix(bits, 16).ok())
+      .map(f32::from_bits)
+      .filter(|v| v.is_nan()),
+  }
+}
+
+pub(crate) struct FloatSerializer<S>(pub(crate) S);
+
+pub(crate) struct Wrap<'a, T: ?Sized>(pub(crate) &'a T);
+
+impl<T: Serialize + ?Sized> Serialize for Wrap<'_, T> {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    self.0.serialize(FloatSerializer(serializer))
+  }
+}
+
+pub(crate) struct Compound<C>(C);
+
+impl<S: Serializer> Serializer for FloatSerializer<S> {
+  type Ok = S::Ok;
+  type Error = S::Error;
+  type SerializeSeq = Compound<S::SerializeSeq>;
+  type SerializeTuple = Compound<S::SerializeTuple>;
+  type SerializeTupleStruct = Compound<S::SerializeTupleStruct>;
+  type SerializeTupleVariant = Compound<S::SerializeTupleVariant>;
+  type SerializeMap = Compound<S::SerializeMap>;
+  type SerializeStruct = Compound<S::SerializeStruct>;
+  type SerializeStructVariant = Compound<S::SerializeStructVariant>;
+
+  fn serialize_f32(self, v: f32) -> Result<S::Ok, S::Error> {
+    match encode(v) {
+      Some(s) => self.0.serialize_str(&s),
+      None => self.0.serialize_f32(v),
+    }
+  }
+
+  fn serialize_bool(self, v: bool) -> Result<S::Ok, S::Error> {
+    self.0.serialize_bool(v)
+  }
+  fn serialize_i8(self, v: i8) -> Result<S::Ok, S::Error> {
+    self.0.serialize_i8(v)
+  }
+  fn serialize_i16(self, v: i16) -> Result<S::Ok, S::Error> {
+    self.0.serialize_i16(v)
+  }
+  fn serialize_i32(self, v: i32) -> Result<S::Ok, S::Error> {
+    self.0.serialize_i32(v)
+  }
+  fn serialize_i64(self, v: i64) -> Result<S::Ok, S::Error> {
+    self.0.serialize_i64(v)
+  }
+  fn serialize_u8(self, v: u8) -> Result<S::Ok, S::Error> {
+    self.0.serialize_u8(v)
+  }
+  fn serialize_u16(self, v: u16) -> Result<S::Ok, S::Error> {
+    self.0.serialize_u16(v)
+  }
+  fn serialize_u32(self, v: u32) -> Result<S::Ok, S::Error> {
+    self.0.serialize_u32(v)
+  }
+  fn serialize_u64(self, v: u64) -> Result<S::Ok, S::Error> {
+    self.0.serialize_u64(v)
+  }
+  fn serialize_f64(self, v: f64) -> Result<S::Ok, S::Error> {
+    self.0.serialize_f64(v)
+  }
+  fn serialize_char(self, v: char) -> Result<S::Ok, S::Error> {
+    self.0.serialize_char(v)
+  }
+  fn serialize_str(self, v: &str) -> Result<S::Ok, S::Error> {
+    self.0.serialize_str(v)
+  }
+  fn serialize_bytes(self, v: &[u8]) -> Result<S::Ok, S::Error> {
+    self.0.serialize_bytes(v)
+  }
+  fn serialize_none(self) -> Result<S::Ok, S::Error> {
+    self.0.serialize_none()
+  }
+  fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<S::Ok, S::Error> {
+    self.0.serialize_some(&Wrap(value))
+  }
+  fn serialize_unit(self) -> Result<S::Ok, S::Error> {
+    self.0.serialize_unit()
+  }
+  fn serialize_unit_struct(self, name: &'static str) -> Result<S::Ok, S::Error> {
+    self.0.serialize_unit_struct(name)
+  }
+  fn serialize_unit_variant(
+    self,
+    name: &'static str,
+    index: u32,
+    variant: &'static str,
+  ) -> Result<S::Ok, S::Error> {
+    self.0.serialize_unit_variant(name, index, variant)
+  }
+  fn serialize_newtype_struct<T: Serialize + ?Sized>(
+    self,
+    name: &'static str,
+    value: &T,
+  ) -> Result<S::Ok, S::Error> {
+    self.0.serialize_newtype_struct(name, &Wrap(value))
+  }
+  fn serialize_newtype_variant<T: Serialize + ?Sized>(
+    self,
+    name: &'static str,
+    index: u32,
+    variant: &'static str,
+    value: &T,
+  ) -> Result<S::Ok, S::Error> {
+    self
+      .0
+      .serialize_newtype_variant(name, index, variant, &Wrap(value))
+  }
+  fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, S::Error> {
+    self.0.serialize_seq(len).map(Compound)
+  }
+  fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, S::Error> {
+    self.0.serialize_tuple(len).map(Compound)
+  }
+  fn serialize_tuple_struct(
+    self,
+    name: &'static str,
+    len: usize,
+  ) -> Result<Self::SerializeTupleStruct, S::Error> {
+    self.0.serialize_tuple_struct(name, len).map(Compound)
+  }
+  fn serialize_tuple_variant(
+    self,
+    name: &'static str,
+    index: u32,
+    variant: &'static str,
+    len: usize,
+  ) -> Result<Self::SerializeTupleVariant, S::Error> {
+    self
+      .0
+      .serialize_tuple_variant(name, index, variant, len)
+      .map(Compound)
+  }
+  fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, S::Error> {
+    self.0.serialize_map(len).map(Compound)
+  }
+  fn serialize_struct(
+    self,
+    name: &'static str,
+    len: usize,
+  ) -> Result<Self::SerializeStruct, S::Error> {
+    self.0.serialize_struct(name, len).map(Compound)
+  }
+  fn serialize_struct_variant(
+    self,
+    name: &'static str,
+    index: u32,
+    variant: &'static str,
+    len: usize,
+  ) -> Result<Self::SerializeStructVariant, S::Error> {
+    self
+      .0
+      .serialize_struct_variant(name, index, variant, len)
+      .map(Compound)
+  }
+  fn is_human_readable(&self) -> bool {
+    self.0.is_human_readable()
+  }
+}
+
+impl<C: ser::SerializeSeq> ser::SerializeSeq for Compound<C> {
+  type Ok = C::Ok;
+  type Error = C::Error;
+  fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), C::Error> {
+    self.0.serialize_element(&Wrap(value))
+  }
+  fn end(self) -> Result<C::Ok, C::Error> {
+    self.0.end()
+  }
+}
+
+impl<C: ser::SerializeTuple> ser::SerializeTuple for Compound<C> {
+  type Ok = C::Ok;
+  type Error = C::Error;
+  fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), C::Error> {
+    self.0.serialize_element(&Wrap(value))
+  }
+  fn end(self) -> Result<C::Ok, C::Error> {
+    self.0.end()
+  }
+}
+
+impl<C: ser::SerializeTupleStruct> ser::SerializeTupleStruct for Compound<C> {
+  type Ok = C::Ok;
+  type Error = C::Error;
+  fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), C::Error> {
+    self.0.serialize_field(&Wrap(value))
+  }
+  fn end(self) -> Result<C::Ok, C::Error> {
+    self.0.end()
+  }
+}
+
+impl<C: ser::SerializeTupleVariant> ser::SerializeTupleVariant for Compound<C> {
+  type Ok = C::Ok;
+  type Error = C::Error;
+  fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), C::Error> {
+    self.0.serialize_field(&Wrap(value))
+  }
+  fn end(self) -> Result<C::Ok, C::Error> {
+    self.0.end()
+  }
+}
+
+impl<C: ser::SerializeMap> ser::SerializeMap for Compound<C> {
+  type Ok = C::Ok;
+  type Error = C::Error;
+  fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), C::Error> {
+    self.0.serialize_key(&Wrap(key))
+  }
+  fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), C::Error> {
+    self.0.serialize_value(&Wrap(value))
+  }
+  fn end(self) -> Result<C::Ok, C::Error> {
+    self.0.end()
+  }
+}
+
+impl<C: ser::SerializeStruct> ser::SerializeStruct for Compound<C> {
+  type Ok = C::Ok;
+  type Error = C::Error;
+  fn serialize_field<T: Serialize + ?Sized>(
+    &mut self,
+    key: &'static str,
+    value: &T,
+  ) -> Result<(), C::Error> {
+    self.0.serialize_field(key, &Wrap(value))
+  }
+  fn end(self) -> Result<C::Ok, C::Error> {
+    self.0.end()
+  }
+}
+
+impl<C: ser::SerializeStructVariant> ser::SerializeStructVariant for Compound<C> {
+  type Ok = C::Ok;
+  type Error = C::Error;
+  fn serialize_field<T: Serialize + ?Sized>(
+    &mut self,
+    key: &'static str,
+    value: &T,
+  ) -> Result<(), C::Error> {
+    self.0.serialize_field(key, &Wrap(value))
+  }
+  fn end(self) -> Result<C::Ok, C::Error> {
+    self.0.end()
+  }
+}
+
+pub(crate) struct FloatDeserializer<D>(pub(crate) D);
+
+struct FloatVisitor<V>(V);
+
+struct F32Visitor<V>(V);
+
+struct Seed<S>(S);
+
+impl<'de, S: DeserializeSeed<'de>> DeserializeSeed<'de> for Seed<S> {
+  type Value = S::Value;
+  fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<S::Value, D::Error> {
+    self.0.deserialize(FloatDeserializer(deserializer))
+  }
+}
+
+macro_rules! forward {
+  ($($method:ident)*) => {
+    $(
+      fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, D::Error> {
+        self.0.$method(FloatVisitor(visitor))
+      }
+    )*
+  };
+}
+
+impl<'de, D: Deserializer<'de>> Deserializer<'de> for FloatDeserializer<D> {
+  type Error = D::Error;
+
+  fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, D::Error> {
+    // numbers and strings both have to be accepted here
+    self.0.deserialize_any(F32Visitor(visitor))
+  }
+
+  forward! {
+    deserialize_any deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32
+    deserialize_i64 deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64
+    deserialize_f64 deserialize_char deserialize_str deserialize_string deserialize_bytes
+    deserialize_byte_buf deserialize_option deserialize_unit deserialize_seq deserialize_map
+    deserialize_identifier deserialize_ignored_any
+  }
+
+  fn deserialize_unit_struct<V: Visitor<'de>>(
+    self,
+    name: &'static str,
+    visitor: V,
+  ) -> Result<V::Value, D::Error> {
+    self.0.deserialize_unit_struct(name, FloatVisitor(visitor))
+  }
+
+  fn deserialize_newtype_struct<V: Visitor<'de>>(
+    self,
+    name: &'static str,
+    visitor: V,
+  ) -> Result<V::Value, D::Error> {
+    self
+      .0
+      .deserialize_newtype_struct(name, FloatVisitor(visitor))
+  }
+
+  fn deserialize_tuple<V: Visitor<'de>>(
+    self,
+    len: usize,
+    visitor: V,
+  ) -> Result<V::Value, D::Error> {
+    self.0.deserialize_tuple(len, FloatVisitor(visitor))
+  }
+
+  fn deserialize_tuple_struct<V: Visitor<'de>>(
+    self,
+    name: &'static str,
+    len: usize,
+    visitor: V,
+  ) -> Result<V::Value, D::Error> {
+    self
+      .0
+      .deserialize_tuple_struct(name, len, FloatVisitor(visitor))
+  }
+
+  fn deserialize_struct<V: Visitor<'de>>(
+    self,
+    name: &'static str,
+    fields: &'static [&'static str],
+    visitor: V,
+  ) -> Result<V::Value, D::Error> {
+    self
+      .0
+      .deserialize_struct(name, fields, FloatVisitor(visitor))
+  }
+
+  fn deserialize_enum<V: Visitor<'de>>(
+    self,
+    name: &'static str,
+    variants: &'static [&'static str],
+    visitor: V,
+  ) -> Result<V::Value, D::Error> {
+    self
+      .0
+      .deserialize_enum(name, variants, FloatVisitor(visitor))
+  }
+
+  fn is_human_readable(&self) -> bool {
+    self.0.is_human_readable()
+  }
+}
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for FloatVisitor<V> {
+  type Value = V::Value;
+
+  fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    self.0.expecting(f)
+  }
+
+  fn visit_str<E: de::Error>(self, v: &str) -> Result<V::Value, E> {
+    self.0.visit_str(v)
+  }
+  fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<V::Value, E> {
+    self.0.visit_borrowed_str(v)
+  }
+  fn visit_string<E: de::Error>(self, v: String) -> Result<V::Value, E> {
+    self.0.visit_string(v)
+  }
+  fn visit_bool<E: de::Error>(self, v: bool) -> Result<V::Value, E> {
+    self.0.visit_bool(v)
+  }
+  fn visit_i8<E: de::Error>(self, v: i8) -> Result<V::Value, E> {
+    self.0.visit_i8(v)
+  }
+  fn visit_i16<E: de::Error>(self, v: i16) -> Result<V::Value, E> {
+    self.0.visit_i16(v)
+  }
+  fn visit_i32<E: de::Error>(self, v: i32) -> Result<V::Value, E> {
+    self.0.visit_i32(v)
+  }
+  fn visit_i64<E: de::Error>(self, v: i64) -> Result<V::Value, E> {
+    self.0.visit_i64(v)
+  }
+  fn visit_u8<E: de::Error>(self, v: u8) -> Result<V::Value, E> {
+    self.0.visit_u8(v)
+  }
+  fn visit_u16<E: de::Error>(self, v: u16) -> Result<V::Value, E> {
+    self.0.visit_u16(v)
+  }
+  fn visit_u32<E: de::Error>(self, v: u32) -> Result<V::Value, E> {
+    self.0.visit_u32(v)
+  }
+  fn visit_u64<E: de::Error>(self, v: u64) -> Result<V::Value, E> {
+    self.0.visit_u64(v)
+  }
+  fn visit_f32<E: de::Error>(self, v: f32) -> Result<V::Value, E> {
+    self.0.visit_f32(v)
+  }
+  fn visit_f64<E: de::Error>(self, v: f64) -> Result<V::Value, E> {
+    self.0.visit_f64(v)
+  }
+  fn visit_char<E: de::Error>(self, v: char) -> Result<V::Value, E> {
+    self.0.visit_char(v)
+  }
+  fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<V::Value, E> {
+    self.0.visit_bytes(v)
+  }
+  fn visit_borrowed_bytes<E: de::Error>(self, v: &'de [u8]) -> Result<V::Value, E> {
+    self.0.visit_borrowed_bytes(v)
+  }
+  fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<V::Value, E> {
+    self.0.visit_byte_buf(v)
+  }
+  fn visit_none<E: de::Error>(self) -> Result<V::Value, E> {
+    self.0.visit_none()
+  }
+  fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<V::Value, D::Error> {
+    self.0.visit_some(FloatDeserializer(deserializer))
+  }
+  fn visit_unit<E: de::Error>(self) -> Result<V::Value, E> {
+    self.0.visit_unit()
+  }
+  fn visit_newtype_struct<D: Deserializer<'de>>(
+    self,
+    deserializer: D,
+  ) -> Result<V::Value, D::Error> {
+    self.0.visit_newtype_struct(FloatDeserializer(deserializer))
+  }
+  fn visit_seq<A: de::SeqAccess<'de>>(self, seq: A) -> Result<V::Value, A::Error> {
+    self.0.visit_seq(Access(seq))
+  }
+  fn visit_map<A: de::MapAccess<'de>>(self, map: A) -> Result<V::Value, A::Error> {
+    self.0.visit_map(Access(map))
+  }
+  fn visit_enum<A: de::EnumAccess<'de>>(self, data: A) -> Result<V::Value, A::Error> {
+    self.0.visit_enum(Access(data))
+  }
+}
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for F32Visitor<V> {
+  type Value = V::Value;
+
+  fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    self.0.expecting(f)
+  }
+
+  fn visit_str<E: de::Error>(self, v: &str) -> Result<V::Value, E> {
+    match decode(v) {
+      Some(float) => self.0.visit_f32(float),
+      None => self.0.visit_str(v),
+    }
+  }
+
+  fn visit_i64<E: de::Error>(self, v: i64) -> Result<V::Value, E> {
+    self.0.visit_i64(v)
+  }
+  fn visit_u64<E: de::Error>(self, v: u64) -> Result<V::Value, E> {
+    self.0.visit_u64(v)
+  }
+  fn visit_f32<E: de::Error>(self, v: f32) -> Result<V::Value, E> {
+    self.0.visit_f32(v)
+  }
+  fn visit_f64<E: de::Error>(self, v: f64) -> Result<V::Value, E> {
+    self.0.visit_f64(v)
+  }
+}
+
+struct Access<A>(A);
+
+impl<'de, A: de::SeqAccess<'de>> de::SeqAccess<'de> for Access<A> {
+  type Error = A::Error;
+  fn next_element_seed<T: DeserializeSeed<'de>>(
+    &mut self,
+    seed: T,
+  ) -> Result<Option<T::Value>, A::Error> {
+    self.0.next_element_seed(Seed(seed))
+  }
+  fn size_hint(&self) -> Option<usize> {
+    self.0.size_hint()
+  }
+}
+
+impl<'de, A: de::MapAccess<'de>> de::MapAccess<'de> for Access<A> {
+  type Error = A::Error;
+  fn next_key_seed<K: DeserializeSeed<'de>>(
+    &mut self,
+    seed: K,
+  ) -> Result<Option<K::Value>, A::Error> {
+    self.0.next_key_seed(Seed(seed))
+  }
+  fn next_value_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<T::Value, A::Error> {
+    self.0.next_value_seed(Seed(seed))
+  }
+  fn size_hint(&self) -> Option<usize> {
+    self.0.size_hint()
+  }
+}
+
+impl<'de, A: de::EnumAccess<'de>> de::EnumAccess<'de> for Access<A> {
+  type Error = A::Error;
+  type Variant = Access<A::Variant>;
+  fn variant_seed<T: DeserializeSeed<'de>>(
+    self,
+    seed: T,
+  ) -> Result<(T::Value, Self::Variant), A::Error> {
+    self
+      .0
+      .variant_seed(Seed(seed))
+      .map(|(value, variant)| (value, Access(variant)))
+  }
+}
+
+impl<'de, A: de::VariantAccess<'de>> de::VariantAccess<'de> for Access<A> {
+  type Error = A::Error;
+  fn unit_variant(self) -> Result<(), A::Error> {
+    self.0.unit_variant()
+  }
+  fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, A::Error> {
+    self.0.newtype_variant_seed(Seed(seed))
+  }
+  fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, A::Error> {
+    self.0.tuple_variant(len, FloatVisitor(visitor))
+  }
+  fn struct_variant<V: Visitor<'de>>(
+    self,
+    fields: &'static [&'static str],
+    visitor: V,
+  ) -> Result<V::Value, A::Error> {
+    self.0.struct_variant(fields, FloatVisitor(visitor))
+  }
+}
@@ -1,11 +1,18 @@
 use arrayvec::ArrayVec;
 
 use crate::Error;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
-use std::{convert::TryFrom, fmt::Debug, iter::FromIterator};
+use std::{
+  convert::{TryFrom, TryInto},
+  fmt::Debug,
+  iter::FromIterator,
+};
 
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
 #[repr(u8)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TextEncoding {
   UTF16LE = 0,
   UTF8 = 1,
@@ -20,6 +27,15 @@ impl Display for TextEncoding {
   }
 }
 
+impl TextEncoding {
+  pub fn encoded_len(&self, text: &str) -> usize {
+    match self {
+      TextEncoding::UTF16LE => text.encode_utf16().count() * 2,
+      TextEncoding::UTF8 => text.len(),
+    }
+  }
+}
+
 impl TryFrom<u8> for TextEncoding {
   type Error = Error;
 
@@ -34,6 +50,7 @@ impl TryFrom<u8> for TextEncoding {
 
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
 #[repr(u8)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum IndexSize {
   I8 = 1,
   I16 = 2,
@@ -50,6 +67,29 @@ impl Display for IndexSize {
   }
 }
 
+impl IndexSize {
+  // Signed indices keep -1 for "none", so the largest element is addressed by the type's max
+  pub fn for_count(count: usize) -> IndexSize {
+    if count <= i8::MAX as usize + 1 {
+      IndexSize::I8
+    } else if count <= i16::MAX as usize + 1 {
+      IndexSize::I16
+    } else {
+      IndexSize::I32
+    }
+  }
+
+  pub fn for_vertex_count(count: usize) -> IndexSize {
+    if count <= u8::MAX as usize + 1 {
+      IndexSize::I8
+    } else if count <= u16::MAX as usize + 1 {
+      IndexSize::I16
+    } else {
+      IndexSize::I32
+    }
+  }
+}
+
 impl TryFrom<u8> for IndexSize {
   type Error = Error;
 
@@ -63,11 +103,55 @@ impl TryFrom<u8> for IndexSize {
   }
 }
 
-pub trait Index: TryFrom<i8> + TryFrom<i16> + TryFrom<i32> + Clone + Debug + Eq {}
-impl<I: TryFrom<i8> + TryFrom<i16> + TryFrom<i32> + Clone + Debug + Eq> Index for I {}
+pub trait Index:
+  TryFrom<i8>
+  + TryFrom<i16>
+  + TryFrom<i32>
+  + TryInto<i8>
+  + TryInto<i16>
+  + TryInto<i32>
+  + Clone
+  + Debug
+  + Eq
+{
+}
+impl<I> Index for I where
+  I: TryFrom<i8>
+    + TryFrom<i16>
+    + TryFrom<i32>
+    + TryInto<i8>
+    + TryInto<i16>
+    + TryInto<i32>
+    + Clone
+    + Debug
+    + Eq
+{
+}
 
-pub trait VertexIndex: TryFrom<u8> + TryFrom<u16> + TryFrom<i32> + Clone + Debug + Eq {}
-impl<I: TryFrom<u8> + TryFrom<u16> + TryFrom<i32> + Clone + Debug + Eq> VertexIndex for I {}
+pub trait VertexIndex:
+  TryFrom<u8>
+  + TryFrom<u16>
+  + TryFrom<i32>
+  + TryInto<u8>
+  + TryInto<u16>
+  + TryInto<i32>
+  + Clone
+  + Debug
+  + Eq
+{
+}
+impl<I> VertexIndex for I where
+  I: TryFrom<u8>
+    + TryFrom<u16>
+    + TryFrom<i32>
+    + TryInto<u8>
+    + TryInto<u16>
+    + TryInto<i32>
+    + Clone
+    + Debug
+    + Eq
+{
+}
 
 pub trait Config {
   type VertexIndex: VertexIndex;
@@ -77,12 +161,13 @@ pub trait Config {
   type MorphIndex: Index;
   type RigidbodyIndex: Index;
 
-  type Vec2: From<[f32; 2]> + Clone + Debug + PartialEq;
-  type Vec3: From<[f32; 3]> + Clone + Debug + PartialEq;
-  type Vec4: From<[f32; 4]> + Clone + Debug + PartialEq;
-  type AdditionalVec4s: FromIterator<Self::Vec4> + Clone + Debug + PartialEq;
+  type Vec2: From<[f32; 2]> + AsRef<[f32]> + Clone + Debug + PartialEq;
+  type Vec3: From<[f32; 3]> + AsRef<[f32]> + Clone + Debug + PartialEq;
+  type Vec4: From<[f32; 4]> + AsRef<[f32]> + Clone + Debug + PartialEq;
+  type AdditionalVec4s: FromIterator<Self::Vec4> + AsRef<[Self::Vec4]> + Clone + Debug + PartialEq;
 }
 
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
 pub struct DefaultConfig;
 
 impl Config for DefaultConfig {
@@ -1,5 +1,16 @@
 use crate::{Config, WeightDeform};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(bound(
+    serialize = "C::BoneIndex: Serialize, C::Vec2: Serialize, C::Vec3: Serialize, C::AdditionalVec4s: Serialize",
+    deserialize = "C::BoneIndex: Deserialize<'de>, C::Vec2: Deserialize<'de>, C::Vec3: Deserialize<'de>, C::AdditionalVec4s: Deserialize<'de>"
+  ))
+)]
 pub struct Vertex<C: Config> {
   pub position: C::Vec3,
   pub normal: C::Vec3,
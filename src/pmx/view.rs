@@ -0,0 +1,200 @@
+mod element;
+mod text;
+mod vertex;
+
+pub use self::element::{
+  BoneView, Bones, DisplayFrameView, DisplayFrames, JointView, Joints, MaterialView, Materials,
+  MorphView, Morphs, RigidBodies, RigidBodyView,
+};
+pub use self::text::Text;
+pub use self::vertex::{Surfaces, VertexView, Vertices};
+
+use crate::{
+  pmx::error::Section,
+  reader::{
+    header::settings, helpers::bounded_capacity, limited::LimitedRead, JointReader, SoftBodyReader,
+  },
+  Error, ParseLimits, Result, Settings,
+};
+use std::convert::TryInto;
+use std::io::ErrorKind;
+
+// Parses a model in place from a byte slice such as a memory map. Names and paths borrow the
+// bytes and elements are read in full on request. Soft bodies are handed to the regular reader
+// running over the same slice.
+pub struct ModelView<'a> {
+  pub version: f32,
+  pub settings: Settings,
+  pub extra_globals: &'a [u8],
+  pub model_local_name: Text<'a>,
+  pub model_universal_name: Text<'a>,
+  pub local_comments: Text<'a>,
+  pub universal_comments: Text<'a>,
+  pub vertices: Vertices<'a>,
+  pub surfaces: Surfaces<'a>,
+  pub textures: Vec<Text<'a>>,
+  pub materials: Materials<'a>,
+  pub bones: Bones<'a>,
+  pub morphs: Morphs<'a>,
+  pub display_frames: DisplayFrames<'a>,
+  pub rigid_bodies: RigidBodies<'a>,
+  pub joints: Joints<'a>,
+  bytes: &'a [u8],
+  soft_bodies: usize,
+  limits: ParseLimits,
+}
+
+impl<'a> ModelView<'a> {
+  pub fn new(bytes: &'a [u8]) -> Result<ModelView<'a>> {
+    Self::with_limits(bytes, ParseLimits::default())
+  }
+
+  pub fn with_limits(bytes: &'a [u8], limits: ParseLimits) -> Result<ModelView<'a>> {
+    let mut cursor = Cursor {
+      bytes,
+      position: 0,
+      limits,
+    };
+
+    let (version, settings, extra_globals, names) =
+      Self::header(&mut cursor).map_err(|e| e.at(Section::Header, None, 0))?;
+    let vertices = Vertices::scan(&mut cursor, version, settings)?;
+    let surfaces = Surfaces::scan(&mut cursor, settings)?;
+
+    let offset = cursor.position as u64;
+    let count = cursor
+      .count()
+      .map_err(|e| e.at(Section::Textures, None, offset))?;
+    let mut textures = Vec::with_capacity(bounded_capacity(count));
+    for i in 0..count {
+      let offset = cursor.position as u64;
+      let texture = cursor
+        .text(settings)
+        .map_err(|e| e.at(Section::Textures, Some(i), offset))?;
+      textures.push(texture);
+    }
+    let materials = Materials::scan(&mut cursor, version, settings)?;
+    let bones = Bones::scan(&mut cursor, version, settings)?;
+    let morphs = Morphs::scan(&mut cursor, version, settings)?;
+    let display_frames = DisplayFrames::scan(&mut cursor, version, settings)?;
+    let rigid_bodies = RigidBodies::scan(&mut cursor, version, settings)?;
+    let joints = Joints::scan(&mut cursor, version, settings)?;
+
+    let [model_local_name, model_universal_name, local_comments, universal_comments] = names;
+    Ok(ModelView {
+      version,
+      settings,
+      extra_globals,
+      model_local_name,
+      model_universal_name,
+      local_comments,
+      universal_comments,
+      vertices,
+      surfaces,
+      textures,
+      materials,
+      bones,
+      morphs,
+      display_frames,
+      rigid_bodies,
+      joints,
+      bytes,
+      soft_bodies: cursor.position,
+      limits,
+    })
+  }
+
+  #[allow(clippy::type_complexity)]
+  fn header(cursor: &mut Cursor<'a>) -> Result<(f32, Settings, &'a [u8], [Text<'a>; 4])> {
+    let magic = cursor.take(4)?;
+    if magic != b"PMX " {
+      return Err(Error::WrongSignature(magic.try_into().unwrap()));
+    }
+    let version = cursor.f32()?;
+    let globals_count = cursor.take(1)?[0];
+    if globals_count < 8 {
+      return Err(Error::GlobalsCountLessThan8(globals_count));
+    }
+    let globals = cursor.take(globals_count as usize)?;
+    let settings = settings(globals)?;
+    let names = [
+      cursor.text(settings)?,
+      cursor.text(settings)?,
+      cursor.text(settings)?,
+      cursor.text(settings)?,
+    ];
+    Ok((version, settings, &globals[8..], names))
+  }
+
+  pub fn soft_bodies(&self) -> Result<SoftBodyReader<&'a [u8]>> {
+    let joints = JointReader {
+      version: self.version,
+      settings: self.settings,
+      count: self.joints.len() as i32,
+      remaining: 0,
+      read: LimitedRead::at(
+        &self.bytes[self.soft_bodies..],
+        self.limits,
+        self.soft_bodies as u64,
+      ),
+      poison: false,
+    };
+    SoftBodyReader::new(joints)
+  }
+}
+
+pub(crate) struct Cursor<'a> {
+  pub(crate) bytes: &'a [u8],
+  pub(crate) position: usize,
+  pub(crate) limits: ParseLimits,
+}
+
+impl<'a> Cursor<'a> {
+  pub(crate) fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+    let end = self.position.saturating_add(len);
+    if end as u64 > self.limits.max_total_bytes {
+      return Err(Error::LimitExceeded {
+        what: "total bytes",
+        requested: end as u64,
+        limit: self.limits.max_total_bytes,
+      });
+    }
+    let bytes = self
+      .bytes
+      .get(self.position..end)
+      .ok_or_else(|| Error::Io(ErrorKind::UnexpectedEof.into()))?;
+    self.position = end;
+    Ok(bytes)
+  }
+
+  pub(crate) fn i32(&mut self) -> Result<i32> {
+    Ok(i32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+  }
+
+  fn f32(&mut self) -> Result<f32> {
+    Ok(f32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+  }
+
+  pub(crate) fn count(&mut self) -> Result<usize> {
+    let count = self.i32()?;
+    if count < 0 {
+      return Err(Error::NegativeCount(count));
+    }
+    Ok(count as usize)
+  }
+
+  pub(crate) fn text(&mut self, settings: Settings) -> Result<Text<'a>> {
+    let size = self.i32()?;
+    if size < 0 {
+      return Err(Error::NegativeTextSize(size));
+    }
+    if size as usize > self.limits.max_text_bytes {
+      return Err(Error::LimitExceeded {
+        what: "text bytes",
+        requested: size as u64,
+        limit: self.limits.max_text_bytes as u64,
+      });
+    }
+    Ok(Text::new(self.take(size as usize)?, settings.text_encoding))
+  }
+}
@@ -0,0 +1,169 @@
+use crate::{
+  pmx::error::Section,
+  pmx::morph::Morph,
+  reader::{
+    helpers::bounded_capacity, limited::LimitedRead, BoneReader, DisplayFrameReader, JointReader,
+    MaterialReader, MorphReader, RigidBodyReader,
+  },
+  view::{Cursor, Text},
+  Bone, Config, DisplayFrame, Error, Joint, Material, ParseLimits, Result, RigidBody, Settings,
+};
+use std::io::{self, ErrorKind};
+
+// Every element after the textures starts with its two names. Those are picked out in place while
+// the section's reader skips over the rest, which is only parsed when the element is read.
+macro_rules! element_views {
+  ($list:ident, $view:ident, $reader:ident, $section:expr, $item:ident) => {
+    pub struct $list<'a> {
+      version: f32,
+      settings: Settings,
+      limits: ParseLimits,
+      bytes: &'a [u8],
+      // start and names of every element
+      elements: Vec<(usize, [Text<'a>; 2])>,
+      end: usize,
+    }
+
+    impl<'a> $list<'a> {
+      pub(crate) fn scan(
+        cursor: &mut Cursor<'a>,
+        version: f32,
+        settings: Settings,
+      ) -> Result<Self> {
+        let offset = cursor.position as u64;
+        let count = cursor.count().map_err(|e| e.at($section, None, offset))?;
+
+        let mut reader = $reader {
+          version,
+          settings,
+          count: count as i32,
+          remaining: count as i32,
+          read: LimitedRead::at(
+            io::Cursor::new(&cursor.bytes[cursor.position..]),
+            cursor.limits,
+            cursor.position as u64,
+          ),
+          poison: false,
+        };
+        let mut elements = Vec::with_capacity(bounded_capacity(count));
+        for element in 0..count {
+          let start = cursor.position;
+          let names = cursor
+            .text(settings)
+            .and_then(|local| Ok([local, cursor.text(settings)?]))
+            .map_err(|e| e.at($section, Some(element), start as u64))?;
+          reader.skip_next()?;
+          // seeking over a long gap does not notice the end of the bytes
+          cursor.position = reader.read.position as usize;
+          if cursor.position > cursor.bytes.len() {
+            return Err(Error::Io(ErrorKind::UnexpectedEof.into()).at(
+              $section,
+              Some(element),
+              start as u64,
+            ));
+          }
+          elements.push((start, names));
+        }
+
+        Ok($list {
+          version,
+          settings,
+          limits: cursor.limits,
+          bytes: cursor.bytes,
+          elements,
+          end: cursor.position,
+        })
+      }
+
+      pub fn len(&self) -> usize {
+        self.elements.len()
+      }
+
+      pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+      }
+
+      pub fn get(&self, index: usize) -> Option<$view<'a>> {
+        let (start, names) = *self.elements.get(index)?;
+        let end = self.elements.get(index + 1).map_or(self.end, |e| e.0);
+        Some($view {
+          version: self.version,
+          settings: self.settings,
+          limits: self.limits,
+          bytes: &self.bytes[start..end],
+          offset: start,
+          index,
+          count: self.len(),
+          names,
+        })
+      }
+
+      pub fn iter(&self) -> impl Iterator<Item = $view<'a>> + '_ {
+        (0..self.len()).filter_map(move |i| self.get(i))
+      }
+    }
+
+    #[derive(Clone, Copy)]
+    pub struct $view<'a> {
+      version: f32,
+      settings: Settings,
+      limits: ParseLimits,
+      bytes: &'a [u8],
+      offset: usize,
+      index: usize,
+      count: usize,
+      names: [Text<'a>; 2],
+    }
+
+    impl<'a> $view<'a> {
+      pub fn local_name(&self) -> Text<'a> {
+        self.names[0]
+      }
+
+      pub fn universal_name(&self) -> Text<'a> {
+        self.names[1]
+      }
+
+      pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+      }
+
+      pub fn read<C: Config>(&self) -> Result<$item<C>> {
+        let mut reader = $reader {
+          version: self.version,
+          settings: self.settings,
+          count: self.count as i32,
+          remaining: (self.count - self.index) as i32,
+          read: LimitedRead::at(self.bytes, self.limits, self.offset as u64),
+          poison: false,
+        };
+        Ok(reader.next::<C>()?.unwrap())
+      }
+    }
+  };
+}
+
+element_views!(
+  Materials,
+  MaterialView,
+  MaterialReader,
+  Section::Materials,
+  Material
+);
+element_views!(Bones, BoneView, BoneReader, Section::Bones, Bone);
+element_views!(Morphs, MorphView, MorphReader, Section::Morphs, Morph);
+element_views!(
+  DisplayFrames,
+  DisplayFrameView,
+  DisplayFrameReader,
+  Section::DisplayFrames,
+  DisplayFrame
+);
+element_views!(
+  RigidBodies,
+  RigidBodyView,
+  RigidBodyReader,
+  Section::RigidBodies,
+  RigidBody
+);
+element_views!(Joints, JointView, JointReader, Section::Joints, Joint);
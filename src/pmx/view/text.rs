@@ -0,0 +1,89 @@
+use crate::{Error, Result, TextEncoding};
+use std::borrow::Cow;
+use std::char::{decode_utf16, REPLACEMENT_CHARACTER};
+use std::fmt::{Display, Formatter};
+
+// Text as stored in the file, UTF-8 borrows and UTF-16 is only decoded on request
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Text<'a> {
+  bytes: &'a [u8],
+  encoding: TextEncoding,
+}
+
+impl<'a> Text<'a> {
+  pub fn new(bytes: &'a [u8], encoding: TextEncoding) -> Text<'a> {
+    Text { bytes, encoding }
+  }
+
+  pub fn as_bytes(&self) -> &'a [u8] {
+    self.bytes
+  }
+
+  pub fn encoding(&self) -> TextEncoding {
+    self.encoding
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.bytes.is_empty()
+  }
+
+  pub fn as_str(&self) -> Option<&'a str> {
+    match self.encoding {
+      TextEncoding::UTF8 => std::str::from_utf8(self.bytes).ok(),
+      TextEncoding::UTF16LE => None,
+    }
+  }
+
+  pub fn decode(&self) -> Result<Cow<'a, str>> {
+    match self.encoding {
+      TextEncoding::UTF8 => std::str::from_utf8(self.bytes)
+        .map(Cow::Borrowed)
+        .map_err(|e| Error::DecodeText(e.to_string().into())),
+      TextEncoding::UTF16LE => {
+        if !self.bytes.chunks_exact(2).remainder().is_empty() {
+          return Err(Error::DecodeText("incomplete sequence".into()));
+        }
+        decode_utf16(self.units())
+          .collect::<std::result::Result<String, _>>()
+          .map(Cow::Owned)
+          .map_err(|e| Error::DecodeText(e.to_string().into()))
+      }
+    }
+  }
+
+  fn units(&self) -> impl Iterator<Item = u16> + 'a {
+    self
+      .bytes
+      .chunks_exact(2)
+      .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+  }
+}
+
+// Compares without decoding, handy for looking up bones or morphs by name
+impl PartialEq<str> for Text<'_> {
+  fn eq(&self, other: &str) -> bool {
+    match self.encoding {
+      TextEncoding::UTF8 => self.bytes == other.as_bytes(),
+      TextEncoding::UTF16LE => {
+        self.bytes.chunks_exact(2).remainder().is_empty() && self.units().eq(other.encode_utf16())
+      }
+    }
+  }
+}
+
+impl PartialEq<&str> for Text<'_> {
+  fn eq(&self, other: &&str) -> bool {
+    *self == **other
+  }
+}
+
+impl Display for Text<'_> {
+  fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    match self.encoding {
+      TextEncoding::UTF8 => write!(f, "{}", String::from_utf8_lossy(self.bytes)),
+      TextEncoding::UTF16LE => decode_utf16(self.units())
+        .map(|c| c.unwrap_or(REPLACEMENT_CHARACTER))
+        .try_for_each(|c| write!(f, "{}", c)),
+    }
+  }
+}
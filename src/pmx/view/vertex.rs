@@ -0,0 +1,226 @@
+use crate::{
+  pmx::error::Section,
+  reader::{helpers::bounded_capacity, limited::LimitedRead, VertexReader},
+  view::Cursor,
+  Config, Error, IndexSize, ParseLimits, Result, Settings, Vertex,
+};
+use std::convert::TryInto;
+
+pub struct Vertices<'a> {
+  version: f32,
+  settings: Settings,
+  limits: ParseLimits,
+  bytes: &'a [u8],
+  // start of every vertex and the end of the last one
+  offsets: Vec<usize>,
+}
+
+impl<'a> Vertices<'a> {
+  pub(crate) fn scan(cursor: &mut Cursor<'a>, version: f32, settings: Settings) -> Result<Self> {
+    let offset = cursor.position as u64;
+    let count = cursor
+      .count()
+      .and_then(|count| {
+        let limit = cursor.limits.max_vertices;
+        if count > limit {
+          return Err(Error::LimitExceeded {
+            what: "vertices",
+            requested: count as u64,
+            limit: limit as u64,
+          });
+        }
+        Ok(count)
+      })
+      .map_err(|e| e.at(Section::Vertices, None, offset))?;
+
+    let fixed = (3 + 3 + 2 + 4 * settings.additional_vec4_count as usize) * 4;
+    let index = settings.bone_index_size as usize;
+    let mut offsets = Vec::with_capacity(bounded_capacity(count + 1));
+    for element in 0..count {
+      let start = cursor.position;
+      offsets.push(start);
+      let mut skip = || {
+        cursor.take(fixed)?;
+        let weights = match cursor.take(1)?[0] {
+          0 => index,
+          1 => 2 * index + 4,
+          2 => 4 * index + 4 * 4,
+          3 => 2 * index + 4 + 3 * 3 * 4,
+          4 => 4 * index + 4 * 4,
+          e => return Err(Error::UnknownWeightType(e)),
+        };
+        cursor.take(weights + 4).map(|_| ())
+      };
+      skip().map_err(|e| e.at(Section::Vertices, Some(element), start as u64))?;
+    }
+    offsets.push(cursor.position);
+
+    Ok(Vertices {
+      version,
+      settings,
+      limits: cursor.limits,
+      bytes: cursor.bytes,
+      offsets,
+    })
+  }
+
+  pub fn len(&self) -> usize {
+    self.offsets.len() - 1
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  pub fn get(&self, index: usize) -> Option<VertexView<'a>> {
+    let (start, end) = (*self.offsets.get(index)?, *self.offsets.get(index + 1)?);
+    Some(VertexView {
+      version: self.version,
+      settings: self.settings,
+      limits: self.limits,
+      bytes: &self.bytes[start..end],
+      offset: start,
+      index,
+      count: self.len(),
+    })
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = VertexView<'a>> + '_ {
+    (0..self.len()).filter_map(move |i| self.get(i))
+  }
+}
+
+// Fixed size fields are read straight from the bytes, the full vertex through VertexReader
+#[derive(Clone, Copy)]
+pub struct VertexView<'a> {
+  version: f32,
+  settings: Settings,
+  limits: ParseLimits,
+  bytes: &'a [u8],
+  offset: usize,
+  index: usize,
+  count: usize,
+}
+
+impl<'a> VertexView<'a> {
+  pub fn as_bytes(&self) -> &'a [u8] {
+    self.bytes
+  }
+
+  pub fn position<C: Config>(&self) -> C::Vec3 {
+    self.floats::<3>(0).into()
+  }
+
+  pub fn normal<C: Config>(&self) -> C::Vec3 {
+    self.floats::<3>(12).into()
+  }
+
+  pub fn uv<C: Config>(&self) -> C::Vec2 {
+    self.floats::<2>(24).into()
+  }
+
+  pub fn additional<C: Config>(&self, index: usize) -> Option<C::Vec4> {
+    if index >= self.settings.additional_vec4_count as usize {
+      return None;
+    }
+    Some(self.floats::<4>(32 + 16 * index).into())
+  }
+
+  pub fn weight_type(&self) -> u8 {
+    self.bytes[32 + 16 * self.settings.additional_vec4_count as usize]
+  }
+
+  pub fn edge_scale(&self) -> f32 {
+    self.floats::<1>(self.bytes.len() - 4)[0]
+  }
+
+  pub fn read<C: Config>(&self) -> Result<Vertex<C>> {
+    let mut reader = VertexReader {
+      version: self.version,
+      settings: self.settings,
+      count: self.count as i32,
+      remaining: (self.count - self.index) as i32,
+      read: LimitedRead::at(self.bytes, self.limits, self.offset as u64),
+      poison: false,
+    };
+    Ok(reader.next::<C>()?.unwrap())
+  }
+
+  fn floats<const N: usize>(&self, at: usize) -> [f32; N] {
+    let mut values = [0.0; N];
+    for (i, v) in values.iter_mut().enumerate() {
+      let start = at + 4 * i;
+      *v = f32::from_le_bytes(self.bytes[start..start + 4].try_into().unwrap());
+    }
+    values
+  }
+}
+
+// Triangles stay packed as in the file, ready to be used as an index buffer
+#[derive(Clone, Copy)]
+pub struct Surfaces<'a> {
+  bytes: &'a [u8],
+  index_size: IndexSize,
+}
+
+impl<'a> Surfaces<'a> {
+  pub(crate) fn scan(cursor: &mut Cursor<'a>, settings: Settings) -> Result<Self> {
+    let offset = cursor.position as u64;
+    let count = cursor
+      .count()
+      .map_err(|e| e.at(Section::Surfaces, None, offset))?;
+    if count % 3 != 0 {
+      return Err(Error::InvalidSurfaceCount(count as i32).at(Section::Surfaces, None, offset));
+    }
+
+    let index_size = settings.vertex_index_size;
+    let start = cursor.position;
+    let bytes = cursor.take(count * index_size as usize).map_err(|e| {
+      // point at the first triangle that does not fit
+      let triangle = 3 * index_size as usize;
+      let element = (cursor.bytes.len().saturating_sub(start) / triangle).min(count / 3);
+      e.at(
+        Section::Surfaces,
+        Some(element),
+        (start + element * triangle) as u64,
+      )
+    })?;
+    Ok(Surfaces { bytes, index_size })
+  }
+
+  pub fn len(&self) -> usize {
+    self.bytes.len() / (3 * self.index_size as usize)
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.bytes.is_empty()
+  }
+
+  pub fn index_size(&self) -> IndexSize {
+    self.index_size
+  }
+
+  pub fn as_bytes(&self) -> &'a [u8] {
+    self.bytes
+  }
+
+  // 8 and 16 bit indices are unsigned, 32 bit ones are kept as their raw bits
+  pub fn get(&self, index: usize) -> Option<[u32; 3]> {
+    let size = self.index_size as usize;
+    let triangle = self.bytes.get(index * 3 * size..(index + 1) * 3 * size)?;
+    let mut vertices = [0; 3];
+    for (v, bytes) in vertices.iter_mut().zip(triangle.chunks_exact(size)) {
+      *v = match self.index_size {
+        IndexSize::I8 => bytes[0] as u32,
+        IndexSize::I16 => u16::from_le_bytes([bytes[0], bytes[1]]) as u32,
+        IndexSize::I32 => u32::from_le_bytes(bytes.try_into().unwrap()),
+      };
+    }
+    Some(vertices)
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = [u32; 3]> + 'a {
+    let surfaces = *self;
+    (0..self.len()).filter_map(move |i| surfaces.get(i))
+  }
+}
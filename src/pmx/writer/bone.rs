@@ -0,0 +1,150 @@
+use crate::{
+  pmx::bone::{BoneFlags, Connection},
+  pmx::error::Section,
+  writer::{
+    counted::CountedWrite,
+    helpers::{checked_count, WriteHelpers},
+    MaterialWriter,
+  },
+  Bone, Config, Error, Result, Settings,
+};
+use byteorder::{WriteBytesExt, LE};
+use std::io::Write;
+
+pub struct BoneWriter<W> {
+  pub version: f32,
+  pub settings: Settings,
+  pub count: i32,
+  pub remaining: i32,
+  pub(crate) write: CountedWrite<W>,
+  pub(crate) poison: bool,
+}
+
+impl<W: Write> BoneWriter<W> {
+  pub fn new(mut m: MaterialWriter<W>, count: usize) -> Result<BoneWriter<W>> {
+    if m.poison {
+      return Err(Error::Poisoned);
+    }
+    if m.remaining != 0 {
+      return Err(
+        Error::ElementCountMismatch {
+          section: Section::Materials,
+          expected: m.count,
+          written: m.count - m.remaining,
+        }
+        .at(Section::Materials, None, m.write.position),
+      );
+    }
+    let offset = m.write.position;
+    let count = checked_count(count)
+      .and_then(|count| {
+        m.write.write_i32::<LE>(count)?;
+        Ok(count)
+      })
+      .map_err(|e| e.at(Section::Bones, None, offset))?;
+
+    Ok(BoneWriter {
+      version: m.version,
+      settings: m.settings,
+      count,
+      remaining: count,
+      write: m.write,
+      poison: false,
+    })
+  }
+
+  pub fn write<C: Config>(&mut self, bone: &Bone<C>) -> Result<()> {
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    let element = (self.count - self.remaining) as usize;
+    let offset = self.write.position;
+    let result = self.write_impl(bone);
+    if result.is_err() {
+      self.poison = true;
+    }
+    result.map_err(|e| e.at(Section::Bones, Some(element), offset))
+  }
+
+  fn write_impl<C: Config>(&mut self, bone: &Bone<C>) -> Result<()> {
+    if self.remaining <= 0 {
+      return Err(Error::ElementCountMismatch {
+        section: Section::Bones,
+        expected: self.count,
+        written: self.count + 1,
+      });
+    }
+
+    let flags = bone.bone_flags;
+    let consistent = flags.contains(BoneFlags::Connection)
+      == matches!(bone.connection, Connection::Index(_))
+      && flags.intersects(BoneFlags::AddRotation | BoneFlags::AddMovement)
+        == bone.additional.is_some()
+      && flags.contains(BoneFlags::FixedAxis) == bone.fixed_axis.is_some()
+      && flags.contains(BoneFlags::LocalAxis) == bone.local_axis.is_some()
+      && flags.contains(BoneFlags::ExternalParentTransform)
+        == bone.external_parent_transform.is_some()
+      && flags.contains(BoneFlags::InverseKinematics) == bone.inverse_kinematics.is_some();
+    if !consistent {
+      return Err(Error::InconsistentBoneFlags(flags.bits()));
+    }
+
+    let encoding = self.settings.text_encoding;
+    let bone_index_size = self.settings.bone_index_size;
+    self.write.write_text(encoding, &bone.local_name)?;
+    self.write.write_text(encoding, &bone.universal_name)?;
+    self.write.write_vec(&bone.position)?;
+    self.write.write_index(bone_index_size, &bone.parent)?;
+    self.write.write_i32::<LE>(bone.transform_level)?;
+    self
+      .write
+      .write_u16::<LE>(flags.bits() | bone.unknown_flags)?;
+
+    match bone.connection {
+      Connection::Index(ref index) => self.write.write_index(bone_index_size, index)?,
+      Connection::Position(ref position) => self.write.write_vec(position)?,
+    }
+
+    if let Some(ref additional) = bone.additional {
+      self
+        .write
+        .write_index(bone_index_size, &additional.parent)?;
+      self.write.write_f32::<LE>(additional.rate)?;
+    }
+
+    if let Some(ref fixed_axis) = bone.fixed_axis {
+      self.write.write_vec(fixed_axis)?;
+    }
+
+    if let Some(ref local_axis) = bone.local_axis {
+      self.write.write_vec(&local_axis.x)?;
+      self.write.write_vec(&local_axis.z)?;
+    }
+
+    if let Some(external_parent_transform) = bone.external_parent_transform {
+      self.write.write_i32::<LE>(external_parent_transform)?;
+    }
+
+    if let Some(ref ik) = bone.inverse_kinematics {
+      self.write.write_index(bone_index_size, &ik.ik_bone)?;
+      self.write.write_u32::<LE>(ik.iterations)?;
+      self.write.write_f32::<LE>(ik.limit_angle)?;
+      self
+        .write
+        .write_u32::<LE>(checked_count(ik.links.len())? as u32)?;
+      for link in &ik.links {
+        self.write.write_index(bone_index_size, &link.ik_bone)?;
+        self
+          .write
+          .write_flag(link.limits.is_some(), link.raw_limits)?;
+        if let Some((ref low, ref high)) = link.limits {
+          self.write.write_vec(low)?;
+          self.write.write_vec(high)?;
+        }
+      }
+    }
+
+    self.remaining -= 1;
+    Ok(())
+  }
+}
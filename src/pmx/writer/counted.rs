@@ -0,0 +1,24 @@
+use std::io::{self, Write};
+
+pub(crate) struct CountedWrite<W> {
+  pub(crate) inner: W,
+  pub(crate) position: u64,
+}
+
+impl<W> CountedWrite<W> {
+  pub(crate) fn new(inner: W) -> Self {
+    CountedWrite { inner, position: 0 }
+  }
+}
+
+impl<W: Write> Write for CountedWrite<W> {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    let written = self.inner.write(buf)?;
+    self.position += written as u64;
+    Ok(written)
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    self.inner.flush()
+  }
+}
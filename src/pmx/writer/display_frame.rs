@@ -0,0 +1,103 @@
+use crate::{
+  pmx::display_frame::{DisplayFrame, FrameElement},
+  pmx::error::Section,
+  writer::{
+    counted::CountedWrite,
+    helpers::{checked_count, WriteHelpers},
+    MorphWriter,
+  },
+  Config, Error, Result, Settings,
+};
+use byteorder::{WriteBytesExt, LE};
+use std::io::Write;
+
+pub struct DisplayFrameWriter<W> {
+  pub version: f32,
+  pub settings: Settings,
+  pub count: i32,
+  pub remaining: i32,
+  pub(crate) write: CountedWrite<W>,
+  pub(crate) poison: bool,
+}
+
+impl<W: Write> DisplayFrameWriter<W> {
+  pub fn new(mut m: MorphWriter<W>, count: usize) -> Result<DisplayFrameWriter<W>> {
+    if m.poison {
+      return Err(Error::Poisoned);
+    }
+    if m.remaining != 0 {
+      return Err(
+        Error::ElementCountMismatch {
+          section: Section::Morphs,
+          expected: m.count,
+          written: m.count - m.remaining,
+        }
+        .at(Section::Morphs, None, m.write.position),
+      );
+    }
+    let offset = m.write.position;
+    let count = checked_count(count)
+      .and_then(|count| {
+        m.write.write_i32::<LE>(count)?;
+        Ok(count)
+      })
+      .map_err(|e| e.at(Section::DisplayFrames, None, offset))?;
+
+    Ok(DisplayFrameWriter {
+      version: m.version,
+      settings: m.settings,
+      count,
+      remaining: count,
+      write: m.write,
+      poison: false,
+    })
+  }
+
+  pub fn write<C: Config>(&mut self, frame: &DisplayFrame<C>) -> Result<()> {
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    let element = (self.count - self.remaining) as usize;
+    let offset = self.write.position;
+    let result = self.write_impl(frame);
+    if result.is_err() {
+      self.poison = true;
+    }
+    result.map_err(|e| e.at(Section::DisplayFrames, Some(element), offset))
+  }
+
+  fn write_impl<C: Config>(&mut self, frame: &DisplayFrame<C>) -> Result<()> {
+    if self.remaining <= 0 {
+      return Err(Error::ElementCountMismatch {
+        section: Section::DisplayFrames,
+        expected: self.count,
+        written: self.count + 1,
+      });
+    }
+
+    let encoding = self.settings.text_encoding;
+    self.write.write_text(encoding, &frame.local_name)?;
+    self.write.write_text(encoding, &frame.universal_name)?;
+    self.write.write_flag(frame.special, frame.raw_special)?;
+    self.write.write_count(frame.elements.len())?;
+    for element in &frame.elements {
+      match element {
+        FrameElement::Bone(index) => {
+          self.write.write_u8(0)?;
+          self
+            .write
+            .write_index(self.settings.bone_index_size, index)?;
+        }
+        FrameElement::Morph(index) => {
+          self.write.write_u8(1)?;
+          self
+            .write
+            .write_index(self.settings.morph_index_size, index)?;
+        }
+      }
+    }
+
+    self.remaining -= 1;
+    Ok(())
+  }
+}
@@ -0,0 +1,79 @@
+use crate::{
+  pmx::error::Section,
+  writer::{counted::CountedWrite, helpers::WriteHelpers},
+  Error, Result, Settings,
+};
+use byteorder::{WriteBytesExt, LE};
+use std::io::Write;
+
+pub struct HeaderWriter<W> {
+  pub version: f32,
+  pub settings: Settings,
+  pub extra_globals: Vec<u8>,
+  pub model_local_name: String,
+  pub model_universal_name: String,
+  pub local_comments: String,
+  pub universal_comments: String,
+  pub(crate) write: CountedWrite<W>,
+}
+
+impl<W: Write> HeaderWriter<W> {
+  // Nothing is written until the header is handed to VertexWriter, fill the public fields first
+  pub fn new(write: W, version: f32, settings: Settings) -> HeaderWriter<W> {
+    HeaderWriter {
+      version,
+      settings,
+      extra_globals: Vec::new(),
+      model_local_name: String::new(),
+      model_universal_name: String::new(),
+      local_comments: String::new(),
+      universal_comments: String::new(),
+      write: CountedWrite::new(write),
+    }
+  }
+
+  pub(crate) fn write_header(&mut self) -> Result<()> {
+    self
+      .write_header_impl()
+      .map_err(|e| e.at(Section::Header, None, 0))
+  }
+
+  fn write_header_impl(&mut self) -> Result<()> {
+    let settings = self.settings;
+    if settings.additional_vec4_count > 4 {
+      return Err(Error::InvalidAdditionalVec4Count(
+        settings.additional_vec4_count,
+      ));
+    }
+    if self.extra_globals.len() > (u8::MAX - 8) as usize {
+      return Err(Error::LimitExceeded {
+        what: "globals",
+        requested: 8 + self.extra_globals.len() as u64,
+        limit: u8::MAX as u64,
+      });
+    }
+
+    self.write.write_all(&[0x50, 0x4D, 0x58, 0x20])?;
+    self.write.write_f32::<LE>(self.version)?;
+    self.write.write_u8(8 + self.extra_globals.len() as u8)?;
+    self.write.write_all(&[
+      settings.text_encoding as u8,
+      settings.additional_vec4_count,
+      settings.vertex_index_size as u8,
+      settings.texture_index_size as u8,
+      settings.material_index_size as u8,
+      settings.bone_index_size as u8,
+      settings.morph_index_size as u8,
+      settings.rigidbody_index_size as u8,
+    ])?;
+    self.write.write_all(&self.extra_globals)?;
+
+    let encoding = settings.text_encoding;
+    self.write.write_text(encoding, &self.model_local_name)?;
+    self
+      .write
+      .write_text(encoding, &self.model_universal_name)?;
+    self.write.write_text(encoding, &self.local_comments)?;
+    self.write.write_text(encoding, &self.universal_comments)
+  }
+}
@@ -0,0 +1,81 @@
+use crate::{pmx::types::*, writer::counted::CountedWrite, Error, Result};
+use byteorder::{WriteBytesExt, LE};
+use std::convert::{TryFrom, TryInto};
+use std::io::Write;
+
+pub(crate) fn checked_count(count: usize) -> Result<i32> {
+  i32::try_from(count).map_err(|_| Error::LimitExceeded {
+    what: "elements",
+    requested: count as u64,
+    limit: i32::MAX as u64,
+  })
+}
+
+pub(crate) trait WriteHelpers: Write {
+  fn write_count(&mut self, count: usize) -> Result<()> {
+    self.write_i32::<LE>(checked_count(count)?)?;
+    Ok(())
+  }
+
+  fn write_text(&mut self, encoding: TextEncoding, text: &str) -> Result<()> {
+    let bytes = match encoding {
+      TextEncoding::UTF8 => text.as_bytes().to_vec(),
+      TextEncoding::UTF16LE => text.encode_utf16().flat_map(u16::to_le_bytes).collect(),
+    };
+    let size = i32::try_from(bytes.len()).map_err(|_| Error::LimitExceeded {
+      what: "text bytes",
+      requested: bytes.len() as u64,
+      limit: i32::MAX as u64,
+    })?;
+    self.write_i32::<LE>(size)?;
+    self.write_all(&bytes)?;
+    Ok(())
+  }
+
+  // the original byte as long as it still agrees with the flag, editors may store any non zero
+  fn write_flag(&mut self, flag: bool, raw: u8) -> Result<()> {
+    self.write_u8(if (raw != 0) == flag { raw } else { flag as u8 })?;
+    Ok(())
+  }
+
+  fn write_vec<V: AsRef<[f32]>>(&mut self, v: &V) -> Result<()> {
+    for &component in v.as_ref() {
+      self.write_f32::<LE>(component)?;
+    }
+    Ok(())
+  }
+
+  fn write_index<I: Index>(&mut self, size: IndexSize, index: &I) -> Result<()> {
+    let overflow = || Error::IndexDoesNotFit(format!("{:?}", index), size);
+    match size {
+      IndexSize::I8 => {
+        self.write_i8(TryInto::<i8>::try_into(index.clone()).map_err(|_| overflow())?)?
+      }
+      IndexSize::I16 => {
+        self.write_i16::<LE>(TryInto::<i16>::try_into(index.clone()).map_err(|_| overflow())?)?
+      }
+      IndexSize::I32 => {
+        self.write_i32::<LE>(TryInto::<i32>::try_into(index.clone()).map_err(|_| overflow())?)?
+      }
+    }
+    Ok(())
+  }
+
+  fn write_vertex_index<I: VertexIndex>(&mut self, size: IndexSize, index: &I) -> Result<()> {
+    let overflow = || Error::IndexDoesNotFit(format!("{:?}", index), size);
+    match size {
+      IndexSize::I8 => {
+        self.write_u8(TryInto::<u8>::try_into(index.clone()).map_err(|_| overflow())?)?
+      }
+      IndexSize::I16 => {
+        self.write_u16::<LE>(TryInto::<u16>::try_into(index.clone()).map_err(|_| overflow())?)?
+      }
+      IndexSize::I32 => {
+        self.write_i32::<LE>(TryInto::<i32>::try_into(index.clone()).map_err(|_| overflow())?)?
+      }
+    }
+    Ok(())
+  }
+}
+
+impl<W: Write> WriteHelpers for CountedWrite<W> {}
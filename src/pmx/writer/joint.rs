@@ -0,0 +1,100 @@
+use crate::{
+  pmx::error::Section,
+  writer::{
+    counted::CountedWrite,
+    helpers::{checked_count, WriteHelpers},
+    RigidBodyWriter,
+  },
+  Config, Error, Joint, Result, Settings,
+};
+use byteorder::{WriteBytesExt, LE};
+use std::io::Write;
+
+pub struct JointWriter<W> {
+  pub version: f32,
+  pub settings: Settings,
+  pub count: i32,
+  pub remaining: i32,
+  pub(crate) write: CountedWrite<W>,
+  pub(crate) poison: bool,
+}
+
+impl<W: Write> JointWriter<W> {
+  pub fn new(mut r: RigidBodyWriter<W>, count: usize) -> Result<JointWriter<W>> {
+    if r.poison {
+      return Err(Error::Poisoned);
+    }
+    if r.remaining != 0 {
+      return Err(
+        Error::ElementCountMismatch {
+          section: Section::RigidBodies,
+          expected: r.count,
+          written: r.count - r.remaining,
+        }
+        .at(Section::RigidBodies, None, r.write.position),
+      );
+    }
+    let offset = r.write.position;
+    let count = checked_count(count)
+      .and_then(|count| {
+        r.write.write_i32::<LE>(count)?;
+        Ok(count)
+      })
+      .map_err(|e| e.at(Section::Joints, None, offset))?;
+
+    Ok(JointWriter {
+      version: r.version,
+      settings: r.settings,
+      count,
+      remaining: count,
+      write: r.write,
+      poison: false,
+    })
+  }
+
+  pub fn write<C: Config>(&mut self, joint: &Joint<C>) -> Result<()> {
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    let element = (self.count - self.remaining) as usize;
+    let offset = self.write.position;
+    let result = self.write_impl(joint);
+    if result.is_err() {
+      self.poison = true;
+    }
+    result.map_err(|e| e.at(Section::Joints, Some(element), offset))
+  }
+
+  fn write_impl<C: Config>(&mut self, joint: &Joint<C>) -> Result<()> {
+    if self.remaining <= 0 {
+      return Err(Error::ElementCountMismatch {
+        section: Section::Joints,
+        expected: self.count,
+        written: self.count + 1,
+      });
+    }
+
+    let encoding = self.settings.text_encoding;
+    let rigidbody_index_size = self.settings.rigidbody_index_size;
+    self.write.write_text(encoding, &joint.local_name)?;
+    self.write.write_text(encoding, &joint.universal_name)?;
+    self.write.write_u8(joint.joint_type as u8)?;
+    self
+      .write
+      .write_index(rigidbody_index_size, &joint.rigid_body_a)?;
+    self
+      .write
+      .write_index(rigidbody_index_size, &joint.rigid_body_b)?;
+    self.write.write_vec(&joint.position)?;
+    self.write.write_vec(&joint.rotation)?;
+    self.write.write_vec(&joint.position_min)?;
+    self.write.write_vec(&joint.position_max)?;
+    self.write.write_vec(&joint.rotation_min)?;
+    self.write.write_vec(&joint.rotation_max)?;
+    self.write.write_vec(&joint.position_spring)?;
+    self.write.write_vec(&joint.rotation_spring)?;
+
+    self.remaining -= 1;
+    Ok(())
+  }
+}
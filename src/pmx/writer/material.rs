@@ -0,0 +1,112 @@
+use crate::{
+  pmx::error::Section,
+  pmx::material::Toon,
+  writer::{
+    counted::CountedWrite,
+    helpers::{checked_count, WriteHelpers},
+    TextureWriter,
+  },
+  Config, Error, Material, Result, Settings,
+};
+use byteorder::{WriteBytesExt, LE};
+use std::io::Write;
+
+pub struct MaterialWriter<W> {
+  pub version: f32,
+  pub settings: Settings,
+  pub count: i32,
+  pub remaining: i32,
+  pub(crate) write: CountedWrite<W>,
+  pub(crate) poison: bool,
+}
+
+impl<W: Write> MaterialWriter<W> {
+  pub fn new(mut t: TextureWriter<W>, count: usize) -> Result<MaterialWriter<W>> {
+    if t.poison {
+      return Err(Error::Poisoned);
+    }
+    if t.remaining != 0 {
+      return Err(
+        Error::ElementCountMismatch {
+          section: Section::Textures,
+          expected: t.count,
+          written: t.count - t.remaining,
+        }
+        .at(Section::Textures, None, t.write.position),
+      );
+    }
+    let offset = t.write.position;
+    let count = checked_count(count)
+      .and_then(|count| {
+        t.write.write_i32::<LE>(count)?;
+        Ok(count)
+      })
+      .map_err(|e| e.at(Section::Materials, None, offset))?;
+
+    Ok(MaterialWriter {
+      version: t.version,
+      settings: t.settings,
+      count,
+      remaining: count,
+      write: t.write,
+      poison: false,
+    })
+  }
+
+  pub fn write<C: Config>(&mut self, material: &Material<C>) -> Result<()> {
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    let element = (self.count - self.remaining) as usize;
+    let offset = self.write.position;
+    let result = self.write_impl(material);
+    if result.is_err() {
+      self.poison = true;
+    }
+    result.map_err(|e| e.at(Section::Materials, Some(element), offset))
+  }
+
+  fn write_impl<C: Config>(&mut self, material: &Material<C>) -> Result<()> {
+    if self.remaining <= 0 {
+      return Err(Error::ElementCountMismatch {
+        section: Section::Materials,
+        expected: self.count,
+        written: self.count + 1,
+      });
+    }
+
+    let encoding = self.settings.text_encoding;
+    let texture_index_size = self.settings.texture_index_size;
+    self.write.write_text(encoding, &material.local_name)?;
+    self.write.write_text(encoding, &material.universal_name)?;
+    self.write.write_vec(&material.diffuse_color)?;
+    self.write.write_vec(&material.specular_color)?;
+    self.write.write_f32::<LE>(material.specular_strength)?;
+    self.write.write_vec(&material.ambient_color)?;
+    self.write.write_u8(material.draw_flags.bits())?;
+    self.write.write_vec(&material.edge_color)?;
+    self.write.write_f32::<LE>(material.edge_scale)?;
+    self
+      .write
+      .write_index(texture_index_size, &material.texture_index)?;
+    self
+      .write
+      .write_index(texture_index_size, &material.environment_index)?;
+    self.write.write_u8(material.environment_blend_mode as u8)?;
+    match material.toon {
+      Toon::Texture(ref index) => {
+        self.write.write_u8(0)?;
+        self.write.write_index(texture_index_size, index)?;
+      }
+      Toon::Internal(index) => {
+        self.write.write_u8(1)?;
+        self.write.write_u8(index)?;
+      }
+    }
+    self.write.write_text(encoding, &material.metadata)?;
+    self.write.write_i32::<LE>(material.surface_count)?;
+
+    self.remaining -= 1;
+    Ok(())
+  }
+}
@@ -0,0 +1,170 @@
+use crate::{
+  pmx::error::Section,
+  pmx::morph::{Morph, Offsets},
+  writer::{
+    counted::CountedWrite,
+    helpers::{checked_count, WriteHelpers},
+    BoneWriter,
+  },
+  Config, Error, Result, Settings,
+};
+use byteorder::{WriteBytesExt, LE};
+use std::io::Write;
+
+pub struct MorphWriter<W> {
+  pub version: f32,
+  pub settings: Settings,
+  pub count: i32,
+  pub remaining: i32,
+  pub(crate) write: CountedWrite<W>,
+  pub(crate) poison: bool,
+}
+
+impl<W: Write> MorphWriter<W> {
+  pub fn new(mut b: BoneWriter<W>, count: usize) -> Result<MorphWriter<W>> {
+    if b.poison {
+      return Err(Error::Poisoned);
+    }
+    if b.remaining != 0 {
+      return Err(
+        Error::ElementCountMismatch {
+          section: Section::Bones,
+          expected: b.count,
+          written: b.count - b.remaining,
+        }
+        .at(Section::Bones, None, b.write.position),
+      );
+    }
+    let offset = b.write.position;
+    let count = checked_count(count)
+      .and_then(|count| {
+        b.write.write_i32::<LE>(count)?;
+        Ok(count)
+      })
+      .map_err(|e| e.at(Section::Morphs, None, offset))?;
+
+    Ok(MorphWriter {
+      version: b.version,
+      settings: b.settings,
+      count,
+      remaining: count,
+      write: b.write,
+      poison: false,
+    })
+  }
+
+  pub fn write<C: Config>(&mut self, morph: &Morph<C>) -> Result<()> {
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    let element = (self.count - self.remaining) as usize;
+    let offset = self.write.position;
+    let result = self.write_impl(morph);
+    if result.is_err() {
+      self.poison = true;
+    }
+    result.map_err(|e| e.at(Section::Morphs, Some(element), offset))
+  }
+
+  fn write_impl<C: Config>(&mut self, morph: &Morph<C>) -> Result<()> {
+    if self.remaining <= 0 {
+      return Err(Error::ElementCountMismatch {
+        section: Section::Morphs,
+        expected: self.count,
+        written: self.count + 1,
+      });
+    }
+
+    let encoding = self.settings.text_encoding;
+    self.write.write_text(encoding, &morph.local_name)?;
+    self.write.write_text(encoding, &morph.universal_name)?;
+    self.write.write_u8(morph.panel.into())?;
+
+    let (morph_type, count) = match morph.offsets {
+      Offsets::Group(ref offsets) => (0, offsets.len()),
+      Offsets::Vertex(ref offsets) => (1, offsets.len()),
+      Offsets::Bone(ref offsets) => (2, offsets.len()),
+      Offsets::UV(ref offsets) => (3, offsets.len()),
+      Offsets::AdditionalUV1(ref offsets) => (4, offsets.len()),
+      Offsets::AdditionalUV2(ref offsets) => (5, offsets.len()),
+      Offsets::AdditionalUV3(ref offsets) => (6, offsets.len()),
+      Offsets::AdditionalUV4(ref offsets) => (7, offsets.len()),
+      Offsets::Material(ref offsets) => (8, offsets.len()),
+      Offsets::Flip(ref offsets) => (9, offsets.len()),
+      Offsets::Impulse(ref offsets) => (10, offsets.len()),
+    };
+    self.write.write_u8(morph_type)?;
+    self.write.write_u32::<LE>(checked_count(count)? as u32)?;
+
+    let settings = self.settings;
+    match morph.offsets {
+      Offsets::Group(ref offsets) | Offsets::Flip(ref offsets) => {
+        for offset in offsets {
+          self
+            .write
+            .write_index(settings.morph_index_size, &offset.morph)?;
+          self.write.write_f32::<LE>(offset.influence)?;
+        }
+      }
+      Offsets::Vertex(ref offsets) => {
+        for offset in offsets {
+          self
+            .write
+            .write_vertex_index(settings.vertex_index_size, &offset.vertex)?;
+          self.write.write_vec(&offset.offset)?;
+        }
+      }
+      Offsets::Bone(ref offsets) => {
+        for offset in offsets {
+          self
+            .write
+            .write_index(settings.bone_index_size, &offset.bone)?;
+          self.write.write_vec(&offset.translation)?;
+          self.write.write_vec(&offset.rotation)?;
+        }
+      }
+      Offsets::UV(ref offsets)
+      | Offsets::AdditionalUV1(ref offsets)
+      | Offsets::AdditionalUV2(ref offsets)
+      | Offsets::AdditionalUV3(ref offsets)
+      | Offsets::AdditionalUV4(ref offsets) => {
+        for offset in offsets {
+          self
+            .write
+            .write_vertex_index(settings.vertex_index_size, &offset.vertex)?;
+          self.write.write_vec(&offset.offset)?;
+        }
+      }
+      Offsets::Material(ref offsets) => {
+        for offset in offsets {
+          self
+            .write
+            .write_index(settings.material_index_size, &offset.material)?;
+          self.write.write_u8(offset.method as u8)?;
+          self.write.write_vec(&offset.diffuse_color)?;
+          self.write.write_vec(&offset.specular_color)?;
+          self.write.write_f32::<LE>(offset.specular_strength)?;
+          self.write.write_vec(&offset.ambient_color)?;
+          self.write.write_vec(&offset.edge_color)?;
+          self.write.write_f32::<LE>(offset.edge_scale)?;
+          self.write.write_vec(&offset.texture_tint)?;
+          self.write.write_vec(&offset.environment_tint)?;
+          self.write.write_vec(&offset.toon_tint)?;
+        }
+      }
+      Offsets::Impulse(ref offsets) => {
+        for offset in offsets {
+          self
+            .write
+            .write_index(settings.rigidbody_index_size, &offset.rigid_body)?;
+          self.write.write_flag(offset.local, offset.raw_local)?;
+          self.write.write_vec(&offset.velocity)?;
+          self.write.write_vec(&offset.torque)?;
+        }
+      }
+    }
+
+    self.remaining -= 1;
+    Ok(())
+  }
+}
@@ -0,0 +1,101 @@
+use crate::{
+  pmx::error::Section,
+  writer::{
+    counted::CountedWrite,
+    helpers::{checked_count, WriteHelpers},
+    DisplayFrameWriter,
+  },
+  Config, Error, Result, RigidBody, Settings,
+};
+use byteorder::{WriteBytesExt, LE};
+use std::io::Write;
+
+pub struct RigidBodyWriter<W> {
+  pub version: f32,
+  pub settings: Settings,
+  pub count: i32,
+  pub remaining: i32,
+  pub(crate) write: CountedWrite<W>,
+  pub(crate) poison: bool,
+}
+
+impl<W: Write> RigidBodyWriter<W> {
+  pub fn new(mut d: DisplayFrameWriter<W>, count: usize) -> Result<RigidBodyWriter<W>> {
+    if d.poison {
+      return Err(Error::Poisoned);
+    }
+    if d.remaining != 0 {
+      return Err(
+        Error::ElementCountMismatch {
+          section: Section::DisplayFrames,
+          expected: d.count,
+          written: d.count - d.remaining,
+        }
+        .at(Section::DisplayFrames, None, d.write.position),
+      );
+    }
+    let offset = d.write.position;
+    let count = checked_count(count)
+      .and_then(|count| {
+        d.write.write_i32::<LE>(count)?;
+        Ok(count)
+      })
+      .map_err(|e| e.at(Section::RigidBodies, None, offset))?;
+
+    Ok(RigidBodyWriter {
+      version: d.version,
+      settings: d.settings,
+      count,
+      remaining: count,
+      write: d.write,
+      poison: false,
+    })
+  }
+
+  pub fn write<C: Config>(&mut self, rigid_body: &RigidBody<C>) -> Result<()> {
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    let element = (self.count - self.remaining) as usize;
+    let offset = self.write.position;
+    let result = self.write_impl(rigid_body);
+    if result.is_err() {
+      self.poison = true;
+    }
+    result.map_err(|e| e.at(Section::RigidBodies, Some(element), offset))
+  }
+
+  fn write_impl<C: Config>(&mut self, rigid_body: &RigidBody<C>) -> Result<()> {
+    if self.remaining <= 0 {
+      return Err(Error::ElementCountMismatch {
+        section: Section::RigidBodies,
+        expected: self.count,
+        written: self.count + 1,
+      });
+    }
+
+    let encoding = self.settings.text_encoding;
+    self.write.write_text(encoding, &rigid_body.local_name)?;
+    self
+      .write
+      .write_text(encoding, &rigid_body.universal_name)?;
+    self
+      .write
+      .write_index(self.settings.bone_index_size, &rigid_body.bone_index)?;
+    self.write.write_u8(rigid_body.group)?;
+    self.write.write_u16::<LE>(rigid_body.non_collision_mask)?;
+    self.write.write_u8(rigid_body.shape as u8)?;
+    self.write.write_vec(&rigid_body.shape_size)?;
+    self.write.write_vec(&rigid_body.shape_position)?;
+    self.write.write_vec(&rigid_body.shape_rotation)?;
+    self.write.write_f32::<LE>(rigid_body.mass)?;
+    self.write.write_f32::<LE>(rigid_body.move_attenuation)?;
+    self.write.write_f32::<LE>(rigid_body.rotation_damping)?;
+    self.write.write_f32::<LE>(rigid_body.repulsion)?;
+    self.write.write_f32::<LE>(rigid_body.friction)?;
+    self.write.write_u8(rigid_body.physics_mode as u8)?;
+
+    self.remaining -= 1;
+    Ok(())
+  }
+}
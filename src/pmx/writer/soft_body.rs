@@ -0,0 +1,188 @@
+use crate::{
+  pmx::error::Section,
+  writer::{
+    counted::CountedWrite,
+    helpers::{checked_count, WriteHelpers},
+    JointWriter,
+  },
+  Config, Error, Result, Settings, SoftBody,
+};
+use byteorder::{WriteBytesExt, LE};
+use std::io::Write;
+
+pub struct SoftBodyWriter<W> {
+  pub version: f32,
+  pub settings: Settings,
+  pub count: i32,
+  pub remaining: i32,
+  pub(crate) write: CountedWrite<W>,
+  pub(crate) poison: bool,
+}
+
+impl<W: Write> SoftBodyWriter<W> {
+  pub fn new(mut j: JointWriter<W>, count: usize) -> Result<SoftBodyWriter<W>> {
+    if j.poison {
+      return Err(Error::Poisoned);
+    }
+    if j.remaining != 0 {
+      return Err(
+        Error::ElementCountMismatch {
+          section: Section::Joints,
+          expected: j.count,
+          written: j.count - j.remaining,
+        }
+        .at(Section::Joints, None, j.write.position),
+      );
+    }
+    // soft bodies were introduced in PMX 2.1, older files end after joints
+    let offset = j.write.position;
+    let count = checked_count(count)
+      .and_then(|count| {
+        if j.version >= 2.1 {
+          j.write.write_i32::<LE>(count)?;
+        } else if count != 0 {
+          return Err(Error::UnsupportedSection {
+            section: Section::SoftBodies,
+            version: j.version,
+          });
+        }
+        Ok(count)
+      })
+      .map_err(|e| e.at(Section::SoftBodies, None, offset))?;
+
+    Ok(SoftBodyWriter {
+      version: j.version,
+      settings: j.settings,
+      count,
+      remaining: count,
+      write: j.write,
+      poison: false,
+    })
+  }
+
+  pub fn write<C: Config>(&mut self, soft_body: &SoftBody<C>) -> Result<()> {
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    let element = (self.count - self.remaining) as usize;
+    let offset = self.write.position;
+    let result = self.write_impl(soft_body);
+    if result.is_err() {
+      self.poison = true;
+    }
+    result.map_err(|e| e.at(Section::SoftBodies, Some(element), offset))
+  }
+
+  fn write_impl<C: Config>(&mut self, soft_body: &SoftBody<C>) -> Result<()> {
+    if self.remaining <= 0 {
+      return Err(Error::ElementCountMismatch {
+        section: Section::SoftBodies,
+        expected: self.count,
+        written: self.count + 1,
+      });
+    }
+
+    let settings = self.settings;
+    self
+      .write
+      .write_text(settings.text_encoding, &soft_body.local_name)?;
+    self
+      .write
+      .write_text(settings.text_encoding, &soft_body.universal_name)?;
+    self.write.write_u8(soft_body.shape as u8)?;
+    self
+      .write
+      .write_index(settings.material_index_size, &soft_body.material_index)?;
+    self.write.write_u8(soft_body.group)?;
+    self.write.write_u16::<LE>(soft_body.non_collision_mask)?;
+    self
+      .write
+      .write_u8(soft_body.flags.bits() | soft_body.unknown_flags)?;
+    self.write.write_i32::<LE>(soft_body.b_link_distance)?;
+    self.write.write_i32::<LE>(soft_body.cluster_count)?;
+    self.write.write_f32::<LE>(soft_body.total_mass)?;
+    self.write.write_f32::<LE>(soft_body.collision_margin)?;
+    self.write.write_i32::<LE>(soft_body.aero_model as i32)?;
+
+    let c = &soft_body.coefficients;
+    for &value in &[
+      c.velocity_correction,
+      c.damping,
+      c.drag,
+      c.lift,
+      c.pressure,
+      c.volume_conservation,
+      c.dynamic_friction,
+      c.pose_matching,
+      c.rigid_contact_hardness,
+      c.kinetic_contact_hardness,
+      c.soft_contact_hardness,
+      c.anchor_hardness,
+    ] {
+      self.write.write_f32::<LE>(value)?;
+    }
+
+    let c = &soft_body.cluster;
+    for &value in &[
+      c.soft_rigid_hardness,
+      c.soft_kinetic_hardness,
+      c.soft_soft_hardness,
+      c.soft_rigid_impulse_split,
+      c.soft_kinetic_impulse_split,
+      c.soft_soft_impulse_split,
+    ] {
+      self.write.write_f32::<LE>(value)?;
+    }
+
+    let i = &soft_body.iterations;
+    for &value in &[i.velocity, i.position, i.drift, i.cluster] {
+      self.write.write_i32::<LE>(value)?;
+    }
+
+    let m = &soft_body.material;
+    for &value in &[m.linear_stiffness, m.area_stiffness, m.volume_stiffness] {
+      self.write.write_f32::<LE>(value)?;
+    }
+
+    self.write.write_count(soft_body.anchors.len())?;
+    for anchor in &soft_body.anchors {
+      self
+        .write
+        .write_index(settings.rigidbody_index_size, &anchor.rigid_body)?;
+      self
+        .write
+        .write_vertex_index(settings.vertex_index_size, &anchor.vertex)?;
+      self
+        .write
+        .write_flag(anchor.near_mode, anchor.raw_near_mode)?;
+    }
+
+    self.write.write_count(soft_body.pin_vertices.len())?;
+    for pin in &soft_body.pin_vertices {
+      self
+        .write
+        .write_vertex_index(settings.vertex_index_size, pin)?;
+    }
+
+    self.remaining -= 1;
+    Ok(())
+  }
+
+  pub fn finish(mut self) -> Result<W> {
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    if self.remaining != 0 {
+      return Err(
+        Error::ElementCountMismatch {
+          section: Section::SoftBodies,
+          expected: self.count,
+          written: self.count - self.remaining,
+        }
+        .at(Section::SoftBodies, None, self.write.position),
+      );
+    }
+    self.write.flush()?;
+    Ok(self.write.inner)
+  }
+}
@@ -0,0 +1,89 @@
+use crate::{
+  pmx::error::Section,
+  writer::{
+    counted::CountedWrite,
+    helpers::{checked_count, WriteHelpers},
+    VertexWriter,
+  },
+  Config, Error, Result, Settings,
+};
+use byteorder::{WriteBytesExt, LE};
+use std::io::Write;
+
+pub struct SurfaceWriter<W> {
+  pub version: f32,
+  pub settings: Settings,
+  pub count: i32,
+  pub remaining: i32,
+  pub(crate) write: CountedWrite<W>,
+  pub(crate) poison: bool,
+}
+
+impl<W: Write> SurfaceWriter<W> {
+  pub fn new(mut v: VertexWriter<W>, count: usize) -> Result<SurfaceWriter<W>> {
+    if v.poison {
+      return Err(Error::Poisoned);
+    }
+    if v.remaining != 0 {
+      return Err(
+        Error::ElementCountMismatch {
+          section: Section::Vertices,
+          expected: v.count,
+          written: v.count - v.remaining,
+        }
+        .at(Section::Vertices, None, v.write.position),
+      );
+    }
+    let offset = v.write.position;
+    let count = checked_count(count)
+      .and_then(|count| {
+        if count % 3 != 0 {
+          return Err(Error::InvalidSurfaceCount(count));
+        }
+        v.write.write_i32::<LE>(count)?;
+        Ok(count)
+      })
+      .map_err(|e| e.at(Section::Surfaces, None, offset))?;
+
+    Ok(SurfaceWriter {
+      version: v.version,
+      settings: v.settings,
+      count,
+      remaining: count,
+      write: v.write,
+      poison: false,
+    })
+  }
+
+  pub fn write<C: Config>(&mut self, surface: &[C::VertexIndex; 3]) -> Result<()> {
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    let element = (self.count - self.remaining) as usize;
+    let offset = self.write.position;
+    let result = self.write_impl::<C>(surface);
+    if result.is_err() {
+      self.poison = true;
+    }
+    result.map_err(|e| e.at(Section::Surfaces, Some(element), offset))
+  }
+
+  fn write_impl<C: Config>(&mut self, surface: &[C::VertexIndex; 3]) -> Result<()> {
+    if self.remaining <= 0 {
+      return Err(Error::ElementCountMismatch {
+        section: Section::Surfaces,
+        expected: self.count,
+        written: self.count + 3,
+      });
+    }
+
+    for index in surface {
+      self
+        .write
+        .write_vertex_index(self.settings.vertex_index_size, index)?;
+    }
+
+    self.remaining -= 3;
+    Ok(())
+  }
+}
@@ -0,0 +1,84 @@
+use crate::{
+  pmx::error::Section,
+  writer::{
+    counted::CountedWrite,
+    helpers::{checked_count, WriteHelpers},
+    SurfaceWriter,
+  },
+  Error, Result, Settings,
+};
+use byteorder::{WriteBytesExt, LE};
+use std::io::Write;
+
+pub struct TextureWriter<W> {
+  pub version: f32,
+  pub settings: Settings,
+  pub count: i32,
+  pub remaining: i32,
+  pub(crate) write: CountedWrite<W>,
+  pub(crate) poison: bool,
+}
+
+impl<W: Write> TextureWriter<W> {
+  pub fn new(mut s: SurfaceWriter<W>, count: usize) -> Result<TextureWriter<W>> {
+    if s.poison {
+      return Err(Error::Poisoned);
+    }
+    if s.remaining != 0 {
+      return Err(
+        Error::ElementCountMismatch {
+          section: Section::Surfaces,
+          expected: s.count,
+          written: s.count - s.remaining,
+        }
+        .at(Section::Surfaces, None, s.write.position),
+      );
+    }
+    let offset = s.write.position;
+    let count = checked_count(count)
+      .and_then(|count| {
+        s.write.write_i32::<LE>(count)?;
+        Ok(count)
+      })
+      .map_err(|e| e.at(Section::Textures, None, offset))?;
+
+    Ok(TextureWriter {
+      version: s.version,
+      settings: s.settings,
+      count,
+      remaining: count,
+      write: s.write,
+      poison: false,
+    })
+  }
+
+  pub fn write(&mut self, texture: &str) -> Result<()> {
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    let element = (self.count - self.remaining) as usize;
+    let offset = self.write.position;
+    let result = self.write_impl(texture);
+    if result.is_err() {
+      self.poison = true;
+    }
+    result.map_err(|e| e.at(Section::Textures, Some(element), offset))
+  }
+
+  fn write_impl(&mut self, texture: &str) -> Result<()> {
+    if self.remaining <= 0 {
+      return Err(Error::ElementCountMismatch {
+        section: Section::Textures,
+        expected: self.count,
+        written: self.count + 1,
+      });
+    }
+
+    self
+      .write
+      .write_text(self.settings.text_encoding, texture)?;
+
+    self.remaining -= 1;
+    Ok(())
+  }
+}
@@ -0,0 +1,130 @@
+use crate::{
+  pmx::error::Section,
+  pmx::weight_deform::*,
+  writer::{
+    counted::CountedWrite,
+    helpers::{checked_count, WriteHelpers},
+    HeaderWriter,
+  },
+  Config, Error, Result, Settings, Vertex,
+};
+use byteorder::{WriteBytesExt, LE};
+use std::io::Write;
+
+pub struct VertexWriter<W> {
+  pub version: f32,
+  pub settings: Settings,
+  pub count: i32,
+  pub remaining: i32,
+  pub(crate) write: CountedWrite<W>,
+  pub(crate) poison: bool,
+}
+
+impl<W: Write> VertexWriter<W> {
+  pub fn new(mut header: HeaderWriter<W>, count: usize) -> Result<VertexWriter<W>> {
+    header.write_header()?;
+    let offset = header.write.position;
+    let count = checked_count(count)
+      .and_then(|count| {
+        header.write.write_i32::<LE>(count)?;
+        Ok(count)
+      })
+      .map_err(|e| e.at(Section::Vertices, None, offset))?;
+
+    Ok(VertexWriter {
+      version: header.version,
+      settings: header.settings,
+      count,
+      remaining: count,
+      write: header.write,
+      poison: false,
+    })
+  }
+
+  pub fn write<C: Config>(&mut self, vertex: &Vertex<C>) -> Result<()> {
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    let element = (self.count - self.remaining) as usize;
+    let offset = self.write.position;
+    let result = self.write_impl(vertex);
+    if result.is_err() {
+      self.poison = true;
+    }
+    result.map_err(|e| e.at(Section::Vertices, Some(element), offset))
+  }
+
+  fn write_impl<C: Config>(&mut self, vertex: &Vertex<C>) -> Result<()> {
+    if self.remaining <= 0 {
+      return Err(Error::ElementCountMismatch {
+        section: Section::Vertices,
+        expected: self.count,
+        written: self.count + 1,
+      });
+    }
+
+    let additional = vertex.additional.as_ref();
+    if additional.len() != self.settings.additional_vec4_count as usize {
+      return Err(Error::AdditionalVec4CountMismatch {
+        expected: self.settings.additional_vec4_count,
+        found: additional.len(),
+      });
+    }
+
+    self.write.write_vec(&vertex.position)?;
+    self.write.write_vec(&vertex.normal)?;
+    self.write.write_vec(&vertex.uv)?;
+    for v in additional {
+      self.write.write_vec(v)?;
+    }
+
+    let bone_index_size = self.settings.bone_index_size;
+    match vertex.weight_deform {
+      WeightDeform::Bdef1(ref w) => {
+        self.write.write_u8(0)?;
+        self.write.write_index(bone_index_size, &w.bone_index)?;
+      }
+      WeightDeform::Bdef2(ref w) => {
+        self.write.write_u8(1)?;
+        self.write.write_index(bone_index_size, &w.bone_1_index)?;
+        self.write.write_index(bone_index_size, &w.bone_2_index)?;
+        self.write.write_f32::<LE>(w.bone_1_weight)?;
+      }
+      WeightDeform::Bdef4(ref w) => {
+        self.write.write_u8(2)?;
+        self.write.write_index(bone_index_size, &w.bone_1_index)?;
+        self.write.write_index(bone_index_size, &w.bone_2_index)?;
+        self.write.write_index(bone_index_size, &w.bone_3_index)?;
+        self.write.write_index(bone_index_size, &w.bone_4_index)?;
+        self.write.write_f32::<LE>(w.bone_1_weight)?;
+        self.write.write_f32::<LE>(w.bone_2_weight)?;
+        self.write.write_f32::<LE>(w.bone_3_weight)?;
+        self.write.write_f32::<LE>(w.bone_4_weight)?;
+      }
+      WeightDeform::Sdef(ref w) => {
+        self.write.write_u8(3)?;
+        self.write.write_index(bone_index_size, &w.bone_1_index)?;
+        self.write.write_index(bone_index_size, &w.bone_2_index)?;
+        self.write.write_f32::<LE>(w.bone_1_weight)?;
+        self.write.write_vec(&w.c)?;
+        self.write.write_vec(&w.r0)?;
+        self.write.write_vec(&w.r1)?;
+      }
+      WeightDeform::Qdef(ref w) => {
+        self.write.write_u8(4)?;
+        self.write.write_index(bone_index_size, &w.bone_1_index)?;
+        self.write.write_index(bone_index_size, &w.bone_2_index)?;
+        self.write.write_index(bone_index_size, &w.bone_3_index)?;
+        self.write.write_index(bone_index_size, &w.bone_4_index)?;
+        self.write.write_f32::<LE>(w.bone_1_weight)?;
+        self.write.write_f32::<LE>(w.bone_2_weight)?;
+        self.write.write_f32::<LE>(w.bone_3_weight)?;
+        self.write.write_f32::<LE>(w.bone_4_weight)?;
+      }
+    }
+    self.write.write_f32::<LE>(vertex.edge_scale)?;
+
+    self.remaining -= 1;
+    Ok(())
+  }
+}
@@ -0,0 +1,16 @@
+pub mod camera;
+pub mod interpolation;
+pub mod motion;
+pub mod reader;
+pub mod types;
+pub mod writer;
+
+pub use self::motion::Motion;
+pub use self::reader::{
+  BoneReader, CameraReader, HeaderReader, IkReader, LightReader, MorphReader, ShadowReader,
+};
+pub use self::types::*;
+pub use self::writer::{
+  BoneWriter, CameraWriter, HeaderWriter, IkWriter, LightWriter, MorphWriter, ShadowWriter,
+  TruncatedName,
+};
@@ -0,0 +1,85 @@
+use crate::{
+  vmd::interpolation::{CameraSample, CameraTrack},
+  Config,
+};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Projection {
+  // vertical field of view in radians
+  Perspective { fov: f32 },
+  // height of the view volume in world units
+  Orthographic { height: f32 },
+}
+
+// Where the camera is and how it looks, in MMD's left handed space with +y up and models facing -z
+#[derive(Clone, Debug, PartialEq)]
+pub struct CameraView<C: Config> {
+  pub eye: C::Vec3,
+  pub target: C::Vec3,
+  // quaternion turning camera space, +z forward and +y up, into the world
+  pub orientation: C::Vec4,
+  pub forward: C::Vec3,
+  pub up: C::Vec3,
+  pub projection: Projection,
+}
+
+impl<C: Config> CameraSample<C> {
+  // The stored angles are negated in this left handed space and applied yaw outermost, so the
+  // camera turns around world up first, then pitches and finally rolls around its view direction.
+  // The eye sits `distance` along the view direction from the target. The usual negative distance
+  // puts it in front of the target, a positive one past it, still facing the same way.
+  pub fn view(&self) -> CameraView<C> {
+    let r = self.rotation.as_ref();
+    let axis = |i: usize, angle: f32| {
+      let mut q = [0.0, 0.0, 0.0, (angle / 2.0).cos()];
+      q[i] = (angle / 2.0).sin();
+      q
+    };
+    let q = multiply(multiply(axis(1, -r[1]), axis(0, -r[0])), axis(2, -r[2]));
+    let forward = rotate(q, [0.0, 0.0, 1.0]);
+    let t = self.position.as_ref();
+    let eye = [0, 1, 2].map(|i| t[i] + forward[i] * self.distance);
+    let fov = self.fov.to_radians();
+
+    CameraView {
+      eye: eye.into(),
+      target: self.position.clone(),
+      orientation: q.into(),
+      forward: forward.into(),
+      up: rotate(q, [0.0, 1.0, 0.0]).into(),
+      projection: if self.perspective {
+        Projection::Perspective { fov }
+      } else {
+        // sized so the target plane looks the same as in perspective
+        Projection::Orthographic {
+          height: 2.0 * self.distance.abs() * (fov / 2.0).tan(),
+        }
+      },
+    }
+  }
+}
+
+impl<C: Config> CameraTrack<'_, C> {
+  pub fn view(&self, frame: f32) -> Option<CameraView<C>> {
+    self.sample(frame).map(|sample| sample.view())
+  }
+}
+
+fn multiply(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+  let [ax, ay, az, aw] = a;
+  let [bx, by, bz, bw] = b;
+  [
+    aw * bx + ax * bw + ay * bz - az * by,
+    aw * by - ax * bz + ay * bw + az * bx,
+    aw * bz + ax * by - ay * bx + az * bw,
+    aw * bw - ax * bx - ay * by - az * bz,
+  ]
+}
+
+fn rotate(q: [f32; 4], v: [f32; 3]) -> [f32; 3] {
+  let p = multiply(
+    multiply(q, [v[0], v[1], v[2], 0.0]),
+    [-q[0], -q[1], -q[2], q[3]],
+  );
+  [p[0], p[1], p[2]]
+}
@@ -0,0 +1,298 @@
+use crate::{
+  vmd::{BoneKeyframe, CameraKeyframe, MorphKeyframe},
+  Config,
+};
+use std::collections::HashMap;
+
+// One channel's cubic Bezier from (0, 0) to (1, 1), control points scaled down from 0..=127
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Bezier {
+  pub x1: f32,
+  pub y1: f32,
+  pub x2: f32,
+  pub y2: f32,
+}
+
+impl Bezier {
+  pub fn new(points: [u8; 4]) -> Bezier {
+    let [x1, y1, x2, y2] = points.map(|p| p.min(127) as f32 / 127.0);
+    Bezier { x1, y1, x2, y2 }
+  }
+
+  // control points on the diagonal make the curve the identity
+  pub fn is_linear(&self) -> bool {
+    self.x1 == self.y1 && self.x2 == self.y2
+  }
+
+  // The progress for a fraction x of the time between two keyframes. x(t) is monotonic, so t is
+  // found by Newton steps kept inside a shrinking bracket, falling back to bisection.
+  pub fn evaluate(&self, x: f32) -> f32 {
+    let x = x.clamp(0.0, 1.0) as f64;
+    if self.is_linear() {
+      return x as f32;
+    }
+    let (x1, x2) = (self.x1 as f64, self.x2 as f64);
+    let (mut lo, mut hi, mut t) = (0.0, 1.0, x);
+    for _ in 0..32 {
+      let error = cubic(x1, x2, t) - x;
+      if error.abs() < 1e-9 {
+        break;
+      }
+      if error > 0.0 {
+        hi = t;
+      } else {
+        lo = t;
+      }
+      let slope = cubic_slope(x1, x2, t);
+      let newton = t - error / slope;
+      t = if slope > 1e-9 && newton > lo && newton < hi {
+        newton
+      } else {
+        (lo + hi) / 2.0
+      };
+    }
+    cubic(self.y1 as f64, self.y2 as f64, t) as f32
+  }
+}
+
+fn cubic(p1: f64, p2: f64, t: f64) -> f64 {
+  let s = 1.0 - t;
+  3.0 * s * s * t * p1 + 3.0 * s * t * t * p2 + t * t * t
+}
+
+fn cubic_slope(p1: f64, p2: f64, t: f64) -> f64 {
+  let s = 1.0 - t;
+  3.0 * s * s * p1 + 6.0 * s * t * (p2 - p1) + 3.0 * t * t * (1.0 - p2)
+}
+
+// Spherical interpolation along the shorter arc of quaternions given as x, y, z, w
+pub fn slerp(a: [f32; 4], b: [f32; 4], t: f32) -> [f32; 4] {
+  let mut dot: f32 = a.iter().zip(&b).map(|(a, b)| a * b).sum();
+  let mut b = b;
+  if dot < 0.0 {
+    dot = -dot;
+    b = b.map(|v| -v);
+  }
+  let (wa, wb) = if dot > 0.9995 {
+    // nearly parallel, lerp is accurate and avoids dividing by a tiny sine
+    (1.0 - t, t)
+  } else {
+    let theta = dot.acos();
+    let sin = theta.sin();
+    (((1.0 - t) * theta).sin() / sin, (t * theta).sin() / sin)
+  };
+  let q = [0, 1, 2, 3].map(|i| wa * a[i] + wb * b[i]);
+  let length = q.iter().map(|v| v * v).sum::<f32>().sqrt();
+  q.map(|v| v / length)
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+  a + (b - a) * t
+}
+
+fn vec3<V: AsRef<[f32]>>(v: &V) -> [f32; 3] {
+  let v = v.as_ref();
+  [v[0], v[1], v[2]]
+}
+
+fn vec4<V: AsRef<[f32]>>(v: &V) -> [f32; 4] {
+  let v = v.as_ref();
+  [v[0], v[1], v[2], v[3]]
+}
+
+enum Segment<'a, K> {
+  At(&'a K),
+  Between(&'a K, &'a K, f32),
+}
+
+// Holds the first and last keyframes outside the track, the curves of the later keyframe shape
+// the way from the earlier one
+fn segment<'a, K>(
+  keyframes: &[&'a K],
+  frame: f32,
+  key_frame: fn(&K) -> u32,
+) -> Option<Segment<'a, K>> {
+  let next = keyframes.partition_point(|k| key_frame(k) as f32 <= frame);
+  match (
+    next.checked_sub(1).map(|i| keyframes[i]),
+    keyframes.get(next).copied(),
+  ) {
+    (None, first) => first.map(Segment::At),
+    (Some(last), None) => Some(Segment::At(last)),
+    (Some(prev), Some(next)) => {
+      let (start, end) = (key_frame(prev) as f32, key_frame(next) as f32);
+      Some(Segment::Between(
+        prev,
+        next,
+        (frame - start) / (end - start),
+      ))
+    }
+  }
+}
+
+fn sorted<'a, K>(
+  keyframes: impl IntoIterator<Item = &'a K>,
+  key_frame: fn(&K) -> u32,
+) -> Vec<&'a K> {
+  let mut keyframes: Vec<_> = keyframes.into_iter().collect();
+  keyframes.sort_by_key(|k| key_frame(k));
+  keyframes
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoneSample<C: Config> {
+  pub translation: C::Vec3,
+  pub rotation: C::Vec4,
+}
+
+// The keyframes of a single bone, sorted by frame
+#[derive(Clone, Debug)]
+pub struct BoneTrack<'a, C: Config> {
+  keyframes: Vec<&'a BoneKeyframe<C>>,
+}
+
+impl<'a, C: Config> BoneTrack<'a, C> {
+  pub fn new(keyframes: impl IntoIterator<Item = &'a BoneKeyframe<C>>) -> BoneTrack<'a, C> {
+    BoneTrack {
+      keyframes: sorted(keyframes, |k| k.frame),
+    }
+  }
+
+  pub fn keyframes(&self) -> &[&'a BoneKeyframe<C>] {
+    &self.keyframes
+  }
+
+  pub fn sample(&self, frame: f32) -> Option<BoneSample<C>> {
+    Some(match segment(&self.keyframes, frame, |k| k.frame)? {
+      Segment::At(k) => BoneSample {
+        translation: k.translation.clone(),
+        rotation: k.rotation.clone(),
+      },
+      Segment::Between(prev, next, x) => {
+        let [cx, cy, cz, cr] = next.curves().map(|c| Bezier::new(c).evaluate(x));
+        let (a, b) = (vec3(&prev.translation), vec3(&next.translation));
+        BoneSample {
+          translation: [
+            lerp(a[0], b[0], cx),
+            lerp(a[1], b[1], cy),
+            lerp(a[2], b[2], cz),
+          ]
+          .into(),
+          rotation: slerp(vec4(&prev.rotation), vec4(&next.rotation), cr).into(),
+        }
+      }
+    })
+  }
+}
+
+pub fn bone_tracks<C: Config>(keyframes: &[BoneKeyframe<C>]) -> HashMap<&str, BoneTrack<'_, C>> {
+  let mut grouped: HashMap<&str, Vec<_>> = HashMap::new();
+  for keyframe in keyframes {
+    grouped
+      .entry(&keyframe.bone_name)
+      .or_default()
+      .push(keyframe);
+  }
+  grouped
+    .into_iter()
+    .map(|(name, keyframes)| (name, BoneTrack::new(keyframes)))
+    .collect()
+}
+
+// Morph weights have no curves and are interpolated linearly
+#[derive(Clone, Debug)]
+pub struct MorphTrack<'a> {
+  keyframes: Vec<&'a MorphKeyframe>,
+}
+
+impl<'a> MorphTrack<'a> {
+  pub fn new(keyframes: impl IntoIterator<Item = &'a MorphKeyframe>) -> MorphTrack<'a> {
+    MorphTrack {
+      keyframes: sorted(keyframes, |k| k.frame),
+    }
+  }
+
+  pub fn keyframes(&self) -> &[&'a MorphKeyframe] {
+    &self.keyframes
+  }
+
+  pub fn sample(&self, frame: f32) -> Option<f32> {
+    Some(match segment(&self.keyframes, frame, |k| k.frame)? {
+      Segment::At(k) => k.weight,
+      Segment::Between(prev, next, x) => lerp(prev.weight, next.weight, x),
+    })
+  }
+}
+
+pub fn morph_tracks(keyframes: &[MorphKeyframe]) -> HashMap<&str, MorphTrack<'_>> {
+  let mut grouped: HashMap<&str, Vec<_>> = HashMap::new();
+  for keyframe in keyframes {
+    grouped
+      .entry(&keyframe.morph_name)
+      .or_default()
+      .push(keyframe);
+  }
+  grouped
+    .into_iter()
+    .map(|(name, keyframes)| (name, MorphTrack::new(keyframes)))
+    .collect()
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CameraSample<C: Config> {
+  pub distance: f32,
+  pub position: C::Vec3,
+  // euler angles in radians, interpolated per component rather than slerped
+  pub rotation: C::Vec3,
+  pub fov: f32,
+  pub perspective: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct CameraTrack<'a, C: Config> {
+  keyframes: Vec<&'a CameraKeyframe<C>>,
+}
+
+impl<'a, C: Config> CameraTrack<'a, C> {
+  pub fn new(keyframes: impl IntoIterator<Item = &'a CameraKeyframe<C>>) -> CameraTrack<'a, C> {
+    CameraTrack {
+      keyframes: sorted(keyframes, |k| k.frame),
+    }
+  }
+
+  pub fn keyframes(&self) -> &[&'a CameraKeyframe<C>] {
+    &self.keyframes
+  }
+
+  pub fn sample(&self, frame: f32) -> Option<CameraSample<C>> {
+    let at = |k: &CameraKeyframe<C>| CameraSample {
+      distance: k.distance,
+      position: k.position.clone(),
+      rotation: k.rotation.clone(),
+      fov: k.fov as f32,
+      perspective: k.perspective,
+    };
+    Some(match segment(&self.keyframes, frame, |k| k.frame)? {
+      Segment::At(k) => at(k),
+      // keyframes on adjacent frames are a cut, the camera jumps instead of moving
+      Segment::Between(prev, next, _) if next.frame - prev.frame <= 1 => at(prev),
+      Segment::Between(prev, next, x) => {
+        let [cx, cy, cz, cr, cd, cf] = next.curves().map(|c| Bezier::new(c).evaluate(x));
+        let (a, b) = (vec3(&prev.position), vec3(&next.position));
+        let (ra, rb) = (vec3(&prev.rotation), vec3(&next.rotation));
+        CameraSample {
+          distance: lerp(prev.distance, next.distance, cd),
+          position: [
+            lerp(a[0], b[0], cx),
+            lerp(a[1], b[1], cy),
+            lerp(a[2], b[2], cz),
+          ]
+          .into(),
+          rotation: [0, 1, 2].map(|i| lerp(ra[i], rb[i], cr)).into(),
+          fov: lerp(prev.fov as f32, next.fov as f32, cf),
+          perspective: prev.perspective,
+        }
+      }
+    })
+  }
+}
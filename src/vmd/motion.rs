@@ -0,0 +1,109 @@
+use crate::{
+  vmd::{reader::*, types::*, writer::*},
+  Config, ParseLimits, Result,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, Read, Write};
+use std::path::Path;
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(bound(
+    serialize = "C::Vec3: Serialize, C::Vec4: Serialize",
+    deserialize = "C::Vec3: Deserialize<'de>, C::Vec4: Deserialize<'de>"
+  ))
+)]
+pub struct Motion<C: Config> {
+  pub model_name: String,
+  pub bones: Vec<BoneKeyframe<C>>,
+  pub morphs: Vec<MorphKeyframe>,
+  pub cameras: Vec<CameraKeyframe<C>>,
+  pub lights: Vec<LightKeyframe<C>>,
+  pub shadows: Vec<ShadowKeyframe>,
+  pub iks: Vec<IkKeyframe>,
+}
+
+impl<C: Config> Motion<C> {
+  pub fn open<P: AsRef<Path>>(path: P) -> Result<Motion<C>> {
+    Self::read(BufReader::new(File::open(path)?))
+  }
+
+  pub fn read<R: Read>(read: R) -> Result<Motion<C>> {
+    Self::read_with_limits(read, ParseLimits::default())
+  }
+
+  pub fn read_with_limits<R: Read>(read: R, limits: ParseLimits) -> Result<Motion<C>> {
+    let header = HeaderReader::with_limits(read, limits)?;
+    let model_name = header.model_name.clone();
+
+    let mut bones = BoneReader::new(header)?;
+    let bone_list = bones.iter::<C>().collect::<Result<_>>()?;
+
+    let mut morphs = MorphReader::new(bones)?;
+    let morph_list = morphs.iter().collect::<Result<_>>()?;
+
+    let mut cameras = CameraReader::new(morphs)?;
+    let camera_list = cameras.iter::<C>().collect::<Result<_>>()?;
+
+    let mut lights = LightReader::new(cameras)?;
+    let light_list = lights.iter::<C>().collect::<Result<_>>()?;
+
+    let mut shadows = ShadowReader::new(lights)?;
+    let shadow_list = shadows.iter().collect::<Result<_>>()?;
+
+    let mut iks = IkReader::new(shadows)?;
+    let ik_list = iks.iter().collect::<Result<_>>()?;
+
+    Ok(Motion {
+      model_name,
+      bones: bone_list,
+      morphs: morph_list,
+      cameras: camera_list,
+      lights: light_list,
+      shadows: shadow_list,
+      iks: ik_list,
+    })
+  }
+
+  // Names that do not fit their fields are truncated and reported alongside the writer
+  pub fn write<W: Write>(&self, write: W) -> Result<(W, Vec<TruncatedName>)> {
+    let mut header = HeaderWriter::new(write);
+    header.model_name = self.model_name.clone();
+
+    let mut bones = BoneWriter::new(header, self.bones.len())?;
+    for bone in &self.bones {
+      bones.write(bone)?;
+    }
+
+    let mut morphs = MorphWriter::new(bones, self.morphs.len())?;
+    for morph in &self.morphs {
+      morphs.write(morph)?;
+    }
+
+    let mut cameras = CameraWriter::new(morphs, self.cameras.len())?;
+    for camera in &self.cameras {
+      cameras.write(camera)?;
+    }
+
+    let mut lights = LightWriter::new(cameras, self.lights.len())?;
+    for light in &self.lights {
+      lights.write(light)?;
+    }
+
+    let mut shadows = ShadowWriter::new(lights, self.shadows.len())?;
+    for shadow in &self.shadows {
+      shadows.write(shadow)?;
+    }
+
+    let mut iks = IkWriter::new(shadows, self.iks.len())?;
+    for ik in &self.iks {
+      iks.write(ik)?;
+    }
+
+    iks.finish()
+  }
+}
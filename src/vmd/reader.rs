@@ -0,0 +1,15 @@
+pub mod bone;
+pub mod camera;
+pub mod header;
+pub mod ik;
+pub mod light;
+pub mod morph;
+pub mod shadow;
+
+pub use bone::BoneReader;
+pub use camera::CameraReader;
+pub use header::HeaderReader;
+pub use ik::IkReader;
+pub use light::LightReader;
+pub use morph::MorphReader;
+pub use shadow::ShadowReader;
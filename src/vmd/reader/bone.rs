@@ -0,0 +1,64 @@
+use crate::{
+  pmd::reader::{element_reader, PmdReadHelpers},
+  pmx::error::Section,
+  reader::{helpers::ReadHelpers, limited::LimitedRead},
+  vmd::reader::HeaderReader,
+  vmd::BoneKeyframe,
+  Config, Result,
+};
+use byteorder::{ReadBytesExt, LE};
+use std::io::Read;
+
+pub struct BoneReader<R> {
+  pub count: i32,
+  pub remaining: i32,
+  pub(crate) read: LimitedRead<R>,
+  pub(crate) poison: bool,
+}
+
+impl<R: Read> BoneReader<R> {
+  pub fn new(mut h: HeaderReader<R>) -> Result<BoneReader<R>> {
+    let offset = h.read.position;
+    let count = h
+      .read
+      .read_count()
+      .map_err(|e| e.at(Section::BoneKeyframes, None, offset))?;
+
+    Ok(BoneReader {
+      count,
+      remaining: count,
+      read: h.read,
+      poison: false,
+    })
+  }
+
+  fn next_impl<C: Config>(&mut self) -> Result<Option<BoneKeyframe<C>>> {
+    if self.remaining <= 0 {
+      return Ok(None);
+    }
+    let bone_name = self.read.read_name(15)?;
+    let frame = self.read.read_u32::<LE>()?;
+    let translation = self.read.read_vec3::<C>()?;
+    let rotation = self.read.read_vec4::<C>()?;
+    let mut interpolation = [[0u8; 16]; 4];
+    for row in &mut interpolation {
+      self.read.read_exact(row)?;
+    }
+
+    self.remaining -= 1;
+    Ok(Some(BoneKeyframe {
+      bone_name,
+      frame,
+      translation,
+      rotation,
+      interpolation,
+    }))
+  }
+}
+
+element_reader!(
+  BoneReader,
+  BoneIterator,
+  Section::BoneKeyframes,
+  BoneKeyframe<C>
+);
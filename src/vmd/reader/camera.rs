@@ -0,0 +1,74 @@
+use crate::{
+  pmd::reader::{element_reader, PmdReadHelpers},
+  pmx::error::Section,
+  reader::{helpers::ReadHelpers, limited::LimitedRead},
+  vmd::reader::MorphReader,
+  vmd::CameraKeyframe,
+  Config, Error, Result,
+};
+use byteorder::{ReadBytesExt, LE};
+use std::io::Read;
+
+// count is 0 when the file ends after the morph keyframes
+pub struct CameraReader<R> {
+  pub count: i32,
+  pub remaining: i32,
+  pub(crate) read: LimitedRead<R>,
+  pub(crate) poison: bool,
+}
+
+impl<R: Read> CameraReader<R> {
+  pub fn new(mut m: MorphReader<R>) -> Result<CameraReader<R>> {
+    if m.poison {
+      return Err(Error::Poisoned);
+    }
+    while m.remaining > 0 {
+      m.next()?;
+    }
+    let offset = m.read.position;
+    let count = m
+      .read
+      .read_optional_count()
+      .map_err(|e| e.at(Section::CameraKeyframes, None, offset))?;
+
+    Ok(CameraReader {
+      count,
+      remaining: count,
+      read: m.read,
+      poison: false,
+    })
+  }
+
+  fn next_impl<C: Config>(&mut self) -> Result<Option<CameraKeyframe<C>>> {
+    if self.remaining <= 0 {
+      return Ok(None);
+    }
+    let frame = self.read.read_u32::<LE>()?;
+    let distance = self.read.read_f32::<LE>()?;
+    let position = self.read.read_vec3::<C>()?;
+    let rotation = self.read.read_vec3::<C>()?;
+    let mut interpolation = [0u8; 24];
+    self.read.read_exact(&mut interpolation)?;
+    let fov = self.read.read_u32::<LE>()?;
+    // the flag is set when perspective is turned off
+    let perspective = self.read.read_u8()? == 0;
+
+    self.remaining -= 1;
+    Ok(Some(CameraKeyframe {
+      frame,
+      distance,
+      position,
+      rotation,
+      interpolation,
+      fov,
+      perspective,
+    }))
+  }
+}
+
+element_reader!(
+  CameraReader,
+  CameraIterator,
+  Section::CameraKeyframes,
+  CameraKeyframe<C>
+);
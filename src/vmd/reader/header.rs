@@ -0,0 +1,46 @@
+use crate::{
+  pmd::reader::PmdReadHelpers, pmx::error::Section, reader::limited::LimitedRead, Error,
+  ParseLimits, Result,
+};
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+
+pub struct HeaderReader<R> {
+  // 1 for the early "Vocaloid Motion Data file" with 10 byte model names, 2 otherwise
+  pub version: u8,
+  pub model_name: String,
+  pub(crate) read: LimitedRead<R>,
+}
+
+impl<R: Read> HeaderReader<R> {
+  pub fn new(read: R) -> Result<HeaderReader<R>> {
+    Self::with_limits(read, ParseLimits::default())
+  }
+
+  pub fn with_limits(read: R, limits: ParseLimits) -> Result<HeaderReader<R>> {
+    Self::read_header(LimitedRead::new(read, limits)).map_err(|e| e.at(Section::Header, None, 0))
+  }
+
+  fn read_header(mut read: LimitedRead<R>) -> Result<HeaderReader<R>> {
+    let mut magic = [0u8; 30];
+    read.read_exact(&mut magic)?;
+    let end = magic.iter().position(|&b| b == 0).unwrap_or(magic.len());
+    let (version, name_size) = match &magic[..end] {
+      b"Vocaloid Motion Data 0002" => (2, 20),
+      b"Vocaloid Motion Data file" => (1, 10),
+      _ => return Err(Error::WrongVmdSignature(magic)),
+    };
+
+    Ok(HeaderReader {
+      version,
+      model_name: read.read_name(name_size)?,
+      read,
+    })
+  }
+}
+
+impl<R> Display for HeaderReader<R> {
+  fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    writeln!(f, "version: {}, model: {}", self.version, self.model_name)
+  }
+}
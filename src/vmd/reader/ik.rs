@@ -0,0 +1,74 @@
+use crate::{
+  pmd::reader::{element_reader, PmdReadHelpers},
+  pmx::error::Section,
+  reader::{
+    helpers::{bounded_capacity, ReadHelpers},
+    limited::LimitedRead,
+  },
+  vmd::reader::ShadowReader,
+  vmd::{IkEnabled, IkKeyframe},
+  Error, Result,
+};
+use byteorder::{ReadBytesExt, LE};
+use std::io::Read;
+
+// Model visibility and ik switches, count is 0 when the file ends before them
+pub struct IkReader<R> {
+  pub count: i32,
+  pub remaining: i32,
+  pub(crate) read: LimitedRead<R>,
+  pub(crate) poison: bool,
+}
+
+impl<R: Read> IkReader<R> {
+  pub fn new(mut s: ShadowReader<R>) -> Result<IkReader<R>> {
+    if s.poison {
+      return Err(Error::Poisoned);
+    }
+    while s.remaining > 0 {
+      s.next()?;
+    }
+    let offset = s.read.position;
+    let count = s
+      .read
+      .read_optional_count()
+      .map_err(|e| e.at(Section::IkKeyframes, None, offset))?;
+
+    Ok(IkReader {
+      count,
+      remaining: count,
+      read: s.read,
+      poison: false,
+    })
+  }
+
+  fn next_impl(&mut self) -> Result<Option<IkKeyframe>> {
+    if self.remaining <= 0 {
+      return Ok(None);
+    }
+    let frame = self.read.read_u32::<LE>()?;
+    let show = self.read.read_u8()? != 0;
+    let count = self.read.read_count()?;
+    self
+      .read
+      .check_limit("ik links", count as usize, self.read.limits().max_ik_links)?;
+    let mut iks = Vec::with_capacity(bounded_capacity(count as usize));
+    for _ in 0..count {
+      iks.push(IkEnabled {
+        bone_name: self.read.read_name(20)?,
+        enabled: self.read.read_u8()? != 0,
+      });
+    }
+
+    self.remaining -= 1;
+    Ok(Some(IkKeyframe { frame, show, iks }))
+  }
+}
+
+element_reader!(
+  IkReader,
+  IkIterator,
+  Section::IkKeyframes,
+  IkKeyframe,
+  plain
+);
@@ -0,0 +1,62 @@
+use crate::{
+  pmd::reader::{element_reader, PmdReadHelpers},
+  pmx::error::Section,
+  reader::{helpers::ReadHelpers, limited::LimitedRead},
+  vmd::reader::CameraReader,
+  vmd::LightKeyframe,
+  Config, DefaultConfig, Error, Result,
+};
+use byteorder::{ReadBytesExt, LE};
+use std::io::Read;
+
+// count is 0 when the file ends before the light keyframes
+pub struct LightReader<R> {
+  pub count: i32,
+  pub remaining: i32,
+  pub(crate) read: LimitedRead<R>,
+  pub(crate) poison: bool,
+}
+
+impl<R: Read> LightReader<R> {
+  pub fn new(mut c: CameraReader<R>) -> Result<LightReader<R>> {
+    if c.poison {
+      return Err(Error::Poisoned);
+    }
+    while c.remaining > 0 {
+      c.next::<DefaultConfig>()?;
+    }
+    let offset = c.read.position;
+    let count = c
+      .read
+      .read_optional_count()
+      .map_err(|e| e.at(Section::LightKeyframes, None, offset))?;
+
+    Ok(LightReader {
+      count,
+      remaining: count,
+      read: c.read,
+      poison: false,
+    })
+  }
+
+  fn next_impl<C: Config>(&mut self) -> Result<Option<LightKeyframe<C>>> {
+    if self.remaining <= 0 {
+      return Ok(None);
+    }
+    let keyframe = LightKeyframe {
+      frame: self.read.read_u32::<LE>()?,
+      color: self.read.read_vec3::<C>()?,
+      direction: self.read.read_vec3::<C>()?,
+    };
+
+    self.remaining -= 1;
+    Ok(Some(keyframe))
+  }
+}
+
+element_reader!(
+  LightReader,
+  LightIterator,
+  Section::LightKeyframes,
+  LightKeyframe<C>
+);
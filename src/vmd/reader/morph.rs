@@ -0,0 +1,62 @@
+use crate::{
+  pmd::reader::{element_reader, PmdReadHelpers},
+  pmx::error::Section,
+  reader::{helpers::ReadHelpers, limited::LimitedRead},
+  vmd::reader::BoneReader,
+  vmd::MorphKeyframe,
+  DefaultConfig, Error, Result,
+};
+use byteorder::{ReadBytesExt, LE};
+use std::io::Read;
+
+pub struct MorphReader<R> {
+  pub count: i32,
+  pub remaining: i32,
+  pub(crate) read: LimitedRead<R>,
+  pub(crate) poison: bool,
+}
+
+impl<R: Read> MorphReader<R> {
+  pub fn new(mut b: BoneReader<R>) -> Result<MorphReader<R>> {
+    if b.poison {
+      return Err(Error::Poisoned);
+    }
+    while b.remaining > 0 {
+      b.next::<DefaultConfig>()?;
+    }
+    let offset = b.read.position;
+    let count = b
+      .read
+      .read_count()
+      .map_err(|e| e.at(Section::MorphKeyframes, None, offset))?;
+
+    Ok(MorphReader {
+      count,
+      remaining: count,
+      read: b.read,
+      poison: false,
+    })
+  }
+
+  fn next_impl(&mut self) -> Result<Option<MorphKeyframe>> {
+    if self.remaining <= 0 {
+      return Ok(None);
+    }
+    let keyframe = MorphKeyframe {
+      morph_name: self.read.read_name(15)?,
+      frame: self.read.read_u32::<LE>()?,
+      weight: self.read.read_f32::<LE>()?,
+    };
+
+    self.remaining -= 1;
+    Ok(Some(keyframe))
+  }
+}
+
+element_reader!(
+  MorphReader,
+  MorphIterator,
+  Section::MorphKeyframes,
+  MorphKeyframe,
+  plain
+);
@@ -0,0 +1,63 @@
+use crate::{
+  pmd::reader::{element_reader, PmdReadHelpers},
+  pmx::error::Section,
+  reader::limited::LimitedRead,
+  vmd::reader::LightReader,
+  vmd::{ShadowKeyframe, ShadowMode},
+  DefaultConfig, Error, Result,
+};
+use byteorder::{ReadBytesExt, LE};
+use std::io::Read;
+
+// count is 0 when the file ends before the self shadow keyframes
+pub struct ShadowReader<R> {
+  pub count: i32,
+  pub remaining: i32,
+  pub(crate) read: LimitedRead<R>,
+  pub(crate) poison: bool,
+}
+
+impl<R: Read> ShadowReader<R> {
+  pub fn new(mut l: LightReader<R>) -> Result<ShadowReader<R>> {
+    if l.poison {
+      return Err(Error::Poisoned);
+    }
+    while l.remaining > 0 {
+      l.next::<DefaultConfig>()?;
+    }
+    let offset = l.read.position;
+    let count = l
+      .read
+      .read_optional_count()
+      .map_err(|e| e.at(Section::ShadowKeyframes, None, offset))?;
+
+    Ok(ShadowReader {
+      count,
+      remaining: count,
+      read: l.read,
+      poison: false,
+    })
+  }
+
+  fn next_impl(&mut self) -> Result<Option<ShadowKeyframe>> {
+    if self.remaining <= 0 {
+      return Ok(None);
+    }
+    let keyframe = ShadowKeyframe {
+      frame: self.read.read_u32::<LE>()?,
+      mode: ShadowMode::from(self.read.read_u8()?),
+      distance: self.read.read_f32::<LE>()?,
+    };
+
+    self.remaining -= 1;
+    Ok(Some(keyframe))
+  }
+}
+
+element_reader!(
+  ShadowReader,
+  ShadowIterator,
+  Section::ShadowKeyframes,
+  ShadowKeyframe,
+  plain
+);
@@ -0,0 +1,181 @@
+use crate::Config;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(bound(
+    serialize = "C::Vec3: Serialize, C::Vec4: Serialize",
+    deserialize = "C::Vec3: Deserialize<'de>, C::Vec4: Deserialize<'de>"
+  ))
+)]
+pub struct BoneKeyframe<C: Config> {
+  pub bone_name: String,
+  pub frame: u32,
+  pub translation: C::Vec3,
+  // quaternion as x, y, z, w
+  pub rotation: C::Vec4,
+  // the 64 byte block as stored, four copies of the same 16 control points shifted by one byte
+  pub interpolation: [[u8; 16]; 4],
+}
+
+// Bezier control points x1, y1, x2, y2 in 0..=127 of the curve MMD uses for new keyframes
+pub const LINEAR_CURVE: [u8; 4] = [20, 20, 107, 107];
+
+impl<C: Config> BoneKeyframe<C> {
+  // x1, y1, x2, y2 of the translation x, y, z and rotation curves
+  pub fn curves(&self) -> [[u8; 4]; 4] {
+    let mut curves = [[0; 4]; 4];
+    for (curve, row) in curves.iter_mut().zip(&self.interpolation) {
+      *curve = [row[0], row[4], row[8], row[12]];
+    }
+    curves
+  }
+
+  // MMD interleaves the points of the four curves and repeats them one byte further per row
+  pub fn set_curves(&mut self, curves: [[u8; 4]; 4]) {
+    let mut points = [0u8; 19];
+    for (i, curve) in curves.iter().enumerate() {
+      for (j, &point) in curve.iter().enumerate() {
+        points[j * 4 + i] = point;
+      }
+    }
+    for (shift, row) in self.interpolation.iter_mut().enumerate() {
+      row.copy_from_slice(&points[shift..shift + 16]);
+    }
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MorphKeyframe {
+  pub morph_name: String,
+  pub frame: u32,
+  pub weight: f32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(bound(
+    serialize = "C::Vec3: Serialize",
+    deserialize = "C::Vec3: Deserialize<'de>"
+  ))
+)]
+pub struct CameraKeyframe<C: Config> {
+  pub frame: u32,
+  // negative when the eye is in front of the target
+  pub distance: f32,
+  // the point the camera looks at
+  pub position: C::Vec3,
+  // euler angles in radians
+  pub rotation: C::Vec3,
+  // x1, x2, y1, y2 for position x, y, z, rotation, distance and field of view
+  pub interpolation: [u8; 24],
+  // vertical, in degrees
+  pub fov: u32,
+  pub perspective: bool,
+}
+
+impl<C: Config> CameraKeyframe<C> {
+  // x1, y1, x2, y2 of the position x, y, z, rotation, distance and field of view curves
+  pub fn curves(&self) -> [[u8; 4]; 6] {
+    let mut curves = [[0; 4]; 6];
+    for (curve, stored) in curves.iter_mut().zip(self.interpolation.chunks_exact(4)) {
+      *curve = [stored[0], stored[2], stored[1], stored[3]];
+    }
+    curves
+  }
+
+  pub fn set_curves(&mut self, curves: [[u8; 4]; 6]) {
+    for (curve, stored) in curves.iter().zip(self.interpolation.chunks_exact_mut(4)) {
+      stored.copy_from_slice(&[curve[0], curve[2], curve[1], curve[3]]);
+    }
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(bound(
+    serialize = "C::Vec3: Serialize",
+    deserialize = "C::Vec3: Deserialize<'de>"
+  ))
+)]
+pub struct LightKeyframe<C: Config> {
+  pub frame: u32,
+  pub color: C::Vec3,
+  pub direction: C::Vec3,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ShadowMode {
+  Off,
+  Mode1,
+  Mode2,
+  Unknown(u8),
+}
+
+impl Display for ShadowMode {
+  fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+    match self {
+      ShadowMode::Off => write!(f, "off"),
+      ShadowMode::Mode1 => write!(f, "mode 1"),
+      ShadowMode::Mode2 => write!(f, "mode 2"),
+      ShadowMode::Unknown(mode) => write!(f, "unknown({})", mode),
+    }
+  }
+}
+
+impl From<u8> for ShadowMode {
+  fn from(value: u8) -> Self {
+    match value {
+      0 => ShadowMode::Off,
+      1 => ShadowMode::Mode1,
+      2 => ShadowMode::Mode2,
+      mode => ShadowMode::Unknown(mode),
+    }
+  }
+}
+
+impl From<ShadowMode> for u8 {
+  fn from(value: ShadowMode) -> Self {
+    match value {
+      ShadowMode::Off => 0,
+      ShadowMode::Mode1 => 1,
+      ShadowMode::Mode2 => 2,
+      ShadowMode::Unknown(mode) => mode,
+    }
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ShadowKeyframe {
+  pub frame: u32,
+  pub mode: ShadowMode,
+  // stored as 0.1 - 0.00001 * the distance shown in MMD
+  pub distance: f32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct IkEnabled {
+  pub bone_name: String,
+  pub enabled: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct IkKeyframe {
+  pub frame: u32,
+  // whether the model is shown at all
+  pub show: bool,
+  pub iks: Vec<IkEnabled>,
+}
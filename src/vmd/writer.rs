@@ -0,0 +1,80 @@
+pub mod bone;
+pub mod camera;
+pub mod header;
+pub mod ik;
+pub mod light;
+pub mod morph;
+pub mod shadow;
+
+pub use bone::BoneWriter;
+pub use camera::CameraWriter;
+pub use header::HeaderWriter;
+pub use ik::IkWriter;
+pub use light::LightWriter;
+pub use morph::MorphWriter;
+pub use shadow::ShadowWriter;
+
+use crate::{writer::counted::CountedWrite, Error, Location, Result, Section};
+use encoding::all::WINDOWS_31J;
+use encoding::{EncoderTrap, Encoding};
+use std::fmt::{Display, Formatter};
+use std::io::Write;
+
+// A name that did not fit its fixed size field and was cut short, the file is still written
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TruncatedName {
+  pub location: Location,
+  pub name: String,
+  pub written: String,
+}
+
+impl Display for TruncatedName {
+  fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    write!(
+      f,
+      "{:?} truncated to {:?} in {}",
+      self.name, self.written, self.location
+    )
+  }
+}
+
+// Names are cut on character boundaries to fit, with a warning recording what was kept
+pub(crate) fn write_name<W: Write>(
+  write: &mut CountedWrite<W>,
+  warnings: &mut Vec<TruncatedName>,
+  name: &str,
+  size: usize,
+  section: Section,
+  element: Option<usize>,
+) -> Result<()> {
+  let offset = write.position;
+  let mut bytes = WINDOWS_31J
+    .encode(name, EncoderTrap::Strict)
+    .map_err(Error::EncodeText)?;
+  if bytes.len() > size {
+    let mut kept = String::new();
+    bytes.clear();
+    for c in name.chars() {
+      let encoded = WINDOWS_31J
+        .encode(c.encode_utf8(&mut [0; 4]), EncoderTrap::Strict)
+        .map_err(Error::EncodeText)?;
+      if bytes.len() + encoded.len() > size {
+        break;
+      }
+      bytes.extend_from_slice(&encoded);
+      kept.push(c);
+    }
+    warnings.push(TruncatedName {
+      location: Location {
+        section,
+        element,
+        offset,
+      },
+      name: name.to_string(),
+      written: kept,
+    });
+  }
+  bytes.resize(size, 0);
+  write.write_all(&bytes)?;
+  Ok(())
+}
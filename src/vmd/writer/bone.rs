@@ -0,0 +1,82 @@
+use crate::{
+  pmx::error::Section,
+  vmd::writer::{write_name, HeaderWriter, TruncatedName},
+  vmd::BoneKeyframe,
+  writer::{
+    counted::CountedWrite,
+    helpers::{checked_count, WriteHelpers},
+  },
+  Config, Error, Result,
+};
+use byteorder::{WriteBytesExt, LE};
+use std::io::Write;
+
+pub struct BoneWriter<W> {
+  pub count: i32,
+  pub remaining: i32,
+  pub warnings: Vec<TruncatedName>,
+  pub(crate) write: CountedWrite<W>,
+  pub(crate) poison: bool,
+}
+
+impl<W: Write> BoneWriter<W> {
+  pub fn new(mut header: HeaderWriter<W>, count: usize) -> Result<BoneWriter<W>> {
+    header.write_header()?;
+    let offset = header.write.position;
+    let count = checked_count(count)
+      .and_then(|count| {
+        header.write.write_i32::<LE>(count)?;
+        Ok(count)
+      })
+      .map_err(|e| e.at(Section::BoneKeyframes, None, offset))?;
+
+    Ok(BoneWriter {
+      count,
+      remaining: count,
+      warnings: header.warnings,
+      write: header.write,
+      poison: false,
+    })
+  }
+
+  pub fn write<C: Config>(&mut self, keyframe: &BoneKeyframe<C>) -> Result<()> {
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    let element = (self.count - self.remaining) as usize;
+    let offset = self.write.position;
+    let result = self.write_impl(keyframe, element);
+    if result.is_err() {
+      self.poison = true;
+    }
+    result.map_err(|e| e.at(Section::BoneKeyframes, Some(element), offset))
+  }
+
+  fn write_impl<C: Config>(&mut self, keyframe: &BoneKeyframe<C>, element: usize) -> Result<()> {
+    if self.remaining <= 0 {
+      return Err(Error::ElementCountMismatch {
+        section: Section::BoneKeyframes,
+        expected: self.count,
+        written: self.count + 1,
+      });
+    }
+
+    write_name(
+      &mut self.write,
+      &mut self.warnings,
+      &keyframe.bone_name,
+      15,
+      Section::BoneKeyframes,
+      Some(element),
+    )?;
+    self.write.write_u32::<LE>(keyframe.frame)?;
+    self.write.write_vec(&keyframe.translation)?;
+    self.write.write_vec(&keyframe.rotation)?;
+    for row in &keyframe.interpolation {
+      self.write.write_all(row)?;
+    }
+
+    self.remaining -= 1;
+    Ok(())
+  }
+}
@@ -0,0 +1,87 @@
+use crate::{
+  pmx::error::Section,
+  vmd::writer::{MorphWriter, TruncatedName},
+  vmd::CameraKeyframe,
+  writer::{
+    counted::CountedWrite,
+    helpers::{checked_count, WriteHelpers},
+  },
+  Config, Error, Result,
+};
+use byteorder::{WriteBytesExt, LE};
+use std::io::Write;
+
+pub struct CameraWriter<W> {
+  pub count: i32,
+  pub remaining: i32,
+  pub warnings: Vec<TruncatedName>,
+  pub(crate) write: CountedWrite<W>,
+  pub(crate) poison: bool,
+}
+
+impl<W: Write> CameraWriter<W> {
+  pub fn new(mut p: MorphWriter<W>, count: usize) -> Result<CameraWriter<W>> {
+    if p.poison {
+      return Err(Error::Poisoned);
+    }
+    if p.remaining != 0 {
+      return Err(
+        Error::ElementCountMismatch {
+          section: Section::MorphKeyframes,
+          expected: p.count,
+          written: p.count - p.remaining,
+        }
+        .at(Section::MorphKeyframes, None, p.write.position),
+      );
+    }
+    let offset = p.write.position;
+    let count = checked_count(count)
+      .and_then(|count| {
+        p.write.write_i32::<LE>(count)?;
+        Ok(count)
+      })
+      .map_err(|e| e.at(Section::CameraKeyframes, None, offset))?;
+
+    Ok(CameraWriter {
+      count,
+      remaining: count,
+      warnings: p.warnings,
+      write: p.write,
+      poison: false,
+    })
+  }
+
+  pub fn write<C: Config>(&mut self, keyframe: &CameraKeyframe<C>) -> Result<()> {
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    let element = (self.count - self.remaining) as usize;
+    let offset = self.write.position;
+    let result = self.write_impl(keyframe);
+    if result.is_err() {
+      self.poison = true;
+    }
+    result.map_err(|e| e.at(Section::CameraKeyframes, Some(element), offset))
+  }
+
+  fn write_impl<C: Config>(&mut self, keyframe: &CameraKeyframe<C>) -> Result<()> {
+    if self.remaining <= 0 {
+      return Err(Error::ElementCountMismatch {
+        section: Section::CameraKeyframes,
+        expected: self.count,
+        written: self.count + 1,
+      });
+    }
+
+    self.write.write_u32::<LE>(keyframe.frame)?;
+    self.write.write_f32::<LE>(keyframe.distance)?;
+    self.write.write_vec(&keyframe.position)?;
+    self.write.write_vec(&keyframe.rotation)?;
+    self.write.write_all(&keyframe.interpolation)?;
+    self.write.write_u32::<LE>(keyframe.fov)?;
+    self.write.write_u8(!keyframe.perspective as u8)?;
+
+    self.remaining -= 1;
+    Ok(())
+  }
+}
@@ -0,0 +1,44 @@
+use crate::{
+  pmx::error::Section,
+  vmd::writer::{write_name, TruncatedName},
+  writer::counted::CountedWrite,
+  Result,
+};
+use std::io::Write;
+
+pub struct HeaderWriter<W> {
+  pub model_name: String,
+  pub warnings: Vec<TruncatedName>,
+  pub(crate) write: CountedWrite<W>,
+}
+
+impl<W: Write> HeaderWriter<W> {
+  // Nothing is written until the header is handed to BoneWriter, fill the public fields first
+  pub fn new(write: W) -> HeaderWriter<W> {
+    HeaderWriter {
+      model_name: String::new(),
+      warnings: Vec::new(),
+      write: CountedWrite::new(write),
+    }
+  }
+
+  pub(crate) fn write_header(&mut self) -> Result<()> {
+    self
+      .write_header_impl()
+      .map_err(|e| e.at(Section::Header, None, 0))
+  }
+
+  fn write_header_impl(&mut self) -> Result<()> {
+    let mut magic = [0u8; 30];
+    magic[..25].copy_from_slice(b"Vocaloid Motion Data 0002");
+    self.write.write_all(&magic)?;
+    write_name(
+      &mut self.write,
+      &mut self.warnings,
+      &self.model_name,
+      20,
+      Section::Header,
+      None,
+    )
+  }
+}
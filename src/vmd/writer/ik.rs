@@ -0,0 +1,112 @@
+use crate::{
+  pmx::error::Section,
+  vmd::writer::{write_name, ShadowWriter, TruncatedName},
+  vmd::IkKeyframe,
+  writer::{counted::CountedWrite, helpers::checked_count},
+  Error, Result,
+};
+use byteorder::{WriteBytesExt, LE};
+use std::io::Write;
+
+pub struct IkWriter<W> {
+  pub count: i32,
+  pub remaining: i32,
+  pub warnings: Vec<TruncatedName>,
+  pub(crate) write: CountedWrite<W>,
+  pub(crate) poison: bool,
+}
+
+impl<W: Write> IkWriter<W> {
+  pub fn new(mut p: ShadowWriter<W>, count: usize) -> Result<IkWriter<W>> {
+    if p.poison {
+      return Err(Error::Poisoned);
+    }
+    if p.remaining != 0 {
+      return Err(
+        Error::ElementCountMismatch {
+          section: Section::ShadowKeyframes,
+          expected: p.count,
+          written: p.count - p.remaining,
+        }
+        .at(Section::ShadowKeyframes, None, p.write.position),
+      );
+    }
+    let offset = p.write.position;
+    let count = checked_count(count)
+      .and_then(|count| {
+        p.write.write_i32::<LE>(count)?;
+        Ok(count)
+      })
+      .map_err(|e| e.at(Section::IkKeyframes, None, offset))?;
+
+    Ok(IkWriter {
+      count,
+      remaining: count,
+      warnings: p.warnings,
+      write: p.write,
+      poison: false,
+    })
+  }
+
+  pub fn write(&mut self, keyframe: &IkKeyframe) -> Result<()> {
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    let element = (self.count - self.remaining) as usize;
+    let offset = self.write.position;
+    let result = self.write_impl(keyframe, element);
+    if result.is_err() {
+      self.poison = true;
+    }
+    result.map_err(|e| e.at(Section::IkKeyframes, Some(element), offset))
+  }
+
+  fn write_impl(&mut self, keyframe: &IkKeyframe, element: usize) -> Result<()> {
+    if self.remaining <= 0 {
+      return Err(Error::ElementCountMismatch {
+        section: Section::IkKeyframes,
+        expected: self.count,
+        written: self.count + 1,
+      });
+    }
+
+    self.write.write_u32::<LE>(keyframe.frame)?;
+    self.write.write_u8(keyframe.show as u8)?;
+    self
+      .write
+      .write_i32::<LE>(checked_count(keyframe.iks.len())?)?;
+    for ik in &keyframe.iks {
+      write_name(
+        &mut self.write,
+        &mut self.warnings,
+        &ik.bone_name,
+        20,
+        Section::IkKeyframes,
+        Some(element),
+      )?;
+      self.write.write_u8(ik.enabled as u8)?;
+    }
+
+    self.remaining -= 1;
+    Ok(())
+  }
+
+  // Returns the writer along with every name that had to be truncated
+  pub fn finish(mut self) -> Result<(W, Vec<TruncatedName>)> {
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    if self.remaining != 0 {
+      return Err(
+        Error::ElementCountMismatch {
+          section: Section::IkKeyframes,
+          expected: self.count,
+          written: self.count - self.remaining,
+        }
+        .at(Section::IkKeyframes, None, self.write.position),
+      );
+    }
+    self.write.flush()?;
+    Ok((self.write.inner, self.warnings))
+  }
+}
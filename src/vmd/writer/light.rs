@@ -0,0 +1,83 @@
+use crate::{
+  pmx::error::Section,
+  vmd::writer::{CameraWriter, TruncatedName},
+  vmd::LightKeyframe,
+  writer::{
+    counted::CountedWrite,
+    helpers::{checked_count, WriteHelpers},
+  },
+  Config, Error, Result,
+};
+use byteorder::{WriteBytesExt, LE};
+use std::io::Write;
+
+pub struct LightWriter<W> {
+  pub count: i32,
+  pub remaining: i32,
+  pub warnings: Vec<TruncatedName>,
+  pub(crate) write: CountedWrite<W>,
+  pub(crate) poison: bool,
+}
+
+impl<W: Write> LightWriter<W> {
+  pub fn new(mut p: CameraWriter<W>, count: usize) -> Result<LightWriter<W>> {
+    if p.poison {
+      return Err(Error::Poisoned);
+    }
+    if p.remaining != 0 {
+      return Err(
+        Error::ElementCountMismatch {
+          section: Section::CameraKeyframes,
+          expected: p.count,
+          written: p.count - p.remaining,
+        }
+        .at(Section::CameraKeyframes, None, p.write.position),
+      );
+    }
+    let offset = p.write.position;
+    let count = checked_count(count)
+      .and_then(|count| {
+        p.write.write_i32::<LE>(count)?;
+        Ok(count)
+      })
+      .map_err(|e| e.at(Section::LightKeyframes, None, offset))?;
+
+    Ok(LightWriter {
+      count,
+      remaining: count,
+      warnings: p.warnings,
+      write: p.write,
+      poison: false,
+    })
+  }
+
+  pub fn write<C: Config>(&mut self, keyframe: &LightKeyframe<C>) -> Result<()> {
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    let element = (self.count - self.remaining) as usize;
+    let offset = self.write.position;
+    let result = self.write_impl(keyframe);
+    if result.is_err() {
+      self.poison = true;
+    }
+    result.map_err(|e| e.at(Section::LightKeyframes, Some(element), offset))
+  }
+
+  fn write_impl<C: Config>(&mut self, keyframe: &LightKeyframe<C>) -> Result<()> {
+    if self.remaining <= 0 {
+      return Err(Error::ElementCountMismatch {
+        section: Section::LightKeyframes,
+        expected: self.count,
+        written: self.count + 1,
+      });
+    }
+
+    self.write.write_u32::<LE>(keyframe.frame)?;
+    self.write.write_vec(&keyframe.color)?;
+    self.write.write_vec(&keyframe.direction)?;
+
+    self.remaining -= 1;
+    Ok(())
+  }
+}
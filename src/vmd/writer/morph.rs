@@ -0,0 +1,87 @@
+use crate::{
+  pmx::error::Section,
+  vmd::writer::{write_name, BoneWriter, TruncatedName},
+  vmd::MorphKeyframe,
+  writer::{counted::CountedWrite, helpers::checked_count},
+  Error, Result,
+};
+use byteorder::{WriteBytesExt, LE};
+use std::io::Write;
+
+pub struct MorphWriter<W> {
+  pub count: i32,
+  pub remaining: i32,
+  pub warnings: Vec<TruncatedName>,
+  pub(crate) write: CountedWrite<W>,
+  pub(crate) poison: bool,
+}
+
+impl<W: Write> MorphWriter<W> {
+  pub fn new(mut p: BoneWriter<W>, count: usize) -> Result<MorphWriter<W>> {
+    if p.poison {
+      return Err(Error::Poisoned);
+    }
+    if p.remaining != 0 {
+      return Err(
+        Error::ElementCountMismatch {
+          section: Section::BoneKeyframes,
+          expected: p.count,
+          written: p.count - p.remaining,
+        }
+        .at(Section::BoneKeyframes, None, p.write.position),
+      );
+    }
+    let offset = p.write.position;
+    let count = checked_count(count)
+      .and_then(|count| {
+        p.write.write_i32::<LE>(count)?;
+        Ok(count)
+      })
+      .map_err(|e| e.at(Section::MorphKeyframes, None, offset))?;
+
+    Ok(MorphWriter {
+      count,
+      remaining: count,
+      warnings: p.warnings,
+      write: p.write,
+      poison: false,
+    })
+  }
+
+  pub fn write(&mut self, keyframe: &MorphKeyframe) -> Result<()> {
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    let element = (self.count - self.remaining) as usize;
+    let offset = self.write.position;
+    let result = self.write_impl(keyframe, element);
+    if result.is_err() {
+      self.poison = true;
+    }
+    result.map_err(|e| e.at(Section::MorphKeyframes, Some(element), offset))
+  }
+
+  fn write_impl(&mut self, keyframe: &MorphKeyframe, element: usize) -> Result<()> {
+    if self.remaining <= 0 {
+      return Err(Error::ElementCountMismatch {
+        section: Section::MorphKeyframes,
+        expected: self.count,
+        written: self.count + 1,
+      });
+    }
+
+    write_name(
+      &mut self.write,
+      &mut self.warnings,
+      &keyframe.morph_name,
+      15,
+      Section::MorphKeyframes,
+      Some(element),
+    )?;
+    self.write.write_u32::<LE>(keyframe.frame)?;
+    self.write.write_f32::<LE>(keyframe.weight)?;
+
+    self.remaining -= 1;
+    Ok(())
+  }
+}
@@ -0,0 +1,80 @@
+use crate::{
+  pmx::error::Section,
+  vmd::writer::{LightWriter, TruncatedName},
+  vmd::ShadowKeyframe,
+  writer::{counted::CountedWrite, helpers::checked_count},
+  Error, Result,
+};
+use byteorder::{WriteBytesExt, LE};
+use std::io::Write;
+
+pub struct ShadowWriter<W> {
+  pub count: i32,
+  pub remaining: i32,
+  pub warnings: Vec<TruncatedName>,
+  pub(crate) write: CountedWrite<W>,
+  pub(crate) poison: bool,
+}
+
+impl<W: Write> ShadowWriter<W> {
+  pub fn new(mut p: LightWriter<W>, count: usize) -> Result<ShadowWriter<W>> {
+    if p.poison {
+      return Err(Error::Poisoned);
+    }
+    if p.remaining != 0 {
+      return Err(
+        Error::ElementCountMismatch {
+          section: Section::LightKeyframes,
+          expected: p.count,
+          written: p.count - p.remaining,
+        }
+        .at(Section::LightKeyframes, None, p.write.position),
+      );
+    }
+    let offset = p.write.position;
+    let count = checked_count(count)
+      .and_then(|count| {
+        p.write.write_i32::<LE>(count)?;
+        Ok(count)
+      })
+      .map_err(|e| e.at(Section::ShadowKeyframes, None, offset))?;
+
+    Ok(ShadowWriter {
+      count,
+      remaining: count,
+      warnings: p.warnings,
+      write: p.write,
+      poison: false,
+    })
+  }
+
+  pub fn write(&mut self, keyframe: &ShadowKeyframe) -> Result<()> {
+    if self.poison {
+      return Err(Error::Poisoned);
+    }
+    let element = (self.count - self.remaining) as usize;
+    let offset = self.write.position;
+    let result = self.write_impl(keyframe);
+    if result.is_err() {
+      self.poison = true;
+    }
+    result.map_err(|e| e.at(Section::ShadowKeyframes, Some(element), offset))
+  }
+
+  fn write_impl(&mut self, keyframe: &ShadowKeyframe) -> Result<()> {
+    if self.remaining <= 0 {
+      return Err(Error::ElementCountMismatch {
+        section: Section::ShadowKeyframes,
+        expected: self.count,
+        written: self.count + 1,
+      });
+    }
+
+    self.write.write_u32::<LE>(keyframe.frame)?;
+    self.write.write_u8(keyframe.mode.into())?;
+    self.write.write_f32::<LE>(keyframe.distance)?;
+
+    self.remaining -= 1;
+    Ok(())
+  }
+}
@@ -0,0 +1,4 @@
+mod parse;
+pub mod pose;
+
+pub use self::pose::{BonePose, Mapping, MorphPose, Pose};
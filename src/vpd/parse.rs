@@ -0,0 +1,140 @@
+use crate::{
+  reader::helpers::bounded_capacity,
+  vpd::{BonePose, MorphPose, Pose},
+  Config, Error, Result,
+};
+use std::str::FromStr;
+
+struct Lines<'a> {
+  lines: Vec<(usize, &'a str)>,
+  position: usize,
+  last: usize,
+}
+
+impl<'a> Lines<'a> {
+  // Blank lines and "//" comments carry no data
+  fn new(text: &'a str) -> Lines<'a> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let lines = text
+      .lines()
+      .enumerate()
+      .map(|(i, line)| (i + 1, line.split("//").next().unwrap_or("").trim()))
+      .filter(|(_, line)| !line.is_empty())
+      .collect();
+    Lines {
+      lines,
+      position: 0,
+      last: 1,
+    }
+  }
+
+  fn error<S: Into<String>>(&self, message: S) -> Error {
+    Error::Vpd {
+      line: self.last,
+      message: message.into(),
+    }
+  }
+
+  fn next(&mut self, expected: &str) -> Result<&'a str> {
+    match self.lines.get(self.position) {
+      Some(&(line, text)) => {
+        self.position += 1;
+        self.last = line;
+        Ok(text)
+      }
+      None => Err(self.error(format!("expected {} before the end", expected))),
+    }
+  }
+
+  // a value line such as "0.5;" or "1.0,2.0,3.0;"
+  fn values<T: FromStr>(&mut self, expected: &str, count: usize) -> Result<Vec<T>> {
+    let line = self.next(expected)?;
+    let values = line
+      .strip_suffix(';')
+      .ok_or_else(|| self.error(format!("expected ';' after {}", expected)))?;
+    let values = values
+      .split(',')
+      .map(|v| v.trim().parse::<T>())
+      .collect::<std::result::Result<Vec<_>, _>>()
+      .map_err(|_| self.error(format!("invalid {} {:?}", expected, values)))?;
+    if values.len() != count {
+      return Err(self.error(format!(
+        "expected {} values for {}, got {}",
+        count,
+        expected,
+        values.len()
+      )));
+    }
+    Ok(values)
+  }
+
+  // "Bone0{name" opens a block
+  fn block(&mut self) -> Result<Option<(&'a str, &'a str)>> {
+    if self.position == self.lines.len() {
+      return Ok(None);
+    }
+    let line = self.next("a block")?;
+    let (head, name) = line
+      .split_once('{')
+      .ok_or_else(|| self.error(format!("expected a block, got {:?}", line)))?;
+    let kind = head.trim_end_matches(|c: char| c.is_ascii_digit());
+    Ok(Some((kind, name.trim())))
+  }
+
+  fn close(&mut self) -> Result<()> {
+    match self.next("'}'")? {
+      "}" => Ok(()),
+      line => Err(self.error(format!("expected '}}', got {:?}", line))),
+    }
+  }
+}
+
+pub(crate) fn parse<C: Config>(text: &str) -> Result<Pose<C>> {
+  let mut lines = Lines::new(text);
+  if lines.next("the signature")? != "Vocaloid Pose Data file" {
+    return Err(lines.error("not a Vocaloid Pose Data file"));
+  }
+  let model_name = lines.next("the model name")?;
+  let model_name = model_name
+    .strip_suffix(';')
+    .ok_or_else(|| lines.error("expected ';' after the model name"))?
+    .to_string();
+  let count = lines.values::<usize>("the bone count", 1)?[0];
+
+  let mut pose = Pose {
+    model_name,
+    bones: Vec::with_capacity(bounded_capacity(count)),
+    morphs: Vec::new(),
+  };
+  while let Some((kind, name)) = lines.block()? {
+    match kind {
+      "Bone" => {
+        let translation = lines.values::<f32>("the translation", 3)?;
+        let rotation = lines.values::<f32>("the rotation", 4)?;
+        pose.bones.push(BonePose {
+          bone_name: name.to_string(),
+          translation: [translation[0], translation[1], translation[2]].into(),
+          rotation: [rotation[0], rotation[1], rotation[2], rotation[3]].into(),
+        });
+      }
+      "Morph" => {
+        let weight = lines.values::<f32>("the weight", 1)?[0];
+        pose.morphs.push(MorphPose {
+          morph_name: name.to_string(),
+          weight,
+        });
+      }
+      kind => return Err(lines.error(format!("unknown block {:?}", kind))),
+    }
+    lines.close()?;
+  }
+
+  if pose.bones.len() != count {
+    return Err(lines.error(format!(
+      "expected {} bones, got {}",
+      count,
+      pose.bones.len()
+    )));
+  }
+  Ok(pose)
+}
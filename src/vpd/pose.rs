@@ -0,0 +1,174 @@
+use crate::pmx::morph::Morph;
+use crate::{vpd::parse, Bone, Config, Error, Result};
+use encoding::all::WINDOWS_31J;
+use encoding::{DecoderTrap, EncoderTrap, Encoding};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{BufReader, Read, Write};
+use std::path::Path;
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(bound(
+    serialize = "C::Vec3: Serialize, C::Vec4: Serialize",
+    deserialize = "C::Vec3: Deserialize<'de>, C::Vec4: Deserialize<'de>"
+  ))
+)]
+pub struct BonePose<C: Config> {
+  pub bone_name: String,
+  pub translation: C::Vec3,
+  // quaternion as x, y, z, w
+  pub rotation: C::Vec4,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MorphPose {
+  pub morph_name: String,
+  pub weight: f32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(bound(
+    serialize = "C::Vec3: Serialize, C::Vec4: Serialize",
+    deserialize = "C::Vec3: Deserialize<'de>, C::Vec4: Deserialize<'de>"
+  ))
+)]
+pub struct Pose<C: Config> {
+  // the model file the pose was saved from, usually "<name>.osm"
+  pub model_name: String,
+  pub bones: Vec<BonePose<C>>,
+  pub morphs: Vec<MorphPose>,
+}
+
+// Pose entries matched to model elements by local name
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mapping<'a, I, T> {
+  pub found: Vec<(I, &'a T)>,
+  pub missing: Vec<&'a str>,
+}
+
+impl<C: Config> Pose<C> {
+  pub fn open<P: AsRef<Path>>(path: P) -> Result<Pose<C>> {
+    Self::read(BufReader::new(File::open(path)?))
+  }
+
+  pub fn read<R: Read>(mut read: R) -> Result<Pose<C>> {
+    let mut bytes = Vec::new();
+    read.read_to_end(&mut bytes)?;
+    let text = WINDOWS_31J
+      .decode(&bytes, DecoderTrap::Strict)
+      .map_err(Error::DecodeText)?;
+    Self::parse(&text)
+  }
+
+  pub fn parse(text: &str) -> Result<Pose<C>> {
+    parse::parse(text)
+  }
+
+  pub fn write<W: Write>(&self, mut write: W) -> Result<W> {
+    let bytes = WINDOWS_31J
+      .encode(&self.to_text(), EncoderTrap::Strict)
+      .map_err(Error::EncodeText)?;
+    write.write_all(&bytes)?;
+    Ok(write)
+  }
+
+  // The same layout and comments MMD saves, with CRLF line ends
+  pub fn to_text(&self) -> String {
+    let mut text = String::new();
+    let mut line = |s: String| {
+      text.push_str(&s);
+      text.push_str("\r\n");
+    };
+    line("Vocaloid Pose Data file".to_string());
+    line(String::new());
+    line(format!("{};\t\t// 親ファイル名", self.model_name));
+    line(format!("{};\t\t\t\t// 総ポーズボーン数", self.bones.len()));
+    line(String::new());
+    for (i, bone) in self.bones.iter().enumerate() {
+      line(format!("Bone{}{{{}", i, bone.bone_name));
+      line(format!(
+        "  {};\t\t\t\t// trans x,y,z",
+        floats(bone.translation.as_ref())
+      ));
+      line(format!(
+        "  {};\t\t// Quaternion x,y,z,w",
+        floats(bone.rotation.as_ref())
+      ));
+      line("}".to_string());
+      line(String::new());
+    }
+    for (i, morph) in self.morphs.iter().enumerate() {
+      line(format!("Morph{}{{{}", i, morph.morph_name));
+      line(format!("  {:.6};\t\t\t\t// weight", morph.weight));
+      line("}".to_string());
+      line(String::new());
+    }
+    text
+  }
+
+  pub fn map_bones<'a>(
+    &'a self,
+    bones: &[Bone<C>],
+  ) -> Result<Mapping<'a, C::BoneIndex, BonePose<C>>> {
+    let names = bones.iter().map(|b| b.local_name.as_str());
+    map(names, self.bones.iter().map(|b| (b.bone_name.as_str(), b)))
+  }
+
+  pub fn map_morphs<'a>(
+    &'a self,
+    morphs: &[Morph<C>],
+  ) -> Result<Mapping<'a, C::MorphIndex, MorphPose>> {
+    let names = morphs.iter().map(|m| m.local_name.as_str());
+    map(
+      names,
+      self.morphs.iter().map(|m| (m.morph_name.as_str(), m)),
+    )
+  }
+}
+
+fn floats(values: &[f32]) -> String {
+  let mut text = String::new();
+  for (i, v) in values.iter().enumerate() {
+    if i > 0 {
+      text.push(',');
+    }
+    let _ = write!(text, "{:.6}", v);
+  }
+  text
+}
+
+// The first element with a name wins, like MMD
+fn map<'a, 'm, I: TryFrom<i32>, T>(
+  names: impl Iterator<Item = &'m str>,
+  entries: impl Iterator<Item = (&'a str, &'a T)>,
+) -> Result<Mapping<'a, I, T>> {
+  let mut indices = HashMap::new();
+  for (i, name) in names.enumerate() {
+    indices.entry(name).or_insert(i);
+  }
+  let mut mapping = Mapping {
+    found: Vec::new(),
+    missing: Vec::new(),
+  };
+  for (name, entry) in entries {
+    match indices.get(name) {
+      Some(&i) => {
+        let index = I::try_from(i as i32).map_err(|_| Error::IndexOverflow(i as i64))?;
+        mapping.found.push((index, entry));
+      }
+      None => mapping.missing.push(name),
+    }
+  }
+  Ok(mapping)
+}
@@ -0,0 +1,122 @@
+#![cfg(feature = "async")]
+
+mod common;
+
+use common::*;
+use futures_io::AsyncRead;
+use mmd::{AsyncHeaderReader, AsyncVertexReader, DefaultConfig, Error, Model, ParseLimits};
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+
+type C = DefaultConfig;
+
+struct Noop;
+
+impl Wake for Noop {
+  fn wake(self: Arc<Self>) {}
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+  let waker = Waker::from(Arc::new(Noop));
+  let mut cx = Context::from_waker(&waker);
+  let mut future = Box::pin(future);
+  loop {
+    if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+      return output;
+    }
+  }
+}
+
+// Hands out a single byte at a time and is pending before every one of them
+struct Trickle {
+  bytes: Vec<u8>,
+  position: usize,
+  pending: bool,
+}
+
+impl Trickle {
+  fn new(bytes: &[u8]) -> Trickle {
+    Trickle {
+      bytes: bytes.to_vec(),
+      position: 0,
+      pending: true,
+    }
+  }
+}
+
+impl AsyncRead for Trickle {
+  fn poll_read(
+    mut self: Pin<&mut Self>,
+    cx: &mut Context,
+    buf: &mut [u8],
+  ) -> Poll<io::Result<usize>> {
+    if self.pending {
+      self.pending = false;
+      cx.waker().wake_by_ref();
+      return Poll::Pending;
+    }
+    self.pending = true;
+    let read = buf.len().min(self.bytes.len() - self.position).min(1);
+    buf[..read].copy_from_slice(&self.bytes[self.position..self.position + read]);
+    self.position += read;
+    Poll::Ready(Ok(read))
+  }
+}
+
+#[test]
+fn matches_sync() {
+  let bytes = sample();
+  let model = block_on(Model::<C>::read_async(Trickle::new(&bytes))).unwrap();
+  assert_eq!(model, Model::<C>::read(bytes.as_slice()).unwrap());
+}
+
+#[test]
+fn section_readers() {
+  let bytes = sample();
+  block_on(async {
+    let header = AsyncHeaderReader::new(Trickle::new(&bytes)).await.unwrap();
+    assert_eq!(header.version, 2.1);
+    let mut vertices = AsyncVertexReader::new(header).await.unwrap();
+    assert_eq!(vertices.count, 5);
+    assert!(vertices.next::<C>().await.unwrap().is_some());
+    assert_eq!(vertices.remaining, 4);
+  });
+}
+
+#[test]
+fn truncated_errors_match_sync() {
+  let sample = sample();
+  for len in (0..sample.len()).step_by(7) {
+    let bytes = &sample[..len];
+    let sync = Model::<C>::read(bytes).unwrap_err();
+    let error = block_on(Model::<C>::read_async(Trickle::new(bytes))).unwrap_err();
+    assert_eq!(error.location(), sync.location(), "truncated at {}", len);
+    assert_eq!(error.to_string(), sync.to_string());
+  }
+}
+
+#[test]
+fn limits() {
+  let limits = ParseLimits {
+    max_total_bytes: 300,
+    ..Default::default()
+  };
+  let bytes = sample();
+  let error = block_on(Model::<C>::read_async_with_limits(
+    Trickle::new(&bytes),
+    limits,
+  ))
+  .unwrap_err();
+  assert!(matches!(
+    error.inner(),
+    Error::LimitExceeded {
+      what: "total bytes",
+      ..
+    }
+  ));
+  let sync = Model::<C>::read_with_limits(bytes.as_slice(), limits).unwrap_err();
+  assert_eq!(error.location(), sync.location());
+}
@@ -0,0 +1,145 @@
+mod common;
+
+use common::*;
+use mmd::vmd::camera::Projection;
+use mmd::vmd::interpolation::CameraTrack;
+use mmd::vmd::{self, CameraKeyframe, LINEAR_CURVE};
+use mmd::DefaultConfig;
+
+type C = DefaultConfig;
+
+fn close(a: [f32; 3], b: [f32; 3]) -> bool {
+  a.iter().zip(&b).all(|(a, b)| (a - b).abs() < 1e-4)
+}
+
+fn camera(rotation: [f32; 3], perspective: bool) -> CameraKeyframe<C> {
+  let mut keyframe = CameraKeyframe {
+    frame: 0,
+    distance: -45.0,
+    position: [0.0, 10.0, 0.0].into(),
+    rotation: rotation.map(f32::to_radians).into(),
+    interpolation: [0; 24],
+    fov: 30,
+    perspective,
+  };
+  keyframe.set_curves([LINEAR_CURVE; 6]);
+  keyframe
+}
+
+fn view(keyframe: CameraKeyframe<C>) -> mmd::vmd::camera::CameraView<C> {
+  CameraTrack::new([&keyframe]).view(0.0).unwrap()
+}
+
+#[test]
+fn default_camera() {
+  let view = view(camera([0.0; 3], true));
+  // MMD's initial camera stands in front of the model, which faces -z
+  assert!(close(view.eye.into_array(), [0.0, 10.0, -45.0]));
+  assert!(close(view.forward.into_array(), [0.0, 0.0, 1.0]));
+  assert!(close(view.up.into_array(), [0.0, 1.0, 0.0]));
+  assert_eq!(view.target, [0.0, 10.0, 0.0].into());
+  assert_eq!(
+    view.projection,
+    Projection::Perspective {
+      fov: 30f32.to_radians()
+    }
+  );
+}
+
+#[test]
+fn rotation_conventions() {
+  let yaw = view(camera([0.0, 90.0, 0.0], true));
+  assert!(close(yaw.eye.into_array(), [45.0, 10.0, 0.0]));
+  assert!(close(yaw.forward.into_array(), [-1.0, 0.0, 0.0]));
+
+  let (sin, cos) = 30f32.to_radians().sin_cos();
+  let pitch = view(camera([30.0, 0.0, 0.0], true));
+  assert!(close(pitch.forward.into_array(), [0.0, sin, cos]));
+  assert!(close(pitch.up.into_array(), [0.0, cos, -sin]));
+  assert!(close(
+    pitch.eye.into_array(),
+    [0.0, 10.0 - 45.0 * sin, -45.0 * cos]
+  ));
+
+  let roll = view(camera([0.0, 0.0, 30.0], true));
+  assert!(close(roll.forward.into_array(), [0.0, 0.0, 1.0]));
+  assert!(close(roll.up.into_array(), [sin, cos, 0.0]));
+}
+
+#[test]
+fn yaw_then_pitch() {
+  // turned to the side, pitch still tilts the view instead of rolling it
+  let (sin, cos) = 30f32.to_radians().sin_cos();
+  let side = view(camera([30.0, 90.0, 0.0], true));
+  assert!(close(side.forward.into_array(), [-cos, sin, 0.0]));
+  assert!(close(side.up.into_array(), [sin, cos, 0.0]));
+  assert!(close(
+    side.eye.into_array(),
+    [45.0 * cos, 10.0 - 45.0 * sin, 0.0]
+  ));
+
+  let (sin_y, cos_y) = (-130f32).to_radians().sin_cos();
+  let (sin_z, cos_z) = 45f32.to_radians().sin_cos();
+  let turned = view(camera([30.0, -130.0, 45.0], true));
+  let forward = [-sin_y * cos, sin, cos_y * cos];
+  assert!(close(turned.forward.into_array(), forward));
+  assert!(close(
+    turned.up.into_array(),
+    [
+      sin_z * cos_y + cos_z * sin * sin_y,
+      cos_z * cos,
+      sin_z * sin_y - cos_z * sin * cos_y
+    ]
+  ));
+  assert!(close(
+    turned.eye.into_array(),
+    [
+      -45.0 * forward[0],
+      10.0 - 45.0 * forward[1],
+      -45.0 * forward[2]
+    ]
+  ));
+}
+
+#[test]
+fn orientation_matches_vectors() {
+  let view = view(camera([20.0, -130.0, 45.0], true));
+  let q = view.orientation;
+  let rotated = |v: vek::Vec3<f32>| {
+    let v = vek::Quaternion::from_xyzw(q.x, q.y, q.z, q.w) * v;
+    v.into_array()
+  };
+  assert!(close(
+    rotated(vek::Vec3::unit_z()),
+    view.forward.into_array()
+  ));
+  assert!(close(rotated(vek::Vec3::unit_y()), view.up.into_array()));
+  let eye = view.target + view.forward * -45.0;
+  assert!(close(eye.into_array(), view.eye.into_array()));
+}
+
+#[test]
+fn orthographic() {
+  let view = view(camera([0.0; 3], false));
+  let height = 2.0 * 45.0 * 15f32.to_radians().tan();
+  assert_eq!(view.projection, Projection::Orthographic { height });
+}
+
+#[test]
+fn evaluates_tracks() {
+  let motion = vmd::Motion::<C>::read(vmd_sample(true).as_slice()).unwrap();
+  let mut next = camera([0.0, 90.0, 0.0], true);
+  next.frame = 15;
+  next.position = motion.cameras[0].position;
+  next.distance = motion.cameras[0].distance;
+  let mut first = motion.cameras[0].clone();
+  first.rotation = [0.0; 3].into();
+  let keyframes = [first, next];
+  let track = CameraTrack::new(&keyframes);
+
+  let view = track.view(10.0).unwrap();
+  let (sin, cos) = 45f32.to_radians().sin_cos();
+  assert!(close(view.forward.into_array(), [-sin, 0.0, cos]));
+  assert!(track.view(-3.0).is_some());
+  assert!(CameraTrack::<C>::new([]).view(0.0).is_none());
+}
@@ -0,0 +1,446 @@
+#![allow(dead_code)]
+
+use encoding::all::WINDOWS_31J;
+use encoding::{EncoderTrap, Encoding};
+use mmd::pmx::reader::*;
+use mmd::{DefaultConfig, Error, ParseLimits};
+
+pub struct Builder(pub Vec<u8>);
+
+impl Builder {
+  pub fn u8(&mut self, v: u8) -> &mut Self {
+    self.0.push(v);
+    self
+  }
+
+  pub fn u16(&mut self, v: u16) -> &mut Self {
+    self.0.extend_from_slice(&v.to_le_bytes());
+    self
+  }
+
+  pub fn i32(&mut self, v: i32) -> &mut Self {
+    self.0.extend_from_slice(&v.to_le_bytes());
+    self
+  }
+
+  pub fn f32(&mut self, v: f32) -> &mut Self {
+    self.0.extend_from_slice(&v.to_le_bytes());
+    self
+  }
+
+  pub fn vec(&mut self, n: usize) -> &mut Self {
+    for i in 0..n {
+      self.f32(i as f32 * 0.5);
+    }
+    self
+  }
+
+  pub fn text(&mut self, s: &str) -> &mut Self {
+    let bytes: Vec<u8> = s.encode_utf16().flat_map(u16::to_le_bytes).collect();
+    self.i32(bytes.len() as i32);
+    self.0.extend_from_slice(&bytes);
+    self
+  }
+
+  // fixed size Shift-JIS field as used by PMD, padded after the NUL like real files
+  pub fn name(&mut self, s: &str, size: usize) -> &mut Self {
+    let mut bytes = WINDOWS_31J.encode(s, EncoderTrap::Strict).unwrap();
+    bytes.push(0);
+    bytes.resize(size, 0xFD);
+    self.0.extend_from_slice(&bytes[..size]);
+    self
+  }
+
+  // every index class is stored as 16 bits in the sample
+  pub fn index(&mut self, v: i16) -> &mut Self {
+    self.0.extend_from_slice(&v.to_le_bytes());
+    self
+  }
+}
+
+pub fn sample() -> Vec<u8> {
+  sample_with_texture("tex.png")
+}
+
+pub fn sample_with_texture(texture: &str) -> Vec<u8> {
+  let mut b = Builder(Vec::new());
+  b.0.extend_from_slice(b"PMX ");
+  b.f32(2.1).u8(8);
+  b.0.extend_from_slice(&[0, 1, 2, 2, 2, 2, 2, 2]);
+  b.text("モデル")
+    .text("model")
+    .text("コメント")
+    .text("comment");
+
+  // vertices: one of each weight deform type
+  b.i32(5);
+  for weight_type in 0..5u8 {
+    b.vec(3).vec(3).vec(2).vec(4).u8(weight_type);
+    match weight_type {
+      0 => {
+        b.index(0);
+      }
+      1 => {
+        b.index(0).index(1).f32(0.5);
+      }
+      3 => {
+        b.index(0).index(1).f32(0.5).vec(3).vec(3).vec(3);
+      }
+      _ => {
+        b.index(0).index(1).index(0).index(1).vec(4);
+      }
+    }
+    b.f32(1.0);
+  }
+
+  // surfaces
+  b.i32(3).index(0).index(1).index(2);
+
+  // textures
+  b.i32(1).text(texture);
+
+  // materials
+  b.i32(2);
+  b.text("材質")
+    .text("material")
+    .vec(4)
+    .vec(3)
+    .f32(5.0)
+    .vec(3)
+    .u8(0b0001_0011);
+  b.vec(4)
+    .f32(1.0)
+    .index(0)
+    .index(-1)
+    .u8(1)
+    .u8(0)
+    .index(0)
+    .text("")
+    .i32(3);
+  b.text("材質2")
+    .text("material2")
+    .vec(4)
+    .vec(3)
+    .f32(5.0)
+    .vec(3)
+    .u8(0);
+  b.vec(4)
+    .f32(1.0)
+    .index(-1)
+    .index(-1)
+    .u8(0)
+    .u8(1)
+    .u8(3)
+    .text("memo")
+    .i32(0);
+
+  // bones
+  b.i32(2);
+  b.text("センター")
+    .text("center")
+    .vec(3)
+    .index(-1)
+    .i32(0)
+    .u16(0b0000_0000_0001_1110);
+  b.vec(3);
+  b.text("足ＩＫ")
+    .text("leg IK")
+    .vec(3)
+    .index(0)
+    .i32(1)
+    .u16(0b0011_1111_0011_1111);
+  b.index(0).index(0).f32(0.5).vec(3).vec(3).vec(3).i32(2);
+  b.index(0).i32(40).f32(2.0).i32(2);
+  b.index(0).u8(1).vec(3).vec(3).index(1).u8(0);
+
+  // morphs
+  b.i32(6);
+  b.text("グループ")
+    .text("group")
+    .u8(4)
+    .u8(0)
+    .i32(1)
+    .index(1)
+    .f32(1.0);
+  b.text("頂点")
+    .text("vertex")
+    .u8(1)
+    .u8(1)
+    .i32(2)
+    .index(0)
+    .vec(3)
+    .index(1)
+    .vec(3);
+  b.text("ボーン")
+    .text("bone")
+    .u8(2)
+    .u8(2)
+    .i32(1)
+    .index(0)
+    .vec(3)
+    .vec(4);
+  b.text("UV").text("uv").u8(3).u8(3).i32(1).index(2).vec(4);
+  b.text("材質")
+    .text("material")
+    .u8(9)
+    .u8(8)
+    .i32(1)
+    .index(0)
+    .u8(1);
+  b.vec(4)
+    .vec(3)
+    .f32(1.0)
+    .vec(3)
+    .vec(4)
+    .f32(1.0)
+    .vec(4)
+    .vec(4)
+    .vec(4);
+  b.text("インパルス")
+    .text("impulse")
+    .u8(0)
+    .u8(10)
+    .i32(1)
+    .index(0)
+    .u8(1);
+  b.vec(3).vec(3);
+
+  // display frames
+  b.i32(1).text("Root").text("Root").u8(1).i32(2);
+  b.u8(0).index(0).u8(1).index(1);
+
+  // rigid bodies
+  b.i32(1)
+    .text("剛体")
+    .text("body")
+    .index(0)
+    .u8(1)
+    .u16(0xfffe)
+    .u8(2);
+  b.vec(3)
+    .vec(3)
+    .vec(3)
+    .f32(1.0)
+    .f32(0.5)
+    .f32(0.5)
+    .f32(0.0)
+    .f32(0.5)
+    .u8(1);
+
+  // joints
+  b.i32(1)
+    .text("ジョイント")
+    .text("joint")
+    .u8(0)
+    .index(0)
+    .index(0);
+  b.vec(3).vec(3).vec(3).vec(3).vec(3).vec(3).vec(3).vec(3);
+
+  // soft bodies
+  b.i32(1)
+    .text("ソフト")
+    .text("soft")
+    .u8(0)
+    .index(0)
+    .u8(1)
+    .u16(0xffff)
+    .u8(0b011);
+  b.i32(2).i32(0).f32(1.0).f32(0.05).i32(1);
+  b.vec(12).vec(6).i32(0).i32(1).i32(0).i32(4).vec(3);
+  b.i32(1).index(0).index(1).u8(1);
+  b.i32(2).index(0).index(2);
+
+  b.0
+}
+
+// A PMD model, extended adds the english names, toon textures and physics sections
+pub fn pmd_sample(extended: bool) -> Vec<u8> {
+  let mut b = Builder(b"Pmd".to_vec());
+  b.f32(1.0).name("テスト", 20).name("コメント", 256);
+
+  b.i32(3);
+  for bones in [[0, 1], [1, 0], [2, 0xFFFF]] {
+    b.vec(3)
+      .vec(3)
+      .vec(2)
+      .u16(bones[0])
+      .u16(bones[1])
+      .u8(100)
+      .u8(0);
+  }
+  b.i32(3).u16(0).u16(1).u16(2);
+
+  b.i32(1).vec(4).f32(5.0).vec(3).vec(3).u8(0).u8(1).i32(3);
+  b.name("tex.bmp*sph.sph", 20);
+
+  b.u16(3);
+  b.name("センター", 20)
+    .u16(0xFFFF)
+    .u16(1)
+    .u8(1)
+    .u16(0)
+    .vec(3);
+  b.name("右足ＩＫ", 20).u16(0).u16(0).u8(2).u16(0).vec(3);
+  // 21 bytes of Shift-JIS, cut in the middle of the last character like editors do
+  b.name("右足首_先端親指人差指", 20)
+    .u16(0)
+    .u16(0)
+    .u8(4)
+    .u16(1)
+    .vec(3);
+  b.u16(1).u16(1).u16(2).u8(1).u16(40).f32(0.5).u16(0);
+
+  b.u16(2);
+  b.name("base", 20).i32(2).u8(0);
+  b.i32(0).vec(3).i32(1).vec(3);
+  b.name("まばたき", 20).i32(1).u8(2);
+  b.i32(1).vec(3);
+
+  b.u8(1).u16(1);
+  b.u8(1).name("足\n", 50);
+  b.i32(1).u16(2).u8(1);
+
+  if extended {
+    b.u8(1).name("test", 20).name("comment", 256);
+    b.name("center", 20)
+      .name("leg IK_R", 20)
+      .name("ankle_R", 20);
+    b.name("blink", 20);
+    b.name("legs", 50);
+
+    for i in 1..=10 {
+      b.name(&format!("toon{:02}.bmp", i), 100);
+    }
+
+    b.i32(1).name("body", 20).u16(0).u8(1).u16(0xFFFE).u8(1);
+    b.vec(3)
+      .vec(3)
+      .vec(3)
+      .f32(1.0)
+      .f32(0.5)
+      .f32(0.5)
+      .f32(0.0)
+      .f32(0.5)
+      .u8(1);
+    b.i32(1).name("joint", 20).i32(0).i32(0);
+    b.vec(3).vec(3).vec(3).vec(3).vec(3).vec(3).vec(3).vec(3);
+  }
+
+  b.0
+}
+
+pub fn vmd_sample(extended: bool) -> Vec<u8> {
+  let mut b = Builder(Vec::new());
+  b.name("Vocaloid Motion Data 0002", 30).name("テスト", 20);
+
+  b.i32(2);
+  b.name("センター", 15)
+    .i32(0)
+    .vec(3)
+    .f32(0.0)
+    .f32(0.0)
+    .f32(0.0)
+    .f32(1.0);
+  for row in 0..4 {
+    for i in 0..16u8 {
+      b.u8(row + i);
+    }
+  }
+  // cut in the middle of the last character like MMD does
+  b.name("センター親指先端", 15).i32(30).vec(3).vec(4);
+  for _ in 0..64 {
+    b.u8(20);
+  }
+
+  b.i32(1).name("まばたき", 15).i32(10).f32(0.5);
+
+  if extended {
+    b.i32(1).i32(5).f32(-45.0).vec(3).vec(3);
+    for i in 0..24 {
+      b.u8(i);
+    }
+    b.i32(30).u8(1);
+
+    b.i32(1).i32(0).vec(3).f32(-0.5).f32(-1.0).f32(0.5);
+    b.i32(1).i32(0).u8(1).f32(0.01);
+
+    b.i32(1).i32(0).u8(1).i32(2);
+    b.name("右足ＩＫ", 20).u8(0);
+    b.name("左足ＩＫ", 20).u8(1);
+  }
+
+  b.0
+}
+
+pub fn vpd_sample() -> Vec<u8> {
+  let text = "Vocaloid Pose Data file\r
+\r
+テスト.osm;\t\t// 親ファイル名\r
+2;\t\t\t\t// 総ポーズボーン数\r
+\r
+Bone0{センター\r
+  0.000000,1.500000,-2.000000;\t\t\t\t// trans x,y,z\r
+  0.000000,0.000000,0.000000,1.000000;\t\t// Quaternion x,y,z,w\r
+}\r
+\r
+Bone1{右足ＩＫ\r
+  0.000000,0.000000,0.000000;\t\t\t\t// trans x,y,z\r
+  0.500000,0.500000,-0.500000,0.500000;\t\t// Quaternion x,y,z,w\r
+}\r
+\r
+Morph0{頂点\r
+  0.250000;\t\t\t\t// weight\r
+}\r
+\r
+";
+  WINDOWS_31J.encode(text, EncoderTrap::Strict).unwrap()
+}
+
+pub fn read_all(bytes: &[u8]) -> Result<(), Error> {
+  read_all_with_limits(bytes, ParseLimits::default())
+}
+
+pub fn read_all_with_limits(bytes: &[u8], limits: ParseLimits) -> Result<(), Error> {
+  let header = HeaderReader::with_limits(bytes, limits)?;
+  let mut vertices = VertexReader::new(header)?;
+  for v in vertices.iter::<DefaultConfig>() {
+    v?;
+  }
+  let mut surfaces = SurfaceReader::new(vertices)?;
+  for s in surfaces.iter::<DefaultConfig>() {
+    s?;
+  }
+  let mut textures = TextureReader::new(surfaces)?;
+  for t in textures.iter() {
+    t?;
+  }
+  let mut materials = MaterialReader::new(textures)?;
+  for m in materials.iter::<DefaultConfig>() {
+    m?;
+  }
+  let mut bones = BoneReader::new(materials)?;
+  for b in bones.iter::<DefaultConfig>() {
+    b?;
+  }
+  let mut morphs = MorphReader::new(bones)?;
+  for m in morphs.iter::<DefaultConfig>() {
+    m?;
+  }
+  let mut display_frames = DisplayFrameReader::new(morphs)?;
+  for d in display_frames.iter::<DefaultConfig>() {
+    d?;
+  }
+  let mut rigid_bodies = RigidBodyReader::new(display_frames)?;
+  for r in rigid_bodies.iter::<DefaultConfig>() {
+    r?;
+  }
+  let mut joints = JointReader::new(rigid_bodies)?;
+  for j in joints.iter::<DefaultConfig>() {
+    j?;
+  }
+  let mut soft_bodies = SoftBodyReader::new(joints)?;
+  for s in soft_bodies.iter::<DefaultConfig>() {
+    s?;
+  }
+  Ok(())
+}
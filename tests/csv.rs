@@ -0,0 +1,162 @@
+mod common;
+
+use common::*;
+use mmd::pmx::csv;
+use mmd::pmx::material::DrawingFlags;
+use mmd::pmx::morph::Offsets;
+use mmd::{DefaultConfig, Error, Model, Section, Settings};
+
+type C = DefaultConfig;
+
+fn close(a: &[f32], b: &[f32]) -> bool {
+  a.len() == b.len() && a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-5)
+}
+
+#[test]
+fn sample_round_trips() {
+  let mut model = Model::<C>::read(sample().as_slice()).unwrap();
+  model.soft_bodies.clear();
+  if let Offsets::Bone(ref mut offsets) = model.morphs[2].offsets {
+    offsets[0].rotation = [0.5, -0.5, 0.5, 0.5].into();
+  }
+
+  let text = csv::to_string(&model).unwrap();
+  assert!(text.contains("PmxBone,\"足ＩＫ\",\"leg IK\",1,1,"));
+  let parsed = csv::from_str::<C>(&text).unwrap();
+
+  let settings = Settings {
+    text_encoding: model.settings.text_encoding,
+    ..model.optimal_settings()
+  };
+  assert_eq!(parsed.settings, settings);
+  assert_eq!(parsed.model_local_name, model.model_local_name);
+  assert_eq!(parsed.vertices, model.vertices);
+  assert_eq!(parsed.surfaces, model.surfaces);
+  assert_eq!(parsed.textures, model.textures);
+  assert_eq!(parsed.materials, model.materials);
+  assert_eq!(parsed.display_frames, model.display_frames);
+
+  for (parsed, bone) in parsed.bones.iter().zip(&model.bones) {
+    let (parsed_ik, ik) = (&parsed.inverse_kinematics, &bone.inverse_kinematics);
+    assert_eq!(parsed.bone_flags, bone.bone_flags);
+    assert_eq!(parsed.connection, bone.connection);
+    assert_eq!(parsed.additional, bone.additional);
+    assert_eq!(parsed.local_axis, bone.local_axis);
+    assert_eq!(parsed_ik.is_some(), ik.is_some());
+    if let (Some(parsed_ik), Some(ik)) = (parsed_ik, ik) {
+      assert!((parsed_ik.limit_angle - ik.limit_angle).abs() < 1e-6);
+      assert_eq!(parsed_ik.links.len(), ik.links.len());
+      let (low, high) = ik.links[0].limits.unwrap();
+      let (parsed_low, parsed_high) = parsed_ik.links[0].limits.unwrap();
+      assert!(close(&parsed_low, &low) && close(&parsed_high, &high));
+    }
+  }
+
+  assert_eq!(parsed.morphs.len(), model.morphs.len());
+  for (parsed, morph) in parsed.morphs.iter().zip(&model.morphs) {
+    match (&parsed.offsets, &morph.offsets) {
+      (Offsets::Bone(parsed), Offsets::Bone(offsets)) => {
+        assert_eq!(parsed[0].translation, offsets[0].translation);
+        assert!(close(&parsed[0].rotation, &offsets[0].rotation));
+      }
+      (parsed, offsets) => assert_eq!(parsed, offsets),
+    }
+  }
+
+  let (parsed, body) = (&parsed.rigid_bodies[0], &model.rigid_bodies[0]);
+  assert_eq!(parsed.non_collision_mask, body.non_collision_mask);
+  assert!(close(&parsed.shape_rotation, &body.shape_rotation));
+}
+
+#[test]
+fn editor_rows() {
+  let text = "\
+;PmxBone,ボーン名,...
+PmxBone,\"左足ＩＫ\",\"leg IK_L\",0,0,1,2,3,1,1,1,1,1,\"\",0,\"\",0,1,0,0,0,0,0,\"\",0,0,0,0,0,0,0,0,0,0,0,0,0,\"左足首\",40,114.5916
+PmxBone,\"左足首\",\"ankle_L\",0,0,1,1,0,1,0,1,1,1,\"左足ＩＫ\",0,\"\",0,-1,0,0,0,0,0,\"\",0,0,0,0,0,0,0,0,0,0,0,0,0,\"\",0,0
+PmxIKLink,\"左足ＩＫ\",\"左足首\",1,-180,-0.5,0,0,0,0
+PmxMorph,\"回転\",\"turn\",4,2
+PmxBoneMorph,\"回転\",\"左足首\",0,0,0,0,90,0
+";
+  let model = csv::from_str::<C>(text).unwrap();
+  assert_eq!(model.bones.len(), 2);
+  assert_eq!(model.bones[1].parent, 0);
+  let ik = model.bones[0].inverse_kinematics.as_ref().unwrap();
+  assert_eq!(ik.ik_bone, 1);
+  assert!((ik.limit_angle - 2.0).abs() < 1e-5);
+  let (low, _) = ik.links[0].limits.unwrap();
+  assert!((low.x + std::f32::consts::PI).abs() < 1e-6);
+
+  match model.morphs[0].offsets {
+    Offsets::Bone(ref offsets) => {
+      let half = std::f32::consts::FRAC_1_SQRT_2;
+      assert_eq!(offsets[0].bone, 1);
+      assert!(close(&offsets[0].rotation, &[0.0, half, 0.0, half]));
+    }
+    ref offsets => panic!("{:?}", offsets),
+  }
+}
+
+#[test]
+fn quoted_text() {
+  let mut model = Model::<C>::read(sample().as_slice()).unwrap();
+  model.soft_bodies.clear();
+  model.local_comments = "line \"one\",\r\nline two".to_string();
+  model.materials[1].metadata = ";not a comment".to_string();
+  let parsed = csv::from_str::<C>(&csv::to_string(&model).unwrap()).unwrap();
+  assert_eq!(parsed.local_comments, model.local_comments);
+  assert_eq!(parsed.materials[1].metadata, model.materials[1].metadata);
+}
+
+#[test]
+fn unknown_name() {
+  let text = "PmxHeader,2.0,0,0\r\nPmxBone,\"a\",\"a\",0,0,0,0,0,1,1,0,1,1,\"missing\"";
+  match csv::from_str::<C>(text) {
+    Err(Error::Csv { line, message }) => {
+      assert_eq!(line, 2);
+      assert!(message.contains("missing"));
+    }
+    other => panic!("{:?}", other.map(|_| ())),
+  }
+}
+
+#[test]
+fn uncovered_surfaces() {
+  let mut model = Model::<C>::read(sample().as_slice()).unwrap();
+  model.soft_bodies.clear();
+  let surfaces = model.surfaces.len() as i32 * 3;
+  model.surfaces.push(model.surfaces[0]);
+  match csv::to_string(&model) {
+    Err(Error::ElementCountMismatch {
+      section: Section::Surfaces,
+      expected,
+      written,
+    }) => assert_eq!((expected, written), (surfaces + 3, surfaces)),
+    other => panic!("{:?}", other.map(|_| ())),
+  }
+  assert!(csv::to_writer(&model, Vec::new()).is_err());
+
+  model.surfaces.pop();
+  model.materials[0].surface_count = -3;
+  match csv::to_string(&model) {
+    Err(Error::NegativeCount(-3)) => {}
+    other => panic!("{:?}", other.map(|_| ())),
+  }
+}
+
+#[test]
+fn point_and_line_drawing() {
+  let mut model = Model::<C>::read(sample().as_slice()).unwrap();
+  model.soft_bodies.clear();
+  model.materials[0].draw_flags |= DrawingFlags::PointDrawing | DrawingFlags::LineDrawing;
+  model.materials[1].draw_flags |= DrawingFlags::LineDrawing;
+  let parsed = csv::from_str::<C>(&csv::to_string(&model).unwrap()).unwrap();
+
+  let flags = parsed.materials[0].draw_flags;
+  assert!(flags.contains(DrawingFlags::PointDrawing));
+  assert!(!flags.contains(DrawingFlags::LineDrawing));
+  assert_eq!(
+    parsed.materials[1].draw_flags,
+    model.materials[1].draw_flags
+  );
+}
@@ -0,0 +1,109 @@
+mod common;
+
+use common::*;
+use mmd::pmx::reader::{HeaderReader, SurfaceReader, VertexReader};
+use mmd::{DefaultConfig, Error, Location, Model, Section};
+
+#[test]
+fn sample_is_valid() {
+  read_all(&sample()).unwrap();
+}
+
+#[test]
+fn truncated_files_fail_without_panic() {
+  let sample = sample();
+  for len in 0..sample.len() {
+    assert!(read_all(&sample[..len]).is_err(), "truncated at {}", len);
+  }
+}
+
+#[test]
+fn mutated_files_never_panic() {
+  let sample = sample();
+  let mut mutated = sample.clone();
+  for i in 0..sample.len() {
+    for &value in &[0x00, 0x01, 0x03, 0x7f, 0x80, 0xfe, 0xff] {
+      mutated[i] = value;
+      let _ = read_all(&mutated);
+    }
+    mutated[i] = sample[i];
+  }
+}
+
+#[test]
+fn negative_text_size() {
+  let mut bytes = sample();
+  // the local model name size follows the 17 byte signature, version and globals
+  bytes[17..21].copy_from_slice(&(-1i32).to_le_bytes());
+  assert!(matches!(
+    read_all(&bytes).unwrap_err().inner(),
+    Error::NegativeTextSize(-1)
+  ));
+}
+
+#[test]
+fn huge_text_size() {
+  let mut bytes = sample();
+  bytes[17..21].copy_from_slice(&i32::MAX.to_le_bytes());
+  assert!(matches!(
+    read_all(&bytes).unwrap_err().inner(),
+    Error::Io(_)
+  ));
+}
+
+#[test]
+fn unknown_bone_flags_are_kept() {
+  let mut bytes = sample();
+  let center = "センター"
+    .encode_utf16()
+    .flat_map(u16::to_le_bytes)
+    .collect::<Vec<u8>>();
+  let position = bytes
+    .windows(center.len())
+    .position(|w| w == center.as_slice())
+    .unwrap();
+  // name, universal name, position, parent and transform level precede the flags
+  let flags = position + center.len() + 4 + 12 + 12 + 2 + 4;
+  bytes[flags + 1] |= 0b1100_0000;
+  let model = Model::<DefaultConfig>::read(bytes.as_slice()).unwrap();
+  assert_eq!(model.bones[0].bone_flags.bits(), 0b0000_0000_0001_1110);
+  assert_eq!(model.bones[0].unknown_flags, 0b1100_0000_0000_0000);
+}
+
+#[test]
+fn poisoned_reader() {
+  let mut bytes = sample();
+  bytes.truncate(200);
+  let header = HeaderReader::new(bytes.as_slice()).unwrap();
+  let mut vertices = VertexReader::new(header).unwrap();
+  let mut iter = vertices.iter::<DefaultConfig>();
+  while let Some(Ok(_)) = iter.next() {}
+  assert!(matches!(
+    vertices.next::<DefaultConfig>(),
+    Err(Error::Poisoned)
+  ));
+  assert!(matches!(SurfaceReader::new(vertices), Err(Error::Poisoned)));
+}
+
+#[test]
+fn error_location() {
+  let mut bytes = sample();
+  // header takes 71 bytes, then the vertex count and two vertices of 55 and 61 bytes
+  let third_vertex = 71 + 4 + 55 + 61;
+  // weight type follows position, normal, uv and one additional vec4
+  bytes[third_vertex + 48] = 9;
+  let error = read_all(&bytes).unwrap_err();
+  assert_eq!(
+    error.location(),
+    Some(&Location {
+      section: Section::Vertices,
+      element: Some(2),
+      offset: third_vertex as u64,
+    })
+  );
+  assert!(matches!(error.inner(), Error::UnknownWeightType(9)));
+  assert_eq!(
+    error.to_string(),
+    "Unknown weigh type 9 in vertices #2 at byte 191"
+  );
+}
@@ -0,0 +1,78 @@
+mod common;
+
+use common::*;
+use mmd::{DefaultConfig, Error, PmxIndex, Section};
+use std::io::{Cursor, Seek, SeekFrom};
+
+fn names(index: &PmxIndex, read: &mut Cursor<Vec<u8>>) -> (String, String, Vec<String>) {
+  let material = index.material::<DefaultConfig, _>(read, 1).unwrap();
+  let bone = index.bone::<DefaultConfig, _>(read, 1).unwrap();
+  let morphs = index
+    .morphs_range::<DefaultConfig, _>(read, 2..5)
+    .unwrap()
+    .into_iter()
+    .map(|m| m.universal_name)
+    .collect();
+  (material.universal_name, bone.universal_name, morphs)
+}
+
+#[test]
+fn sections() {
+  let index = PmxIndex::new(Cursor::new(sample()), false).unwrap();
+  assert_eq!(index.vertices.offset, 75);
+  assert_eq!(index.vertices.count, 5);
+  assert_eq!(index.surfaces.count, 3);
+  assert_eq!(index.textures.count, 1);
+  assert_eq!(index.materials.count, 2);
+  assert_eq!(index.bones.count, 2);
+  assert_eq!(index.morphs.count, 6);
+  assert_eq!(index.display_frames.count, 1);
+  assert_eq!(index.rigid_bodies.count, 1);
+  assert_eq!(index.joints.count, 1);
+  assert_eq!(index.soft_bodies.count, 1);
+  assert_eq!(index.bones.elements, None);
+}
+
+#[test]
+fn random_access() {
+  let expected = (
+    "material2".to_string(),
+    "leg IK".to_string(),
+    vec!["bone".to_string(), "uv".to_string(), "material".to_string()],
+  );
+
+  let mut read = Cursor::new(sample());
+  let scanning = PmxIndex::new(&mut read, false).unwrap();
+  assert_eq!(names(&scanning, &mut read), expected);
+
+  read.seek(SeekFrom::Start(0)).unwrap();
+  let indexed = PmxIndex::new(&mut read, true).unwrap();
+  assert_eq!(indexed.morphs.elements.as_ref().map(Vec::len), Some(6));
+  assert_eq!(names(&indexed, &mut read), expected);
+}
+
+#[test]
+fn offsets_are_absolute() {
+  let mut bytes = vec![0xaa; 16];
+  bytes.extend(sample());
+  let mut read = Cursor::new(bytes);
+  read.seek(SeekFrom::Start(16)).unwrap();
+  let index = PmxIndex::new(&mut read, true).unwrap();
+  assert_eq!(index.vertices.offset, 16 + 75);
+  let bone = index.bone::<DefaultConfig, _>(&mut read, 0).unwrap();
+  assert_eq!(bone.universal_name, "center");
+}
+
+#[test]
+fn out_of_range() {
+  let mut read = Cursor::new(sample());
+  let index = PmxIndex::new(&mut read, true).unwrap();
+  assert!(matches!(
+    index.bone::<DefaultConfig, _>(&mut read, 2),
+    Err(Error::ElementOutOfRange {
+      section: Section::Bones,
+      index: 2,
+      count: 2
+    })
+  ));
+}
@@ -0,0 +1,78 @@
+mod common;
+
+use common::*;
+use mmd::{Error, ParseLimits};
+
+fn exceeded(limits: ParseLimits) -> Option<(&'static str, u64, u64)> {
+  match read_all_with_limits(&sample(), limits) {
+    Err(e) => match *e.inner() {
+      Error::LimitExceeded {
+        what,
+        requested,
+        limit,
+      } => Some((what, requested, limit)),
+      _ => None,
+    },
+    Ok(()) => None,
+  }
+}
+
+#[test]
+fn default_limits_accept_sample() {
+  read_all_with_limits(&sample(), ParseLimits::default()).unwrap();
+}
+
+#[test]
+fn text_bytes() {
+  let limits = ParseLimits {
+    max_text_bytes: 4,
+    ..Default::default()
+  };
+  assert_eq!(exceeded(limits), Some(("text bytes", 6, 4)));
+}
+
+#[test]
+fn vertices() {
+  let limits = ParseLimits {
+    max_vertices: 4,
+    ..Default::default()
+  };
+  assert_eq!(exceeded(limits), Some(("vertices", 5, 4)));
+}
+
+#[test]
+fn morph_offsets() {
+  let limits = ParseLimits {
+    max_morph_offsets: 1,
+    ..Default::default()
+  };
+  assert_eq!(exceeded(limits), Some(("morph offsets", 2, 1)));
+}
+
+#[test]
+fn ik_links() {
+  let limits = ParseLimits {
+    max_ik_links: 1,
+    ..Default::default()
+  };
+  assert_eq!(exceeded(limits), Some(("ik links", 2, 1)));
+}
+
+#[test]
+fn total_bytes() {
+  let sample = sample();
+  let exact = ParseLimits {
+    max_total_bytes: sample.len() as u64,
+    ..Default::default()
+  };
+  read_all_with_limits(&sample, exact).unwrap();
+
+  let limits = ParseLimits {
+    max_total_bytes: sample.len() as u64 - 1,
+    ..Default::default()
+  };
+  let (what, requested, limit) = exceeded(limits).unwrap();
+  assert_eq!(what, "total bytes");
+  assert!(requested > limit);
+  assert_eq!(limit, sample.len() as u64 - 1);
+}
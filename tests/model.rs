@@ -0,0 +1,35 @@
+mod common;
+
+use common::*;
+use mmd::pmx::morph::Offsets;
+use mmd::{DefaultConfig, Model, WeightDeform};
+
+#[test]
+fn read() {
+  let model = Model::<DefaultConfig>::read(sample().as_slice()).unwrap();
+  assert_eq!(model.model_local_name, "モデル");
+  assert_eq!(model.universal_comments, "comment");
+  assert_eq!(model.vertices.len(), 5);
+  assert!(matches!(
+    model.vertices[3].weight_deform,
+    WeightDeform::Sdef(_)
+  ));
+  assert_eq!(model.surfaces, [[0, 1, 2]]);
+  assert_eq!(model.textures, ["tex.png"]);
+  assert_eq!(model.materials[1].metadata, "memo");
+  assert_eq!(model.bones[1].universal_name, "leg IK");
+  assert!(matches!(model.morphs[1].offsets, Offsets::Vertex(ref v) if v.len() == 2));
+  assert_eq!(model.display_frames[0].elements.len(), 2);
+  assert_eq!(model.rigid_bodies[0].universal_name, "body");
+  assert_eq!(model.joints[0].universal_name, "joint");
+  assert_eq!(model.soft_bodies[0].anchors.len(), 1);
+}
+
+#[test]
+fn open() {
+  let path = std::env::temp_dir().join("mmd-model-open.pmx");
+  std::fs::write(&path, sample()).unwrap();
+  let model = Model::<DefaultConfig>::open(&path);
+  std::fs::remove_file(&path).unwrap();
+  assert_eq!(model.unwrap().bones.len(), 2);
+}
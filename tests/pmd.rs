@@ -0,0 +1,194 @@
+mod common;
+
+use common::*;
+use mmd::pmd::{self, BoneKind};
+use mmd::pmx::bone::{BoneFlags, Connection};
+use mmd::pmx::display_frame::FrameElement;
+use mmd::pmx::joint::JointType;
+use mmd::pmx::material::{DrawingFlags, EnvironmentBlendMode, Toon};
+use mmd::pmx::morph::{Offsets, Panel};
+use mmd::pmx::rigid_body::ShapeType;
+use mmd::pmx::weight_deform::{Bdef1, Bdef2};
+use mmd::{DefaultConfig, Error, Location, Model, Section, WeightDeform};
+
+type C = DefaultConfig;
+
+#[test]
+fn reads_sample() {
+  let model = pmd::Model::<C>::read(pmd_sample(true).as_slice()).unwrap();
+  assert_eq!(model.local_name, "テスト");
+  assert_eq!(model.universal_name, "test");
+  assert_eq!(model.local_comment, "コメント");
+
+  assert_eq!(model.vertices.len(), 3);
+  assert_eq!(model.vertices[2].bones, [2, -1]);
+  assert_eq!(model.vertices[0].weight, 100);
+  assert!(model.vertices[0].edge);
+  assert_eq!(model.surfaces, vec![[0, 1, 2]]);
+
+  let material = &model.materials[0];
+  assert_eq!(material.toon, Some(0));
+  assert_eq!(material.surface_count, 3);
+  assert_eq!(material.texture, "tex.bmp*sph.sph");
+
+  assert_eq!(model.bones[0].parent, -1);
+  assert_eq!(model.bones[1].local_name, "右足ＩＫ");
+  assert_eq!(model.bones[1].universal_name, "leg IK_R");
+  assert_eq!(model.bones[2].local_name, "右足首_先端親指人差");
+  assert_eq!(model.bones[2].kind, BoneKind::IkInfluenced);
+  assert_eq!(model.bones[2].ik_parent, 1);
+  assert_eq!(model.iks[0].bone, 1);
+  assert_eq!(model.iks[0].target, 2);
+  assert_eq!(model.iks[0].iterations, 40);
+  assert_eq!(model.iks[0].chain, vec![0]);
+
+  assert_eq!(model.morphs[0].panel, Panel::Hidden);
+  assert_eq!(model.morphs[0].universal_name, "");
+  assert_eq!(model.morphs[1].panel, Panel::Eyes);
+  assert_eq!(model.morphs[1].universal_name, "blink");
+  assert_eq!(model.morphs[1].offsets[0].vertex, 1);
+
+  assert_eq!(model.morph_display, vec![1]);
+  assert_eq!(model.bone_frames[0].local_name, "足\n");
+  assert_eq!(model.bone_frames[0].universal_name, "legs");
+  assert_eq!(model.bone_display[0].bone, 2);
+  assert_eq!(model.bone_display[0].frame, 1);
+
+  assert_eq!(model.toon_textures.len(), 10);
+  assert_eq!(model.toon_textures[9], "toon10.bmp");
+  assert_eq!(model.rigid_bodies[0].shape, ShapeType::Box);
+  assert_eq!(model.rigid_bodies[0].non_collision_mask, 0xFFFE);
+  assert_eq!(model.joints[0].local_name, "joint");
+}
+
+#[test]
+fn optional_sections() {
+  let model = pmd::Model::<C>::read(pmd_sample(false).as_slice()).unwrap();
+  assert_eq!(model.universal_name, "");
+  assert_eq!(model.bones[1].universal_name, "");
+  assert!(model.toon_textures.is_empty());
+  assert!(model.rigid_bodies.is_empty());
+  assert!(model.joints.is_empty());
+}
+
+#[test]
+fn truncated() {
+  let sample = pmd_sample(true);
+  let required = pmd_sample(false).len();
+  for len in 0..required {
+    let result = pmd::Model::<C>::read(&sample[..len]);
+    assert!(result.is_err(), "truncated at {}", len);
+  }
+  // later sections may be missing entirely, so the file can end where each of them starts
+  let toons = sample.len() - 1000 - 4 - 83 - 4 - 124;
+  let ends = [toons, toons + 1000, toons + 1000 + 4 + 83];
+  for len in required + 1..sample.len() {
+    if !ends.contains(&len) {
+      let result = pmd::Model::<C>::read(&sample[..len]);
+      assert!(result.is_err(), "truncated at {}", len);
+    }
+  }
+}
+
+#[test]
+fn error_location() {
+  let mut bytes = pmd_sample(true);
+  let body = bytes.len() - 4 - 124 - 83;
+  // shape follows name, bone, group and mask
+  bytes[body + 25] = 7;
+  let error = pmd::Model::<C>::read(bytes.as_slice()).unwrap_err();
+  assert_eq!(
+    error.location(),
+    Some(&Location {
+      section: Section::RigidBodies,
+      element: Some(0),
+      offset: body as u64,
+    })
+  );
+  assert!(matches!(error.inner(), Error::InvalidRigidBodyShape(7)));
+}
+
+#[test]
+fn wrong_signature() {
+  let error = pmd::Model::<C>::read(sample().as_slice()).unwrap_err();
+  assert!(matches!(error.inner(), Error::WrongPmdSignature(magic) if magic == b"PMX"));
+}
+
+#[test]
+fn converts_to_pmx() {
+  let mut pmd = pmd::Model::<C>::read(pmd_sample(true).as_slice()).unwrap();
+  pmd.vertices[1].weight = 40;
+  let model = pmd.to_pmx().unwrap();
+
+  assert_eq!(model.model_universal_name, "test");
+  assert!(matches!(
+    model.vertices[0].weight_deform,
+    WeightDeform::Bdef1(Bdef1 { bone_index: 0 })
+  ));
+  assert!(matches!(
+    model.vertices[1].weight_deform,
+    WeightDeform::Bdef2(Bdef2 { bone_1_index: 1, bone_2_index: 0, bone_1_weight })
+      if bone_1_weight == 0.4
+  ));
+
+  assert_eq!(model.textures, vec!["tex.bmp", "sph.sph"]);
+  let material = &model.materials[0];
+  assert_eq!(material.texture_index, 0);
+  assert_eq!(material.environment_index, 1);
+  assert_eq!(
+    material.environment_blend_mode,
+    EnvironmentBlendMode::Multiply
+  );
+  assert_eq!(material.toon, Toon::Internal(0));
+  assert!(material.draw_flags.contains(DrawingFlags::HasEdge));
+
+  let center = &model.bones[0];
+  assert!(center
+    .bone_flags
+    .contains(BoneFlags::Movable | BoneFlags::Connection));
+  assert_eq!(center.connection, Connection::Index(1));
+  let ik = model.bones[1].inverse_kinematics.as_ref().unwrap();
+  assert!(model.bones[1]
+    .bone_flags
+    .contains(BoneFlags::InverseKinematics));
+  assert_eq!(ik.ik_bone, 2);
+  assert_eq!(ik.iterations, 40);
+  assert_eq!(ik.limit_angle, 2.0);
+  assert_eq!(ik.links[0].ik_bone, 0);
+  assert_eq!(ik.links[0].limits, None);
+
+  // the base morph is folded into the others
+  assert_eq!(model.morphs.len(), 1);
+  assert_eq!(model.morphs[0].universal_name, "blink");
+  match &model.morphs[0].offsets {
+    Offsets::Vertex(offsets) => assert_eq!(offsets[0].vertex, 1),
+    offsets => panic!("unexpected offsets {:?}", offsets),
+  }
+
+  let frames = &model.display_frames;
+  assert_eq!(frames[0].elements, vec![FrameElement::Bone(0)]);
+  assert_eq!(frames[1].elements, vec![FrameElement::Morph(0)]);
+  assert_eq!(frames[2].local_name, "足");
+  assert_eq!(frames[2].elements, vec![FrameElement::Bone(2)]);
+
+  assert_eq!(model.rigid_bodies[0].shape_position, [0.0, 1.0, 2.0].into());
+  assert_eq!(model.joints[0].joint_type, JointType::Spring6Dof);
+
+  let bytes = model.write(Vec::new()).unwrap();
+  assert_eq!(Model::<C>::read(bytes.as_slice()).unwrap(), model);
+}
+
+#[test]
+fn convert_errors() {
+  let mut pmd = pmd::Model::<C>::read(pmd_sample(true).as_slice()).unwrap();
+  pmd.morphs[1].offsets[0].vertex = 2;
+  let error = pmd.to_pmx().unwrap_err();
+  assert!(matches!(
+    error,
+    Error::ElementOutOfRange {
+      section: Section::Morphs,
+      index: 2,
+      count: 2
+    }
+  ));
+}
@@ -0,0 +1,449 @@
+mod common;
+
+use enumflags2::BitFlags;
+use mmd::pmx::bone::*;
+use mmd::pmx::display_frame::{DisplayFrame, FrameElement};
+use mmd::pmx::joint::JointType;
+use mmd::pmx::material::{DrawingFlags, EnvironmentBlendMode, Toon};
+use mmd::pmx::morph::*;
+use mmd::pmx::rigid_body::{PhysicsMode, ShapeType};
+use mmd::pmx::soft_body::*;
+use mmd::pmx::weight_deform::*;
+use mmd::{
+  DefaultConfig, IndexSize, Joint, Material, Model, RigidBody, Settings, SoftBody, TextEncoding,
+  Vertex, WeightDeform,
+};
+
+type C = DefaultConfig;
+
+fn vertex(weight_deform: WeightDeform<C>, additional: u8) -> Vertex<C> {
+  Vertex {
+    position: [0.5, -1.25, 3.0].into(),
+    normal: [0.0, 1.0, 0.0].into(),
+    uv: [0.25, 0.75].into(),
+    additional: (0..additional)
+      .map(|i| [i as f32, 1.0, 2.0, 3.0].into())
+      .collect(),
+    weight_deform,
+    edge_scale: 1.0,
+  }
+}
+
+fn material(name: &str, toon: Toon<C>) -> Material<C> {
+  Material {
+    local_name: name.to_string(),
+    universal_name: name.to_string(),
+    diffuse_color: [1.0, 0.5, 0.25, 1.0].into(),
+    specular_color: [0.1, 0.2, 0.3].into(),
+    specular_strength: 5.0,
+    ambient_color: [0.4, 0.5, 0.6].into(),
+    draw_flags: DrawingFlags::NoCull | DrawingFlags::HasEdge | DrawingFlags::LineDrawing,
+    edge_color: [0.0, 0.0, 0.0, 1.0].into(),
+    edge_scale: 0.5,
+    texture_index: 0,
+    environment_index: -1,
+    environment_blend_mode: EnvironmentBlendMode::Additive,
+    toon,
+    metadata: "メモ".to_string(),
+    surface_count: 3,
+  }
+}
+
+fn bone(name: &str, flags: BitFlags<BoneFlags>) -> Bone<C> {
+  Bone {
+    local_name: name.to_string(),
+    universal_name: name.to_string(),
+    position: [0.0, 10.0, 0.0].into(),
+    parent: -1,
+    transform_level: 0,
+    bone_flags: flags,
+    unknown_flags: 0,
+    connection: if flags.contains(BoneFlags::Connection) {
+      Connection::Index(1)
+    } else {
+      Connection::Position([0.0, 1.0, 0.0].into())
+    },
+    additional: flags
+      .intersects(BoneFlags::AddRotation | BoneFlags::AddMovement)
+      .then_some(Additional {
+        parent: 0,
+        rate: 0.5,
+      }),
+    fixed_axis: flags
+      .contains(BoneFlags::FixedAxis)
+      .then(|| [1.0, 0.0, 0.0].into()),
+    local_axis: flags.contains(BoneFlags::LocalAxis).then(|| LocalAxis {
+      x: [1.0, 0.0, 0.0].into(),
+      z: [0.0, 0.0, 1.0].into(),
+    }),
+    external_parent_transform: flags
+      .contains(BoneFlags::ExternalParentTransform)
+      .then_some(3),
+    inverse_kinematics: flags
+      .contains(BoneFlags::InverseKinematics)
+      .then(|| InverseKinematics {
+        ik_bone: 0,
+        iterations: 40,
+        limit_angle: 2.0,
+        links: vec![
+          IKLink {
+            ik_bone: 0,
+            limits: Some(([-1.0, 0.0, 0.0].into(), [1.0, 0.0, 0.0].into())),
+            raw_limits: 1,
+          },
+          IKLink {
+            ik_bone: 1,
+            limits: None,
+            raw_limits: 0,
+          },
+        ],
+      }),
+  }
+}
+
+fn morph(name: &str, panel: Panel, offsets: Offsets<C>) -> Morph<C> {
+  Morph {
+    local_name: name.to_string(),
+    universal_name: name.to_string(),
+    panel,
+    offsets,
+  }
+}
+
+fn uv_offsets() -> Vec<UVOffset<C>> {
+  vec![UVOffset {
+    vertex: 4,
+    offset: [0.1, 0.2, 0.3, 0.4].into(),
+  }]
+}
+
+fn synthetic(settings: Settings) -> Model<C> {
+  let additional = settings.additional_vec4_count;
+  let group = || {
+    vec![GroupOffset {
+      morph: 0,
+      influence: 0.5,
+    }]
+  };
+
+  Model {
+    version: 2.1,
+    settings,
+    extra_globals: Vec::new(),
+    model_local_name: "合成".to_string(),
+    model_universal_name: "synthetic".to_string(),
+    local_comments: "コメント\r\n".to_string(),
+    universal_comments: String::new(),
+    vertices: vec![
+      vertex(WeightDeform::Bdef1(Bdef1 { bone_index: 0 }), additional),
+      vertex(
+        WeightDeform::Bdef2(Bdef2 {
+          bone_1_index: 0,
+          bone_2_index: 1,
+          bone_1_weight: 0.25,
+        }),
+        additional,
+      ),
+      vertex(
+        WeightDeform::Bdef4(Bdef4 {
+          bone_1_index: 0,
+          bone_2_index: 1,
+          bone_3_index: 2,
+          bone_4_index: -1,
+          bone_1_weight: 0.1,
+          bone_2_weight: 0.2,
+          bone_3_weight: 0.3,
+          bone_4_weight: 0.4,
+        }),
+        additional,
+      ),
+      vertex(
+        WeightDeform::Sdef(Sdef {
+          bone_1_index: 1,
+          bone_2_index: 2,
+          bone_1_weight: 0.75,
+          c: [1.0, 2.0, 3.0].into(),
+          r0: [4.0, 5.0, 6.0].into(),
+          r1: [7.0, 8.0, 9.0].into(),
+        }),
+        additional,
+      ),
+      vertex(
+        WeightDeform::Qdef(Qdef {
+          bone_1_index: 2,
+          bone_2_index: 1,
+          bone_3_index: 0,
+          bone_4_index: -1,
+          bone_1_weight: 0.4,
+          bone_2_weight: 0.3,
+          bone_3_weight: 0.2,
+          bone_4_weight: 0.1,
+        }),
+        additional,
+      ),
+    ],
+    surfaces: vec![[0, 1, 2], [2, 3, 4]],
+    textures: vec!["tex.png".to_string(), "toon01.bmp".to_string()],
+    materials: vec![
+      material("texture toon", Toon::Texture(1)),
+      material("internal toon", Toon::Internal(9)),
+    ],
+    bones: vec![
+      bone("root", BoneFlags::Rotatable | BoneFlags::Movable),
+      bone(
+        "everything",
+        BitFlags::all() & !BitFlags::from(BoneFlags::Unknown6),
+      ),
+      bone("additional", BoneFlags::AddRotation | BoneFlags::Connection),
+    ],
+    morphs: vec![
+      morph("group", Panel::Hidden, Offsets::Group(group())),
+      morph(
+        "vertex",
+        Panel::Eyebrows,
+        Offsets::Vertex(vec![VertexOffset {
+          vertex: 1,
+          offset: [0.0, 0.5, 0.0].into(),
+        }]),
+      ),
+      morph(
+        "bone",
+        Panel::Eyes,
+        Offsets::Bone(vec![BoneOffset {
+          bone: 2,
+          translation: [1.0, 0.0, 0.0].into(),
+          rotation: [0.0, 0.0, 0.0, 1.0].into(),
+        }]),
+      ),
+      morph("uv", Panel::Mouth, Offsets::UV(uv_offsets())),
+      morph("uv1", Panel::Other, Offsets::AdditionalUV1(uv_offsets())),
+      morph("uv2", Panel::Other, Offsets::AdditionalUV2(uv_offsets())),
+      morph("uv3", Panel::Other, Offsets::AdditionalUV3(uv_offsets())),
+      morph("uv4", Panel::Other, Offsets::AdditionalUV4(uv_offsets())),
+      morph(
+        "material",
+        Panel::Other,
+        Offsets::Material(vec![MaterialOffset {
+          material: -1,
+          method: OffsetMethod::Additive,
+          diffuse_color: [0.1, 0.1, 0.1, 0.0].into(),
+          specular_color: [0.2, 0.2, 0.2].into(),
+          specular_strength: 1.0,
+          ambient_color: [0.3, 0.3, 0.3].into(),
+          edge_color: [0.4, 0.4, 0.4, 0.4].into(),
+          edge_scale: 2.0,
+          texture_tint: [1.0, 1.0, 1.0, 1.0].into(),
+          environment_tint: [0.5, 0.5, 0.5, 0.5].into(),
+          toon_tint: [0.0, 0.0, 0.0, 0.0].into(),
+        }]),
+      ),
+      morph("flip", Panel::Unknown(5), Offsets::Flip(group())),
+      morph(
+        "impulse",
+        Panel::Unknown(255),
+        Offsets::Impulse(vec![ImpulseOffset {
+          rigid_body: 0,
+          local: true,
+          raw_local: 1,
+          velocity: [0.0, 0.0, 1.0].into(),
+          torque: [0.0, 1.0, 0.0].into(),
+        }]),
+      ),
+    ],
+    display_frames: vec![DisplayFrame {
+      local_name: "Root".to_string(),
+      universal_name: "Root".to_string(),
+      special: true,
+      raw_special: 1,
+      elements: vec![FrameElement::Bone(0), FrameElement::Morph(10)],
+    }],
+    rigid_bodies: vec![RigidBody {
+      local_name: "剛体".to_string(),
+      universal_name: "body".to_string(),
+      bone_index: 1,
+      group: 3,
+      non_collision_mask: 0xfff0,
+      shape: ShapeType::Capsule,
+      shape_size: [1.0, 2.0, 0.0].into(),
+      shape_position: [0.0, 10.0, 0.0].into(),
+      shape_rotation: [0.0, 0.0, 1.5].into(),
+      mass: 1.0,
+      move_attenuation: 0.5,
+      rotation_damping: 0.5,
+      repulsion: 0.0,
+      friction: 0.5,
+      physics_mode: PhysicsMode::PhysicsWithBone,
+    }],
+    joints: vec![Joint {
+      local_name: "ジョイント".to_string(),
+      universal_name: "joint".to_string(),
+      joint_type: JointType::ConeTwist,
+      rigid_body_a: 0,
+      rigid_body_b: -1,
+      position: [0.0, 10.0, 0.0].into(),
+      rotation: [0.0, 0.0, 0.0].into(),
+      position_min: [-1.0, -1.0, -1.0].into(),
+      position_max: [1.0, 1.0, 1.0].into(),
+      rotation_min: [-0.5, -0.5, -0.5].into(),
+      rotation_max: [0.5, 0.5, 0.5].into(),
+      position_spring: [0.0, 0.0, 0.0].into(),
+      rotation_spring: [10.0, 10.0, 10.0].into(),
+    }],
+    soft_bodies: vec![SoftBody {
+      local_name: "布".to_string(),
+      universal_name: "cloth".to_string(),
+      shape: SoftBodyShape::Rope,
+      material_index: 1,
+      group: 2,
+      non_collision_mask: 0x00ff,
+      flags: SoftBodyFlags::BLink | SoftBodyFlags::LinkCrossing,
+      unknown_flags: 0,
+      b_link_distance: 2,
+      cluster_count: 0,
+      total_mass: 1.5,
+      collision_margin: 0.05,
+      aero_model: AeroModel::FaceOneSided,
+      coefficients: SoftBodyCoefficients {
+        velocity_correction: 1.0,
+        damping: 0.1,
+        drag: 0.2,
+        lift: 0.3,
+        pressure: 0.4,
+        volume_conservation: 0.5,
+        dynamic_friction: 0.6,
+        pose_matching: 0.7,
+        rigid_contact_hardness: 0.8,
+        kinetic_contact_hardness: 0.9,
+        soft_contact_hardness: 1.0,
+        anchor_hardness: 1.1,
+      },
+      cluster: SoftBodyCluster {
+        soft_rigid_hardness: 0.1,
+        soft_kinetic_hardness: 0.2,
+        soft_soft_hardness: 0.3,
+        soft_rigid_impulse_split: 0.4,
+        soft_kinetic_impulse_split: 0.5,
+        soft_soft_impulse_split: 0.6,
+      },
+      iterations: SoftBodyIterations {
+        velocity: 1,
+        position: 2,
+        drift: 3,
+        cluster: 4,
+      },
+      material: SoftBodyMaterial {
+        linear_stiffness: 1.0,
+        area_stiffness: 0.5,
+        volume_stiffness: 0.25,
+      },
+      anchors: vec![SoftBodyAnchor {
+        rigid_body: 0,
+        vertex: 3,
+        near_mode: true,
+        raw_near_mode: 1,
+      }],
+      pin_vertices: vec![0, 4],
+    }],
+  }
+}
+
+fn settings(text_encoding: TextEncoding, index_size: IndexSize, additional: u8) -> Settings {
+  Settings {
+    text_encoding,
+    additional_vec4_count: additional,
+    vertex_index_size: index_size,
+    texture_index_size: index_size,
+    material_index_size: index_size,
+    bone_index_size: index_size,
+    morph_index_size: index_size,
+    rigidbody_index_size: index_size,
+  }
+}
+
+fn assert_round_trip(bytes: &[u8]) -> Model<C> {
+  let model = Model::<C>::read(bytes).unwrap();
+  assert_eq!(model.write(Vec::new()).unwrap(), bytes);
+  model
+}
+
+#[test]
+fn synthetic_models() {
+  for &encoding in &[TextEncoding::UTF16LE, TextEncoding::UTF8] {
+    for &index_size in &[IndexSize::I8, IndexSize::I16, IndexSize::I32] {
+      for additional in 0..=4 {
+        let bytes = synthetic(settings(encoding, index_size, additional))
+          .write(Vec::new())
+          .unwrap();
+        let model = assert_round_trip(&bytes);
+        assert_eq!(model.settings.vertex_index_size, index_size);
+        assert_eq!(model.morphs[10].panel, Panel::Unknown(255));
+        assert!(matches!(model.materials[1].toon, Toon::Internal(9)));
+      }
+    }
+  }
+}
+
+#[test]
+fn sample() {
+  assert_round_trip(&common::sample());
+}
+
+#[test]
+fn extra_globals() {
+  let mut bytes = common::sample();
+  // the globals count follows the signature and version, globals end at byte 17
+  bytes[8] = 10;
+  bytes.splice(17..17, [0xab, 0xcd].iter().cloned());
+  let model = assert_round_trip(&bytes);
+  assert_eq!(model.extra_globals, [0xab, 0xcd]);
+}
+
+#[test]
+fn unknown_flags_and_float_bits() {
+  let mut model = synthetic(settings(TextEncoding::UTF16LE, IndexSize::I16, 1));
+  let nan = f32::from_bits(0x7fc0_1234);
+  model.version = f32::from_bits(2.1f32.to_bits() + 1);
+  model.vertices[0].position = [nan, -0.0, f32::MIN_POSITIVE / 2.0].into();
+  model.bones[0].unknown_flags = 0b1100_0000_0000_0000;
+  model.soft_bodies[0].unknown_flags = 0b1000_0000;
+  // editors may store any non zero byte for a set flag
+  let ik = model
+    .bones
+    .iter_mut()
+    .find_map(|b| b.inverse_kinematics.as_mut());
+  ik.unwrap().links[0].raw_limits = 2;
+  model.display_frames[0].raw_special = 0xff;
+  let impulse = model.morphs.iter_mut().find_map(|m| match m.offsets {
+    Offsets::Impulse(ref mut offsets) => Some(offsets),
+    _ => None,
+  });
+  impulse.unwrap()[0].raw_local = 7;
+  model.soft_bodies[0].anchors[0].raw_near_mode = 0x80;
+
+  let bytes = model.write(Vec::new()).unwrap();
+  let read = assert_round_trip(&bytes);
+  assert_eq!(read.version.to_bits(), model.version.to_bits());
+  assert_eq!(read.vertices[0].position.x.to_bits(), 0x7fc0_1234);
+  assert_eq!(read.vertices[0].position.y.to_bits(), (-0.0f32).to_bits());
+  assert_eq!(read.bones[0].unknown_flags, 0b1100_0000_0000_0000);
+  assert_eq!(read.soft_bodies[0].unknown_flags, 0b1000_0000);
+  assert_eq!(read.display_frames[0].raw_special, 0xff);
+  assert!(read.display_frames[0].special);
+  assert_eq!(read.soft_bodies[0].anchors[0].raw_near_mode, 0x80);
+  let ik = read
+    .bones
+    .iter()
+    .find_map(|b| b.inverse_kinematics.as_ref());
+  assert_eq!(ik.unwrap().links[0].raw_limits, 2);
+  assert!(ik.unwrap().links[0].limits.is_some());
+  assert!(read.morphs.iter().any(|m| match m.offsets {
+    Offsets::Impulse(ref offsets) => offsets[0].raw_local == 7 && offsets[0].local,
+    _ => false,
+  }));
+
+  // a flag changed after reading is written as 0 or 1 again
+  let mut changed = read.clone();
+  changed.display_frames[0].special = false;
+  let changed = Model::<C>::read(changed.write(Vec::new()).unwrap().as_slice()).unwrap();
+  assert_eq!(changed.display_frames[0].raw_special, 0);
+}
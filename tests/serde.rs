@@ -0,0 +1,25 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+use common::*;
+use mmd::{DefaultConfig, Model, Settings};
+
+#[test]
+fn model_json_round_trip() {
+  let model = Model::<DefaultConfig>::read(sample().as_slice()).unwrap();
+  let json = serde_json::to_string(&model).unwrap();
+  let deserialized: Model<DefaultConfig> = serde_json::from_str(&json).unwrap();
+  assert_eq!(deserialized, model);
+  assert_eq!(deserialized.write(Vec::new()).unwrap(), sample());
+}
+
+#[test]
+fn settings_json() {
+  let model = Model::<DefaultConfig>::read(sample().as_slice()).unwrap();
+  let json = serde_json::to_value(model.settings).unwrap();
+  assert_eq!(json["text_encoding"], "UTF16LE");
+  assert_eq!(json["vertex_index_size"], "I16");
+  let settings: Settings = serde_json::from_value(json).unwrap();
+  assert_eq!(settings, model.settings);
+}
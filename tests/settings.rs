@@ -0,0 +1,64 @@
+mod common;
+
+use common::*;
+use mmd::{DefaultConfig, IndexSize, Model, TextEncoding};
+
+fn sample_model() -> Model<DefaultConfig> {
+  Model::read(sample().as_slice()).unwrap()
+}
+
+#[test]
+fn index_size_for_count() {
+  assert_eq!(IndexSize::for_count(0), IndexSize::I8);
+  assert_eq!(IndexSize::for_count(128), IndexSize::I8);
+  assert_eq!(IndexSize::for_count(129), IndexSize::I16);
+  assert_eq!(IndexSize::for_count(32768), IndexSize::I16);
+  assert_eq!(IndexSize::for_count(32769), IndexSize::I32);
+
+  assert_eq!(IndexSize::for_vertex_count(256), IndexSize::I8);
+  assert_eq!(IndexSize::for_vertex_count(257), IndexSize::I16);
+  assert_eq!(IndexSize::for_vertex_count(65536), IndexSize::I16);
+  assert_eq!(IndexSize::for_vertex_count(65537), IndexSize::I32);
+}
+
+#[test]
+fn encoded_len() {
+  assert_eq!(TextEncoding::UTF8.encoded_len("center"), 6);
+  assert_eq!(TextEncoding::UTF16LE.encoded_len("center"), 12);
+  assert_eq!(TextEncoding::UTF8.encoded_len("センター"), 12);
+  assert_eq!(TextEncoding::UTF16LE.encoded_len("センター"), 8);
+}
+
+#[test]
+fn optimal_settings() {
+  let mut model = sample_model();
+  let settings = model.optimal_settings();
+  assert_eq!(settings.additional_vec4_count, 1);
+  assert_eq!(settings.vertex_index_size, IndexSize::I8);
+  assert_eq!(settings.bone_index_size, IndexSize::I8);
+  assert_eq!(settings.rigidbody_index_size, IndexSize::I8);
+  // the sample is mostly ascii
+  assert_eq!(settings.text_encoding, TextEncoding::UTF8);
+
+  model.optimize();
+  let bytes = model.write(Vec::new()).unwrap();
+  assert!(bytes.len() < sample().len());
+
+  let optimized = Model::<DefaultConfig>::read(bytes.as_slice()).unwrap();
+  assert_eq!(optimized.settings.bone_index_size, IndexSize::I8);
+  assert_eq!(optimized.bones[1].local_name, "足ＩＫ");
+  assert_eq!(optimized.surfaces, [[0, 1, 2]]);
+}
+
+#[test]
+fn reencode() {
+  let mut model = sample_model();
+  model.reencode(TextEncoding::UTF8);
+  let utf8 = model.write(Vec::new()).unwrap();
+
+  let mut model = Model::<DefaultConfig>::read(utf8.as_slice()).unwrap();
+  assert_eq!(model.settings.text_encoding, TextEncoding::UTF8);
+  assert_eq!(model.model_local_name, "モデル");
+  model.reencode(TextEncoding::UTF16LE);
+  assert_eq!(model.write(Vec::new()).unwrap(), sample());
+}
@@ -0,0 +1,136 @@
+mod common;
+
+use common::*;
+use mmd::pmx::reader::*;
+use mmd::DefaultConfig;
+use std::io::Cursor;
+
+fn long_texture() -> String {
+  "a".repeat(5000)
+}
+
+#[test]
+fn skip_odd_sections() {
+  let texture = long_texture();
+  let bytes = sample_with_texture(&texture);
+  let header = HeaderReader::new(Cursor::new(bytes)).unwrap();
+
+  let mut vertices = VertexReader::new(header).unwrap();
+  vertices.skip().unwrap();
+
+  let mut surfaces = SurfaceReader::new(vertices).unwrap();
+  assert_eq!(surfaces.next::<DefaultConfig>().unwrap(), Some([0, 1, 2]));
+
+  let mut textures = TextureReader::new(surfaces).unwrap();
+  textures.skip().unwrap();
+
+  let mut materials = MaterialReader::new(textures).unwrap();
+  let names = materials
+    .iter::<DefaultConfig>()
+    .map(|m| m.unwrap().local_name)
+    .collect::<Vec<_>>();
+  assert_eq!(names, ["材質", "材質2"]);
+
+  let mut bones = BoneReader::new(materials).unwrap();
+  bones.skip().unwrap();
+
+  let mut morphs = MorphReader::new(bones).unwrap();
+  let names = morphs
+    .iter::<DefaultConfig>()
+    .map(|m| m.unwrap().universal_name)
+    .collect::<Vec<_>>();
+  assert_eq!(
+    names,
+    ["group", "vertex", "bone", "uv", "material", "impulse"]
+  );
+
+  let mut display_frames = DisplayFrameReader::new(morphs).unwrap();
+  display_frames.skip().unwrap();
+
+  let mut rigid_bodies = RigidBodyReader::new(display_frames).unwrap();
+  let rigid_body = rigid_bodies.next::<DefaultConfig>().unwrap().unwrap();
+  assert_eq!(rigid_body.universal_name, "body");
+
+  let mut joints = JointReader::new(rigid_bodies).unwrap();
+  joints.skip().unwrap();
+
+  let mut soft_bodies = SoftBodyReader::new(joints).unwrap();
+  let soft_body = soft_bodies.next::<DefaultConfig>().unwrap().unwrap();
+  assert_eq!(soft_body.universal_name, "soft");
+  assert_eq!(soft_body.pin_vertices, [0, 2]);
+}
+
+#[test]
+fn skip_even_sections() {
+  let texture = long_texture();
+  let bytes = sample_with_texture(&texture);
+  let header = HeaderReader::new(Cursor::new(bytes)).unwrap();
+
+  let mut vertices = VertexReader::new(header).unwrap();
+  assert_eq!(vertices.iter::<DefaultConfig>().count(), 5);
+
+  let mut surfaces = SurfaceReader::new(vertices).unwrap();
+  surfaces.skip().unwrap();
+
+  let mut textures = TextureReader::new(surfaces).unwrap();
+  assert_eq!(textures.next().unwrap(), Some(texture));
+
+  let mut materials = MaterialReader::new(textures).unwrap();
+  materials.skip().unwrap();
+
+  let mut bones = BoneReader::new(materials).unwrap();
+  let names = bones
+    .iter::<DefaultConfig>()
+    .map(|b| b.unwrap().universal_name)
+    .collect::<Vec<_>>();
+  assert_eq!(names, ["center", "leg IK"]);
+
+  let mut morphs = MorphReader::new(bones).unwrap();
+  morphs.skip().unwrap();
+
+  let mut display_frames = DisplayFrameReader::new(morphs).unwrap();
+  let frame = display_frames.next::<DefaultConfig>().unwrap().unwrap();
+  assert_eq!(frame.elements.len(), 2);
+
+  let mut rigid_bodies = RigidBodyReader::new(display_frames).unwrap();
+  rigid_bodies.skip().unwrap();
+
+  let mut joints = JointReader::new(rigid_bodies).unwrap();
+  let joint = joints.next::<DefaultConfig>().unwrap().unwrap();
+  assert_eq!(joint.universal_name, "joint");
+
+  let soft_bodies = SoftBodyReader::new(joints).unwrap();
+  assert_eq!(soft_bodies.count, 1);
+}
+
+#[test]
+fn skip_truncated() {
+  let bytes = sample();
+  for len in 0..bytes.len() {
+    let read = || -> mmd::Result<()> {
+      let header = HeaderReader::new(Cursor::new(&bytes[..len]))?;
+      let mut vertices = VertexReader::new(header)?;
+      vertices.skip()?;
+      let mut surfaces = SurfaceReader::new(vertices)?;
+      surfaces.skip()?;
+      let mut textures = TextureReader::new(surfaces)?;
+      textures.skip()?;
+      let mut materials = MaterialReader::new(textures)?;
+      materials.skip()?;
+      let mut bones = BoneReader::new(materials)?;
+      bones.skip()?;
+      let mut morphs = MorphReader::new(bones)?;
+      morphs.skip()?;
+      let mut display_frames = DisplayFrameReader::new(morphs)?;
+      display_frames.skip()?;
+      let mut rigid_bodies = RigidBodyReader::new(display_frames)?;
+      rigid_bodies.skip()?;
+      let mut joints = JointReader::new(rigid_bodies)?;
+      joints.skip()?;
+      let mut soft_bodies = SoftBodyReader::new(joints)?;
+      soft_bodies.next::<DefaultConfig>()?;
+      Ok(())
+    };
+    assert!(read().is_err(), "truncated at {}", len);
+  }
+}
@@ -0,0 +1,69 @@
+#![cfg(feature = "json")]
+
+mod common;
+
+use common::*;
+use mmd::pmx::text;
+use mmd::{DefaultConfig, Model};
+
+#[test]
+fn sample_round_trips() {
+  let mut json = Vec::new();
+  text::pmx_to_text(sample().as_slice(), &mut json).unwrap();
+  let json = String::from_utf8(json).unwrap();
+  assert!(json.contains("\"model_local_name\": \"モデル\""));
+  assert_eq!(
+    text::text_to_pmx(json.as_bytes(), Vec::new()).unwrap(),
+    sample()
+  );
+}
+
+#[test]
+fn special_floats_and_look_alike_strings() {
+  let mut model = Model::<DefaultConfig>::read(sample().as_slice()).unwrap();
+  model.vertices[0].position = [f32::from_bits(0x7fc0_1234), f32::INFINITY, -0.0].into();
+  model.vertices[0].normal = [f32::NAN, f32::NEG_INFINITY, f32::MIN_POSITIVE / 3.0].into();
+  model.bones[0].local_name = "NaN".to_string();
+  model.bones[1].local_name = "inf".to_string();
+  let bytes = model.write(Vec::new()).unwrap();
+
+  let json = text::to_string(&model).unwrap();
+  assert!(json.contains("\"NaN:7fc01234\""));
+  let parsed = text::from_str::<DefaultConfig>(&json).unwrap();
+  assert_eq!(parsed.bones[0].local_name, "NaN");
+  assert_eq!(parsed.vertices[0].normal.x.to_bits(), f32::NAN.to_bits());
+  assert_eq!(parsed.write(Vec::new()).unwrap(), bytes);
+}
+
+#[test]
+fn random_float_bits() {
+  let mut model = Model::<DefaultConfig>::read(sample().as_slice()).unwrap();
+  let template = model.vertices[0].clone();
+  let mut state = 0x1234_5678u32;
+  let mut next = || {
+    state ^= state << 13;
+    state ^= state >> 17;
+    state ^= state << 5;
+    f32::from_bits(state)
+  };
+  model.vertices = (0..4000)
+    .map(|_| {
+      let mut vertex = template.clone();
+      vertex.position = [next(), next(), next()].into();
+      vertex.uv = [next(), next()].into();
+      vertex.edge_scale = next();
+      vertex
+    })
+    .collect();
+  let bytes = model.write(Vec::new()).unwrap();
+  let parsed = text::from_str::<DefaultConfig>(&text::to_string(&model).unwrap()).unwrap();
+  assert_eq!(parsed.write(Vec::new()).unwrap(), bytes);
+}
+
+#[test]
+fn invalid_text() {
+  assert!(matches!(
+    text::from_str::<DefaultConfig>("{\"version\": 2.0}"),
+    Err(mmd::Error::Text(_))
+  ));
+}
@@ -0,0 +1,227 @@
+mod common;
+
+use common::*;
+use mmd::{
+  DefaultConfig, Error, IndexSize, Location, Model, ModelView, ParseLimits, Section, TextEncoding,
+};
+
+type C = DefaultConfig;
+
+#[test]
+fn matches_reader() {
+  let bytes = sample();
+  let model = Model::<C>::read(bytes.as_slice()).unwrap();
+  let view = ModelView::new(&bytes).unwrap();
+
+  assert_eq!(view.version, model.version);
+  assert_eq!(view.settings, model.settings);
+  assert_eq!(view.model_local_name, "モデル");
+  assert_eq!(view.universal_comments.decode().unwrap(), "comment");
+  assert_eq!(view.model_local_name.as_str(), None);
+
+  assert_eq!(view.vertices.len(), model.vertices.len());
+  for (vertex, expected) in view.vertices.iter().zip(&model.vertices) {
+    assert_eq!(vertex.read::<C>().unwrap(), *expected);
+    assert_eq!(vertex.position::<C>(), expected.position);
+    assert_eq!(vertex.uv::<C>(), expected.uv);
+    assert_eq!(vertex.additional::<C>(0), Some(expected.additional[0]));
+    assert_eq!(vertex.edge_scale(), expected.edge_scale);
+  }
+
+  assert_eq!(view.surfaces.index_size(), IndexSize::I16);
+  let surfaces = view.surfaces.iter().collect::<Vec<_>>();
+  let expected = model
+    .surfaces
+    .iter()
+    .map(|s| s.map(|v| v as u32))
+    .collect::<Vec<_>>();
+  assert_eq!(surfaces, expected);
+
+  let textures = view.textures.iter().map(|t| t.to_string());
+  assert!(textures.eq(model.textures.iter().cloned()));
+
+  macro_rules! same_elements {
+    ($views:expr, $elements:expr) => {
+      assert_eq!($views.len(), $elements.len());
+      for (view, element) in $views.iter().zip(&$elements) {
+        assert_eq!(view.local_name(), element.local_name.as_str());
+        assert_eq!(view.universal_name(), element.universal_name.as_str());
+        assert_eq!(view.read::<C>().unwrap(), *element);
+      }
+    };
+  }
+  same_elements!(view.materials, model.materials);
+  same_elements!(view.bones, model.bones);
+  same_elements!(view.morphs, model.morphs);
+  same_elements!(view.display_frames, model.display_frames);
+  same_elements!(view.rigid_bodies, model.rigid_bodies);
+  same_elements!(view.joints, model.joints);
+  assert_eq!(view.bones.get(view.bones.len()).map(|_| ()), None);
+
+  let mut soft_bodies = view.soft_bodies().unwrap();
+  let soft_body_list = soft_bodies
+    .iter::<C>()
+    .collect::<Result<Vec<_>, _>>()
+    .unwrap();
+  assert_eq!(soft_body_list, model.soft_bodies);
+}
+
+#[test]
+fn borrows_utf8() {
+  let mut model = Model::<C>::read(sample().as_slice()).unwrap();
+  model.reencode(TextEncoding::UTF8);
+  let bytes = model.write(Vec::new()).unwrap();
+  let view = ModelView::new(&bytes).unwrap();
+  assert_eq!(view.model_local_name.as_str(), Some("モデル"));
+  assert_eq!(view.textures[0].as_str(), Some(model.textures[0].as_str()));
+  let bone = view.bones.get(1).unwrap();
+  assert_eq!(
+    bone.local_name().as_str(),
+    Some(model.bones[1].local_name.as_str())
+  );
+  assert_eq!(bone.read::<C>().unwrap(), model.bones[1]);
+  assert_eq!(
+    view.vertices.get(4).unwrap().read::<C>().unwrap(),
+    model.vertices[4]
+  );
+}
+
+fn read_all_views(bytes: &[u8]) -> Result<(), Error> {
+  let view = ModelView::new(bytes)?;
+  for vertex in view.vertices.iter() {
+    vertex.read::<C>()?;
+  }
+  for material in view.materials.iter() {
+    material.read::<C>()?;
+  }
+  for bone in view.bones.iter() {
+    bone.read::<C>()?;
+  }
+  for morph in view.morphs.iter() {
+    morph.read::<C>()?;
+  }
+  for frame in view.display_frames.iter() {
+    frame.read::<C>()?;
+  }
+  for body in view.rigid_bodies.iter() {
+    body.read::<C>()?;
+  }
+  for joint in view.joints.iter() {
+    joint.read::<C>()?;
+  }
+  let mut soft_bodies = view.soft_bodies()?;
+  soft_bodies.iter::<C>().collect::<Result<Vec<_>, _>>()?;
+  Ok(())
+}
+
+#[test]
+fn truncated() {
+  let sample = sample();
+  read_all_views(&sample).unwrap();
+  for len in 0..sample.len() {
+    assert!(
+      read_all_views(&sample[..len]).is_err(),
+      "truncated at {}",
+      len
+    );
+  }
+}
+
+#[test]
+fn error_location() {
+  let mut bytes = sample();
+  let third_vertex = 71 + 4 + 55 + 61;
+  bytes[third_vertex + 48] = 9;
+  let error = ModelView::new(&bytes).err().unwrap();
+  assert_eq!(
+    error.location(),
+    Some(&Location {
+      section: Section::Vertices,
+      element: Some(2),
+      offset: third_vertex as u64,
+    })
+  );
+  assert!(matches!(error.inner(), Error::UnknownWeightType(9)));
+}
+
+#[test]
+fn limits() {
+  let limits = ParseLimits {
+    max_vertices: 4,
+    ..Default::default()
+  };
+  let error = ModelView::with_limits(&sample(), limits).err().unwrap();
+  assert!(matches!(
+    error.inner(),
+    Error::LimitExceeded {
+      what: "vertices",
+      requested: 5,
+      limit: 4
+    }
+  ));
+}
+
+#[test]
+fn limits_match_reader() {
+  let bytes = sample();
+  for limits in [
+    ParseLimits {
+      max_ik_links: 0,
+      ..Default::default()
+    },
+    ParseLimits {
+      max_morph_offsets: 0,
+      ..Default::default()
+    },
+  ] {
+    let expected = Model::<C>::read_with_limits(bytes.as_slice(), limits)
+      .err()
+      .unwrap();
+    let error = ModelView::with_limits(&bytes, limits).err().unwrap();
+    assert_eq!(error.location(), expected.location());
+    assert!(matches!(error.inner(), Error::LimitExceeded { .. }));
+  }
+}
+
+#[test]
+fn name_limit() {
+  let mut model = Model::<C>::read(sample().as_slice()).unwrap();
+  model.bones[1].local_name = "x".repeat(200);
+  let bytes = model.write(Vec::new()).unwrap();
+  let limits = ParseLimits {
+    max_text_bytes: 399,
+    ..Default::default()
+  };
+  let expected = Model::<C>::read_with_limits(bytes.as_slice(), limits)
+    .err()
+    .unwrap();
+  let error = ModelView::with_limits(&bytes, limits).err().unwrap();
+  assert_eq!(error.location(), expected.location());
+  assert_eq!(error.location().unwrap().section, Section::Bones);
+  assert_eq!(error.location().unwrap().element, Some(1));
+  assert!(matches!(
+    error.inner(),
+    Error::LimitExceeded {
+      what: "text bytes",
+      requested: 400,
+      limit: 399
+    }
+  ));
+}
+
+#[test]
+fn huge_counts() {
+  let bytes = sample();
+  let view = ModelView::new(&bytes).unwrap();
+  let surfaces = view.surfaces.as_bytes();
+  let textures = surfaces.as_ptr() as usize - bytes.as_ptr() as usize + surfaces.len();
+  let materials =
+    view.materials.get(0).unwrap().as_bytes().as_ptr() as usize - bytes.as_ptr() as usize - 4;
+
+  for at in [textures, materials] {
+    let mut bytes = bytes.clone();
+    bytes[at..at + 4].copy_from_slice(&i32::MAX.to_le_bytes());
+    let error = ModelView::new(&bytes).err().unwrap();
+    assert!(matches!(error.inner(), Error::Io(_)));
+  }
+}
@@ -0,0 +1,191 @@
+mod common;
+
+use common::*;
+use mmd::vmd::{self, ShadowMode};
+use mmd::{DefaultConfig, Error, Location, Section};
+
+type C = DefaultConfig;
+
+#[test]
+fn reads_sample() {
+  let motion = vmd::Motion::<C>::read(vmd_sample(true).as_slice()).unwrap();
+  assert_eq!(motion.model_name, "テスト");
+
+  let bone = &motion.bones[0];
+  assert_eq!(bone.bone_name, "センター");
+  assert_eq!(bone.frame, 0);
+  assert_eq!(bone.translation, [0.0, 0.5, 1.0].into());
+  assert_eq!(bone.rotation, [0.0, 0.0, 0.0, 1.0].into());
+  assert_eq!(bone.interpolation[1][0], 1);
+  assert_eq!(bone.interpolation[3][15], 18);
+  assert_eq!(motion.bones[1].bone_name, "センター親指先");
+  assert_eq!(motion.bones[1].frame, 30);
+
+  assert_eq!(motion.morphs[0].morph_name, "まばたき");
+  assert_eq!(motion.morphs[0].frame, 10);
+  assert_eq!(motion.morphs[0].weight, 0.5);
+
+  let camera = &motion.cameras[0];
+  assert_eq!(camera.frame, 5);
+  assert_eq!(camera.distance, -45.0);
+  assert_eq!(camera.interpolation[23], 23);
+  assert_eq!(camera.fov, 30);
+  assert!(!camera.perspective);
+
+  assert_eq!(motion.lights[0].direction, [-0.5, -1.0, 0.5].into());
+  assert_eq!(motion.shadows[0].mode, ShadowMode::Mode1);
+  assert_eq!(motion.shadows[0].distance, 0.01);
+
+  let ik = &motion.iks[0];
+  assert!(ik.show);
+  assert_eq!(ik.iks[0].bone_name, "右足ＩＫ");
+  assert!(!ik.iks[0].enabled);
+  assert!(ik.iks[1].enabled);
+}
+
+#[test]
+fn optional_sections() {
+  let motion = vmd::Motion::<C>::read(vmd_sample(false).as_slice()).unwrap();
+  assert_eq!(motion.morphs.len(), 1);
+  assert!(motion.cameras.is_empty());
+  assert!(motion.lights.is_empty());
+  assert!(motion.shadows.is_empty());
+  assert!(motion.iks.is_empty());
+}
+
+#[test]
+fn legacy_header() {
+  let mut bytes = vmd_sample(false);
+  bytes[21..25].copy_from_slice(b"file");
+  // the old header has a 10 byte model name
+  bytes.drain(40..50);
+  let header = vmd::HeaderReader::new(bytes.as_slice()).unwrap();
+  assert_eq!(header.version, 1);
+  assert_eq!(header.model_name, "テスト");
+  let motion = vmd::Motion::<C>::read(bytes.as_slice()).unwrap();
+  assert_eq!(motion.bones.len(), 2);
+}
+
+#[test]
+fn truncated() {
+  let sample = vmd_sample(true);
+  let required = vmd_sample(false).len();
+  for len in 0..required {
+    let result = vmd::Motion::<C>::read(&sample[..len]);
+    assert!(result.is_err(), "truncated at {}", len);
+  }
+  // each section after the morphs may be missing entirely
+  let iks = sample.len() - 4 - 51;
+  let shadows = iks - 4 - 9;
+  let ends = [required, shadows - 4 - 28, shadows, iks];
+  for len in required + 1..sample.len() {
+    let result = vmd::Motion::<C>::read(&sample[..len]);
+    assert_eq!(result.is_ok(), ends.contains(&len), "truncated at {}", len);
+  }
+}
+
+#[test]
+fn error_location() {
+  let mut bytes = vmd_sample(true);
+  let ik = bytes.len() - 42 - 4 - 1 - 4;
+  bytes[ik + 5..ik + 9].copy_from_slice(&(-1i32).to_le_bytes());
+  let error = vmd::Motion::<C>::read(bytes.as_slice()).unwrap_err();
+  assert_eq!(
+    error.location(),
+    Some(&Location {
+      section: Section::IkKeyframes,
+      element: Some(0),
+      offset: ik as u64,
+    })
+  );
+  assert!(matches!(error.inner(), Error::NegativeCount(-1)));
+}
+
+#[test]
+fn wrong_signature() {
+  let error = vmd::Motion::<C>::read(pmd_sample(false).as_slice()).unwrap_err();
+  assert!(matches!(error.inner(), Error::WrongVmdSignature(magic) if magic.starts_with(b"Pmd")));
+}
+
+#[test]
+fn round_trip() {
+  let motion = vmd::Motion::<C>::read(vmd_sample(true).as_slice()).unwrap();
+  let (bytes, warnings) = motion.write(Vec::new()).unwrap();
+  assert!(warnings.is_empty());
+  assert_eq!(bytes.len(), vmd_sample(true).len());
+  assert_eq!(vmd::Motion::<C>::read(bytes.as_slice()).unwrap(), motion);
+}
+
+#[test]
+fn truncates_names() {
+  let mut motion = vmd::Motion::<C>::read(vmd_sample(true).as_slice()).unwrap();
+  motion.bones[0].bone_name = "右腕捩れ補助ボーン".to_string();
+  motion.iks[0].iks[1].bone_name = "left leg IK with a long name".to_string();
+  let (bytes, warnings) = motion.write(Vec::new()).unwrap();
+
+  let bones = 30 + 20 + 4;
+  assert_eq!(warnings.len(), 2);
+  assert_eq!(warnings[0].written, "右腕捩れ補助ボ");
+  assert_eq!(
+    warnings[0].location,
+    Location {
+      section: Section::BoneKeyframes,
+      element: Some(0),
+      offset: bones as u64,
+    }
+  );
+  assert_eq!(warnings[1].name, "left leg IK with a long name");
+  assert_eq!(warnings[1].written, "left leg IK with a l");
+  assert_eq!(warnings[1].location.section, Section::IkKeyframes);
+  assert_eq!(warnings[1].location.offset, bytes.len() as u64 - 21);
+
+  let written = vmd::Motion::<C>::read(bytes.as_slice()).unwrap();
+  assert_eq!(written.bones[0].bone_name, "右腕捩れ補助ボ");
+  assert_eq!(written.iks[0].iks[1].bone_name, "left leg IK with a l");
+}
+
+#[test]
+fn interpolation_layout() {
+  let mut motion = vmd::Motion::<C>::read(vmd_sample(true).as_slice()).unwrap();
+  let bone = &mut motion.bones[0];
+  let curves = [
+    [1, 2, 3, 4],
+    [5, 6, 7, 8],
+    [9, 10, 11, 12],
+    vmd::LINEAR_CURVE,
+  ];
+  bone.set_curves(curves);
+  assert_eq!(
+    bone.interpolation[0],
+    [1, 5, 9, 20, 2, 6, 10, 20, 3, 7, 11, 107, 4, 8, 12, 107]
+  );
+  assert_eq!(bone.interpolation[3][..13], bone.interpolation[0][3..]);
+  assert_eq!(bone.curves(), curves);
+
+  let camera = &mut motion.cameras[0];
+  assert_eq!(camera.curves()[0], [0, 2, 1, 3]);
+  camera.set_curves([vmd::LINEAR_CURVE; 6]);
+  assert_eq!(camera.interpolation[..4], [20, 107, 20, 107]);
+  assert_eq!(camera.curves(), [vmd::LINEAR_CURVE; 6]);
+}
+
+#[test]
+fn write_errors() {
+  let mut motion = vmd::Motion::<C>::read(vmd_sample(true).as_slice()).unwrap();
+  motion.morphs[0].morph_name = "😉".to_string();
+  let error = motion.write(Vec::new()).unwrap_err();
+  assert_eq!(error.location().unwrap().section, Section::MorphKeyframes);
+  assert!(matches!(error.inner(), Error::EncodeText(_)));
+
+  let mut bones = vmd::BoneWriter::new(vmd::HeaderWriter::new(Vec::new()), 2).unwrap();
+  bones.write(&motion.bones[0]).unwrap();
+  let error = vmd::MorphWriter::new(bones, 0).err().unwrap();
+  assert!(matches!(
+    error.inner(),
+    Error::ElementCountMismatch {
+      section: Section::BoneKeyframes,
+      expected: 2,
+      written: 1
+    }
+  ));
+}
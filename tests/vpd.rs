@@ -0,0 +1,62 @@
+mod common;
+
+use common::*;
+use mmd::vpd::Pose;
+use mmd::{DefaultConfig, Error, Model};
+
+type C = DefaultConfig;
+
+#[test]
+fn reads_sample() {
+  let pose = Pose::<C>::read(vpd_sample().as_slice()).unwrap();
+  assert_eq!(pose.model_name, "テスト.osm");
+  assert_eq!(pose.bones.len(), 2);
+  assert_eq!(pose.bones[0].bone_name, "センター");
+  assert_eq!(pose.bones[0].translation, [0.0, 1.5, -2.0].into());
+  assert_eq!(pose.bones[1].bone_name, "右足ＩＫ");
+  assert_eq!(pose.bones[1].rotation, [0.5, 0.5, -0.5, 0.5].into());
+  assert_eq!(pose.morphs[0].morph_name, "頂点");
+  assert_eq!(pose.morphs[0].weight, 0.25);
+}
+
+#[test]
+fn writes_mmd_layout() {
+  let pose = Pose::<C>::read(vpd_sample().as_slice()).unwrap();
+  let bytes = pose.write(Vec::new()).unwrap();
+  assert_eq!(bytes, vpd_sample());
+}
+
+#[test]
+fn maps_names() {
+  let model = Model::<C>::read(sample().as_slice()).unwrap();
+  let pose = Pose::<C>::read(vpd_sample().as_slice()).unwrap();
+
+  let bones = pose.map_bones(&model.bones).unwrap();
+  assert_eq!(bones.found.len(), 1);
+  assert_eq!(bones.found[0].0, 0);
+  assert_eq!(bones.found[0].1.bone_name, "センター");
+  assert_eq!(bones.missing, vec!["右足ＩＫ"]);
+
+  let morphs = pose.map_morphs(&model.morphs).unwrap();
+  assert_eq!(morphs.found[0].0, 1);
+  assert!(morphs.missing.is_empty());
+}
+
+#[test]
+fn parse_errors() {
+  assert!(Pose::<C>::parse("").is_err());
+
+  let error = |text: &str| match Pose::<C>::parse(text).unwrap_err() {
+    Error::Vpd { line, message } => (line, message),
+    e => panic!("unexpected error {}", e),
+  };
+  let (line, message) = error("Vocaloid Pose Data file\n\nm.osm;\n1;\nBone0{a\n  0,1;\n}\n");
+  assert_eq!(line, 6);
+  assert!(message.contains("translation"), "{}", message);
+
+  let (line, _) = error("Vocaloid Pose Data file\nm.osm;\n2;\nBone0{a\n0,0,0;\n0,0,0,1;\n}\n");
+  assert_eq!(line, 7);
+
+  let (line, _) = error("Vocaloid Motion Data 0002\n");
+  assert_eq!(line, 1);
+}
@@ -0,0 +1,98 @@
+mod common;
+
+use common::*;
+use mmd::pmx::bone::BoneFlags;
+use mmd::writer::{HeaderWriter, SurfaceWriter, VertexWriter};
+use mmd::{DefaultConfig, Error, IndexSize, Model, Section};
+
+fn sample_model() -> Model<DefaultConfig> {
+  Model::read(sample().as_slice()).unwrap()
+}
+
+#[test]
+fn sample_round_trips() {
+  let bytes = sample_model().write(Vec::new()).unwrap();
+  assert_eq!(bytes, sample());
+}
+
+#[test]
+fn too_few_elements() {
+  let model = sample_model();
+  let header = HeaderWriter::new(Vec::new(), 2.1, model.settings);
+  let mut vertices = VertexWriter::new(header, 2).unwrap();
+  vertices.write(&model.vertices[0]).unwrap();
+  let error = SurfaceWriter::new(vertices, 0).err().unwrap();
+  assert!(matches!(
+    error.inner(),
+    Error::ElementCountMismatch {
+      section: Section::Vertices,
+      expected: 2,
+      written: 1,
+    }
+  ));
+}
+
+#[test]
+fn too_many_elements() {
+  let model = sample_model();
+  let header = HeaderWriter::new(Vec::new(), 2.1, model.settings);
+  let mut vertices = VertexWriter::new(header, 1).unwrap();
+  vertices.write(&model.vertices[0]).unwrap();
+  assert!(matches!(
+    vertices.write(&model.vertices[1]).unwrap_err().inner(),
+    Error::ElementCountMismatch {
+      section: Section::Vertices,
+      expected: 1,
+      written: 2,
+    }
+  ));
+  assert!(matches!(
+    vertices.write(&model.vertices[1]),
+    Err(Error::Poisoned)
+  ));
+}
+
+#[test]
+fn index_does_not_fit() {
+  let mut model = sample_model();
+  model.settings.bone_index_size = IndexSize::I8;
+  model.bones[0].parent = 200;
+  let error = model.write(Vec::new()).unwrap_err();
+  assert_eq!(error.location().unwrap().section, Section::Bones);
+  assert_eq!(error.location().unwrap().element, Some(0));
+  assert!(matches!(
+    error.inner(),
+    Error::IndexDoesNotFit(ref index, IndexSize::I8) if index == "200"
+  ));
+}
+
+#[test]
+fn inconsistent_bone_flags() {
+  let mut model = sample_model();
+  model.bones[1]
+    .bone_flags
+    .remove(BoneFlags::InverseKinematics);
+  assert!(matches!(
+    model.write(Vec::new()).unwrap_err().inner(),
+    Error::InconsistentBoneFlags(_)
+  ));
+}
+
+#[test]
+fn soft_bodies_need_version_2_1() {
+  let mut model = sample_model();
+  model.version = 2.0;
+  assert!(matches!(
+    model.write(Vec::new()).unwrap_err().inner(),
+    Error::UnsupportedSection {
+      section: Section::SoftBodies,
+      ..
+    }
+  ));
+
+  model.soft_bodies.clear();
+  let bytes = model.write(Vec::new()).unwrap();
+  let read = Model::<DefaultConfig>::read(bytes.as_slice()).unwrap();
+  assert_eq!(read.joints.len(), 1);
+  assert!(read.soft_bodies.is_empty());
+}